@@ -0,0 +1,74 @@
+Feature: Cat
+  Concatenate several Parquet, Avro, or ORC files into one output file.
+
+  Scenario: Concatenate files of different formats
+    When I run `datu convert fixtures/userdata.parquet $TEMPDIR/first.avro --select id,first_name --limit 3`
+    Then the command should succeed
+    When I run `datu convert fixtures/userdata.parquet $TEMPDIR/second.parquet --select id,first_name --limit 2`
+    Then the command should succeed
+    When I run `datu cat $TEMPDIR/first.avro $TEMPDIR/second.parquet $TEMPDIR/all.csv`
+    Then the command should succeed
+    And the output should contain "Concatenating 2 files into $TEMPDIR/all.csv"
+    And the file "$TEMPDIR/all.csv" should contain:
+      """
+      id,first_name
+      1,Amanda
+      2,Albert
+      3,Evelyn
+      1,Amanda
+      2,Albert
+      """
+
+  Scenario: Strict mode rejects different schemas
+    When I run `datu convert fixtures/userdata.parquet $TEMPDIR/first.avro --select id,first_name --limit 3`
+    Then the command should succeed
+    When I run `datu convert fixtures/userdata.parquet $TEMPDIR/second.parquet --select first_name,email --limit 2`
+    Then the command should succeed
+    When I run `datu cat $TEMPDIR/first.avro $TEMPDIR/second.parquet $TEMPDIR/all.csv`
+    Then the command should fail
+    And the output should contain "use --union-by-name"
+
+  Scenario: Union by name with source file column
+    When I run `datu convert fixtures/userdata.parquet $TEMPDIR/first.avro --select id,first_name --limit 2`
+    Then the command should succeed
+    When I run `datu convert fixtures/userdata.parquet $TEMPDIR/second.parquet --select first_name,email --limit 1`
+    Then the command should succeed
+    When I run `datu cat $TEMPDIR/first.avro $TEMPDIR/second.parquet $TEMPDIR/all.csv --union-by-name --source-file`
+    Then the command should succeed
+    And the file "$TEMPDIR/all.csv" should exist
+    And the first line of that file should contain "id,first_name,email,_source_file"
+    And that file should have 4 lines
+    And that file should contain ",Amanda,ajordan0@com.com,"
+    And that file should contain "second.parquet"
+
+  Scenario: Concatenate into Parquet with selected columns
+    When I run `datu cat fixtures/userdata.parquet fixtures/userdata.parquet $TEMPDIR/double.parquet --select id`
+    Then the command should succeed
+    When I run `datu count $TEMPDIR/double.parquet`
+    Then the command should succeed
+    And the output should contain "2000"
+
+  Scenario: One sheet per input
+    When I run `datu convert fixtures/userdata.parquet $TEMPDIR/first.avro --select id,first_name --limit 3`
+    Then the command should succeed
+    When I run `datu cat $TEMPDIR/first.avro fixtures/table.parquet $TEMPDIR/report.xlsx --sheet-per-input --sheet-name Users`
+    Then the command should succeed
+    And the output should contain "Writing 2 files as sheets of $TEMPDIR/report.xlsx"
+    And the file "$TEMPDIR/report.xlsx" should exist
+
+  Scenario: One sheet per input requires an XLSX output
+    When I run `datu cat fixtures/table.parquet fixtures/table.parquet $TEMPDIR/report.csv --sheet-per-input`
+    Then the command should fail
+    And the output should contain "--sheet-per-input requires an .xlsx output file"
+
+  Scenario: More sheet names than inputs
+    When I run `datu cat fixtures/table.parquet fixtures/table.parquet $TEMPDIR/report.xlsx --sheet-per-input --sheet-name A --sheet-name B --sheet-name C`
+    Then the command should fail
+    And the output should contain "--sheet-name was given 3 times, but there are only 2 inputs"
+
+  Scenario: Refuse to overwrite an input
+    When I run `datu convert fixtures/table.parquet $TEMPDIR/first.parquet`
+    Then the command should succeed
+    When I run `datu cat fixtures/table.parquet $TEMPDIR/first.parquet $TEMPDIR/first.parquet`
+    Then the command should fail
+    And the output should contain "Output file $TEMPDIR/first.parquet is also an input; use --force to replace it"
@@ -13,13 +13,22 @@ Feature: CLI
       Usage: datu <COMMAND>
       
       Commands:
-        convert  convert between file formats
-        count    return the number of rows in a file
-        head     print the first n lines of a file
-        tail     print the last n lines of a file
-        schema   display the schema of a file
-        version  print the datu version
-        help     Print this message or the help of the given subcommand(s)
+        convert       convert between file formats
+        cat           concatenate several files into one output file
+        split         split a file into several files by rows, size, or key
+        count         return the number of rows in a file
+        freq          print the most common values or a histogram of columns
+        head          print the first n lines of a file
+        tail          print the last n lines of a file
+        schema        display the schema of a file
+        schema-check  check that a schema can evolve into another without breaking readers
+        inspect       display the file-level metadata of a file
+        validate      fully decode a file and report any damage
+        diff          compare the schema and rows of two files
+        grep          print the rows with a value matching a pattern
+        view          browse a file in a full-screen terminal viewer
+        version       print the datu version
+        help          Print this message or the help of the given subcommand(s)
       
       Options:
         -h, --help     Print help
@@ -35,13 +44,22 @@ Feature: CLI
       Usage: datu <COMMAND>
       
       Commands:
-        convert  convert between file formats
-        count    return the number of rows in a file
-        head     print the first n lines of a file
-        tail     print the last n lines of a file
-        schema   display the schema of a file
-        version  print the datu version
-        help     Print this message or the help of the given subcommand(s)
+        convert       convert between file formats
+        cat           concatenate several files into one output file
+        split         split a file into several files by rows, size, or key
+        count         return the number of rows in a file
+        freq          print the most common values or a histogram of columns
+        head          print the first n lines of a file
+        tail          print the last n lines of a file
+        schema        display the schema of a file
+        schema-check  check that a schema can evolve into another without breaking readers
+        inspect       display the file-level metadata of a file
+        validate      fully decode a file and report any damage
+        diff          compare the schema and rows of two files
+        grep          print the rows with a value matching a pattern
+        view          browse a file in a full-screen terminal viewer
+        version       print the datu version
+        help          Print this message or the help of the given subcommand(s)
       
       Options:
         -h, --help     Print help
@@ -99,6 +99,25 @@ Feature: Convert
       [{"one":-1.0,"two":"foo","three":true,"four":"2022-12-23T00:00:00Z","five":"2022-12-23T11:43:49","__index_level_0__":"a"},{"two":"bar","three":false,"four":"2021-12-23T00:00:00Z","five":"2021-12-23T12:44:50","__index_level_0__":"b"},{"one":2.5,"two":"baz","four":"2020-12-23T00:00:00Z","five":"2020-12-23T13:45:51","__index_level_0__":"c"}]
       ```
 
+  Scenario: Parquet to JSON Lines
+    When I run `datu convert fixtures/table.parquet $TEMPDIR/table.ndjson --select one,two`
+    Then the command should succeed
+    And the file "$TEMPDIR/table.ndjson" should exist
+    And that file should have 3 lines
+    And the file "$TEMPDIR/table.ndjson" should contain:
+      ```
+      {"one":-1.0,"two":"foo"}
+      {"two":"bar"}
+      {"one":2.5,"two":"baz"}
+      ```
+
+  Scenario: Parquet to JSON Lines with nulls (`.jsonl`)
+    When I run `datu convert fixtures/table.parquet $TEMPDIR/table.jsonl --select one,two --sparse=false`
+    Then the command should succeed
+    And the file "$TEMPDIR/table.jsonl" should exist
+    And the first line of that file should contain "{"one":-1.0,"two":"foo"}"
+    And that file should contain "{"one":null,"two":"bar"}"
+
   Scenario: Parquet to JSON (with `--json-pretty`)
     When I run `datu convert fixtures/table.parquet $TEMPDIR/table_sparse.json --json-pretty`
     Then the command should succeed
@@ -109,26 +128,26 @@ Feature: Convert
       ```
       [
         {
-          "__index_level_0__": "a",
-          "five": "2022-12-23T11:43:49",
-          "four": "2022-12-23T00:00:00Z",
           "one": -1.0,
+          "two": "foo",
           "three": true,
-          "two": "foo"
+          "four": "2022-12-23T00:00:00Z",
+          "five": "2022-12-23T11:43:49",
+          "__index_level_0__": "a"
         },
         {
-          "__index_level_0__": "b",
-          "five": "2021-12-23T12:44:50",
-          "four": "2021-12-23T00:00:00Z",
+          "two": "bar",
           "three": false,
-          "two": "bar"
+          "four": "2021-12-23T00:00:00Z",
+          "five": "2021-12-23T12:44:50",
+          "__index_level_0__": "b"
         },
         {
-          "__index_level_0__": "c",
-          "five": "2020-12-23T13:45:51",
-          "four": "2020-12-23T00:00:00Z",
           "one": 2.5,
-          "two": "baz"
+          "two": "baz",
+          "four": "2020-12-23T00:00:00Z",
+          "five": "2020-12-23T13:45:51",
+          "__index_level_0__": "c"
         }
       ]
       ```
@@ -148,21 +167,21 @@ Feature: Convert
     And the file "$TEMPDIR/table.yaml" should be valid YAML
     And the file "$TEMPDIR/table.yaml" should contain:
       ```
-      - one: -1
+      - one: -1.0
         two: foo
         three: true
-        four: "2022-12-23T00:00:00Z"
-        five: "2022-12-23T11:43:49"
+        four: 2022-12-23T00:00:00Z
+        five: 2022-12-23T11:43:49
         __index_level_0__: a
       - two: bar
         three: false
-        four: "2021-12-23T00:00:00Z"
-        five: "2021-12-23T12:44:50"
+        four: 2021-12-23T00:00:00Z
+        five: 2021-12-23T12:44:50
         __index_level_0__: b
       - one: 2.5
         two: baz
-        four: "2020-12-23T00:00:00Z"
-        five: "2020-12-23T13:45:51"
+        four: 2020-12-23T00:00:00Z
+        five: 2020-12-23T13:45:51
         __index_level_0__: c
       ```
 
@@ -181,7 +200,7 @@ Feature: Convert
     And the output should contain "Converting fixtures/table.parquet to $TEMPDIR/table_no_sparse.yaml"
     And the file "$TEMPDIR/table_no_sparse.yaml" should exist
     And that file should be valid YAML
-    And that file should contain "one:"
+    And that file should contain "one: null"
 
   Scenario: Avro to YAML
     When I run `datu convert fixtures/userdata5.avro $TEMPDIR/userdata5.yaml`
@@ -284,3 +303,162 @@ Feature: Convert
     Then the command should succeed
     And the output should contain "Converting fixtures/table.parquet to $TEMPDIR/table_limit.xlsx"
     And the file "$TEMPDIR/table_limit.xlsx" should exist
+
+  Scenario: Parquet to XLSX with a sheet name
+    When I run `datu convert fixtures/table.parquet $TEMPDIR/sales.xlsx --sheet-name Sales`
+    Then the command should succeed
+    And the file "$TEMPDIR/sales.xlsx" should exist
+
+  Scenario: XLSX with an invalid sheet name
+    When I run `datu convert fixtures/table.parquet $TEMPDIR/bad_sheet.xlsx --sheet-name Q1/Q2`
+    Then the command should fail
+    And the output should contain "Q1/Q2"
+
+  Scenario: Parquet to Markdown
+    When I run `datu convert fixtures/userdata.parquet $TEMPDIR/sample.md --select id,first_name --limit 3`
+    Then the command should succeed
+    And the file "$TEMPDIR/sample.md" should exist
+    And the first line of that file should contain "|   id | first_name |"
+    And that file should have 5 lines
+
+  Scenario: Avro to HTML
+    When I run `datu convert fixtures/userdata5.avro $TEMPDIR/sample.html --select id,first_name --limit 3`
+    Then the command should succeed
+    And the file "$TEMPDIR/sample.html" should exist
+    And the first line of that file should contain "<!DOCTYPE html>"
+    And that file should contain "<title>sample</title>"
+    And that file should contain "<p class="rows">3 rows</p>"
+
+  Scenario: Avro to Parquet with compression and row group options
+    When I run `datu convert fixtures/userdata5.avro $TEMPDIR/tuned.parquet --compression zstd --compression-level 9 --max-row-group-size 300 --bloom-filter-columns id --sorting-columns id --writer-version v2`
+    Then the command should succeed
+    And the file "$TEMPDIR/tuned.parquet" should exist
+    When I run `datu count $TEMPDIR/tuned.parquet`
+    Then the command should succeed
+    And the output should contain "1000"
+    When I run `datu head $TEMPDIR/tuned.parquet -n 1 --select id,first_name`
+    Then the command should succeed
+    And the output should contain "1,Kelly"
+
+  Scenario: Parquet output with an unknown compression codec
+    When I run `datu convert fixtures/userdata5.avro $TEMPDIR/bad.parquet --compression lzo`
+    Then the command should fail
+    And the output should contain "unknown compression 'lzo'"
+
+  Scenario: Parquet output with a bloom filter on an unknown column
+    When I run `datu convert fixtures/userdata5.avro $TEMPDIR/bad.parquet --bloom-filter-columns nope`
+    Then the command should fail
+    And the output should contain "Column 'nope' not found in the Parquet schema"
+
+  Scenario: Avro output with a codec and a writer schema
+    When I run `datu convert fixtures/userdata.parquet $TEMPDIR/registered.avro --avro-codec snappy --avro-schema fixtures/userdata_export.avsc`
+    Then the command should succeed
+    When I run `datu inspect $TEMPDIR/registered.avro`
+    Then the command should succeed
+    And the output should contain "Codec: snappy"
+    And the output should contain ""name": "Registration""
+    And the output should contain ""namespace": "com.example.users""
+    When I run `datu schema $TEMPDIR/registered.avro`
+    Then the command should succeed
+    And the output should contain "registration_dttm: Timestamp(ms, "+00:00")"
+
+  Scenario: Avro output with a writer schema naming a missing column
+    When I run `datu convert fixtures/userdata.parquet $TEMPDIR/bad.avro --avro-schema fixtures/user_v1.avsc`
+    Then the command should fail
+    And the output should contain "Field 'name' of fixtures/user_v1.avsc is not a column of the input"
+
+  Scenario: Avro input resolved against a newer reader schema
+    When I run `datu convert fixtures/userdata5.avro $TEMPDIR/resolved.csv --avro-schema fixtures/userdata_v2.avsc --limit 2`
+    Then the command should succeed
+    And the file "$TEMPDIR/resolved.csv" should exist
+    And the first line of that file should contain "id,first_name,last_name,salary,nickname"
+
+  Scenario: ORC output with compression and stripe options
+    When I run `datu convert fixtures/userdata.parquet $TEMPDIR/hive.orc --select id,first_name,salary --orc-compression zlib --orc-stripe-size 8KB --orc-batch-size 200`
+    Then the command should succeed
+    When I run `datu inspect $TEMPDIR/hive.orc`
+    Then the command should succeed
+    And the output should contain "Compression: ZLIB"
+    And the output should contain "Compression block size: 262144"
+    When I run `datu tail $TEMPDIR/hive.orc -n 1`
+    Then the command should succeed
+    And the output should contain "1000,Julie,222561.13"
+
+  Scenario: ORC compression block size without compression
+    When I run `datu convert fixtures/userdata.parquet $TEMPDIR/bad.orc --select id --orc-compression-block-size 64KB`
+    Then the command should fail
+    And the output should contain "--orc-compression-block-size requires --orc-compression"
+
+  Scenario: ORC output with an unknown compression codec
+    When I run `datu convert fixtures/userdata.parquet $TEMPDIR/bad.orc --orc-compression gzip`
+    Then the command should fail
+    And the output should contain "unknown ORC compression 'gzip'"
+
+  Scenario: Parquet to TSV
+    When I run `datu convert fixtures/table.parquet $TEMPDIR/table.tsv --select two,three`
+    Then the command should succeed
+    And the file "$TEMPDIR/table.tsv" should exist
+    And that file should contain `two\tthree\nfoo\ttrue\n`
+
+  Scenario: CSV for Excel with a delimiter, quoting, CRLF, and a byte order mark
+    When I run `datu convert fixtures/table.parquet $TEMPDIR/excel.csv --select one,two --delimiter ; --quote-style always --line-terminator crlf --bom`
+    Then the command should succeed
+    And the file "$TEMPDIR/excel.csv" should exist
+    And that file should contain `\u{feff}\"one\";\"two\"\r\n\"-1.0\";\"foo\"\r\n`
+
+  Scenario: CSV without a header and with a null value
+    When I run `datu convert fixtures/table.parquet $TEMPDIR/nulls.csv --select one,two --no-header --null-value NULL`
+    Then the command should succeed
+    And the file "$TEMPDIR/nulls.csv" should exist
+    And the first line of that file should contain "-1.0,foo"
+    And that file should contain "NULL,bar"
+
+  Scenario: CSV with a timestamp format
+    When I run `datu convert fixtures/userdata.parquet $TEMPDIR/dates.csv --select id,registration_dttm --limit 1 --timestamp-format %d/%m/%Y`
+    Then the command should succeed
+    And the file "$TEMPDIR/dates.csv" should exist
+    And that file should contain "1,03/02/2016"
+
+  Scenario: CSV with an invalid delimiter
+    When I run `datu convert fixtures/table.parquet $TEMPDIR/bad.csv --delimiter ab`
+    Then the command should fail
+    And the output should contain "invalid delimiter 'ab'"
+
+  Scenario: Refuse to overwrite the input
+    When I run `datu convert fixtures/table.parquet $TEMPDIR/table.parquet`
+    Then the command should succeed
+    When I run `datu convert $TEMPDIR/table.parquet $TEMPDIR/table.parquet --select one`
+    Then the command should fail
+    And the output should contain "is also an input; use --force to replace it"
+    When I run `datu count $TEMPDIR/table.parquet`
+    Then the command should succeed
+    And the output should contain "3"
+
+  Scenario: Overwrite the input with --force
+    When I run `datu convert fixtures/table.parquet $TEMPDIR/table.parquet`
+    Then the command should succeed
+    When I run `datu convert $TEMPDIR/table.parquet $TEMPDIR/table.parquet --select one --limit 2 --force`
+    Then the command should succeed
+    When I run `datu head $TEMPDIR/table.parquet`
+    Then the command should succeed
+    And the first line should contain "one"
+    And the output should have a header and 2 lines
+
+  Scenario: Refuse to replace an existing file with --no-clobber
+    When I run `datu convert fixtures/table.parquet $TEMPDIR/table.csv --select one`
+    Then the command should succeed
+    When I run `datu convert fixtures/table.parquet $TEMPDIR/table.csv --no-clobber`
+    Then the command should fail
+    And the output should contain "Output file $TEMPDIR/table.csv already exists"
+
+  Scenario: A failed write keeps the existing file
+    When I run `datu convert fixtures/table.parquet $TEMPDIR/table.csv --select one --limit 1`
+    Then the command should succeed
+    When I run `datu convert fixtures/table.parquet $TEMPDIR/table.csv --date-format %Q`
+    Then the command should fail
+    And the file "$TEMPDIR/table.csv" should contain:
+      """
+      one
+      -1.0
+      """
+    And the file "$TEMPDIR/table.csv" should have 2 lines
@@ -17,3 +17,39 @@ Feature: Count
     When I run `datu count $TEMPDIR/userdata5.orc`
     Then the command should succeed
     And the output should contain "10"
+
+  Scenario: Count Parquet from the footer
+    When I run `datu count fixtures/userdata.parquet`
+    Then the command should succeed
+    And the output should contain "1000"
+
+  Scenario: Count ORC from the footer
+    When I run `datu count fixtures/userdata.orc`
+    Then the command should succeed
+    And the output should contain "1000"
+
+  Scenario: Count with --output json
+    When I run `datu count fixtures/userdata5.avro -o json`
+    Then the command should succeed
+    And the output should contain "{"count":1000}"
+
+  Scenario: Count by a column
+    When I run `datu count fixtures/userdata.parquet --by gender`
+    Then the command should succeed
+    And the output should be:
+      """
+      gender,count
+      Female,482
+      Male,451
+      ,67
+      """
+
+  Scenario: Count by several columns with --top
+    When I run `datu count fixtures/userdata.parquet --by country,gender --top 2 -o json`
+    Then the command should succeed
+    And the output should contain "[{"country":"China","gender":"Female","count":89},{"country":"China","gender":"Male","count":88}]"
+
+  Scenario: Count by an unknown column
+    When I run `datu count fixtures/userdata.parquet --by nope`
+    Then the command should fail
+    And the output should contain "Column 'nope' not found"
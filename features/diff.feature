@@ -0,0 +1,68 @@
+Feature: Diff
+  Compare the schema and rows of two Parquet, Avro, or ORC files.
+
+  Scenario: Diff a file with itself
+    When I run `datu diff fixtures/table.parquet fixtures/table.parquet`
+    Then the command should succeed
+    And the output should contain "Schema: 0 added, 0 removed, 0 retyped"
+    And the output should contain "Rows: 3 -> 3"
+    And the output should contain "Files are identical"
+
+  Scenario: Diff files with different rows and columns
+    When I run `datu convert fixtures/userdata.parquet $TEMPDIR/left.parquet --limit 10 --select id,first_name,last_name,email`
+    Then the command should succeed
+    When I run `datu convert fixtures/userdata.parquet $TEMPDIR/right.avro --limit 12 --select id,first_name,last_name,country`
+    Then the command should succeed
+    When I run `datu diff $TEMPDIR/left.parquet $TEMPDIR/right.avro`
+    Then the command should fail
+    And the output should contain "Schema: 1 added, 1 removed, 0 retyped"
+    And the output should contain "+ country: Utf8"
+    And the output should contain "- email: Utf8"
+    And the output should contain "Rows: 10 -> 12"
+    And the output should contain "Rows matched by position"
+    And the output should contain "Added rows: 2"
+    And the output should contain "Removed rows: 0"
+    And the output should contain "Changed rows: 0"
+
+  Scenario: Diff files by key with --output json
+    When I run `datu convert fixtures/userdata.parquet $TEMPDIR/left.parquet --limit 10 --select id,first_name`
+    Then the command should succeed
+    When I run `datu convert fixtures/userdata.parquet $TEMPDIR/right.orc --limit 12 --select id,first_name`
+    Then the command should succeed
+    When I run `datu diff $TEMPDIR/left.parquet $TEMPDIR/right.orc --key id --output json`
+    Then the command should fail
+    And the output should be valid JSON
+    And the output should contain ""key":["id"]"
+    And the output should contain ""added_rows":[{"id":"11","first_name":"Susan"},{"id":"12","first_name":"Alice"}]"
+
+  Scenario: Diff files by key with changed values
+    When I run `datu convert fixtures/userdata.parquet $TEMPDIR/left.parquet --limit 3 --select id,first_name`
+    Then the command should succeed
+    When I run `datu convert fixtures/userdata5.avro $TEMPDIR/right.avro --limit 3 --select id,first_name`
+    Then the command should succeed
+    When I run `datu diff $TEMPDIR/left.parquet $TEMPDIR/right.avro --key id`
+    Then the command should fail
+    And the output should contain "~ id: Int32 -> Int64"
+    And the output should contain "Changed rows: 3"
+    And the output should contain "first_name: 3 changed"
+
+  Scenario: Diff files by key with --output yaml
+    When I run `datu convert fixtures/userdata.parquet $TEMPDIR/left.parquet --limit 3 --select id,first_name`
+    Then the command should succeed
+    When I run `datu convert fixtures/userdata5.avro $TEMPDIR/right.avro --limit 3 --select id,first_name`
+    Then the command should succeed
+    When I run `datu diff $TEMPDIR/left.parquet $TEMPDIR/right.avro --key id -o yaml`
+    Then the command should fail
+    And the output should be valid YAML
+    And the output should contain "left: Amanda"
+    And the output should contain "right: Kelly"
+
+  Scenario: Diff with a missing key column
+    When I run `datu diff fixtures/table.parquet fixtures/table.parquet --key nope`
+    Then the command should fail
+    And the output should contain "Key column 'nope' must be present in both files"
+
+  Scenario: Diff rejects table output formats
+    When I run `datu diff fixtures/table.parquet fixtures/table.parquet --output markdown`
+    Then the command should fail
+    And the output should contain "unknown output type 'markdown', expected text, json, json-pretty, or yaml"
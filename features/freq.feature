@@ -0,0 +1,55 @@
+Feature: Freq
+  Print the most common values of columns, or histograms of numeric and temporal columns.
+
+  Scenario: Freq of a categorical column
+    When I run `datu freq fixtures/userdata.parquet --columns gender`
+    Then the command should succeed
+    And the output should be:
+      """
+      column,value,count,percent
+      gender,Female,482,48.2
+      gender,Male,451,45.1
+      gender,,67,6.7
+      """
+
+  Scenario: Freq with --top sums up the other values
+    When I run `datu freq fixtures/userdata.parquet --columns country --top 2`
+    Then the command should succeed
+    And the output should contain "country,China,189,18.9"
+    And the output should contain "country,Indonesia,97,9.7"
+    And the output should contain "country,(other),714,71.4"
+
+  Scenario: Freq of an integer column as a histogram
+    When I run `datu freq fixtures/userdata.parquet --columns id --bins 4`
+    Then the command should succeed
+    And the output should contain "id,"[1, 250]",250,25.0"
+    And the output should contain "id,"[751, 1000]",250,25.0"
+
+  Scenario: Freq of a timestamp column rounds buckets to whole time units
+    When I run `datu freq fixtures/userdata.parquet --columns registration_dttm --bins 4 -o json`
+    Then the command should succeed
+    And the output should contain ""value":"[2016-02-03T00:00:00, 2016-02-03T06:00:00)""
+
+  Scenario: Freq of a float column with nulls
+    When I run `datu freq fixtures/userdata5.avro --columns salary --bins 3`
+    Then the command should succeed
+    And the output should contain "salary,"[12182, 103583)",321,32.1"
+    And the output should contain "salary,"[194983, 286384]",303,30.3"
+    And the output should contain "salary,,54,5.4"
+
+  Scenario: Freq with --categorical counts numeric values
+    When I run `datu freq fixtures/userdata.parquet --columns id --categorical --top 1`
+    Then the command should succeed
+    And the output should contain "id,1,1,0.1"
+    And the output should contain "id,(other),999,99.9"
+
+  Scenario: Freq with --bars and --output table
+    When I run `datu freq fixtures/userdata.parquet --columns gender --bars -o table`
+    Then the command should succeed
+    And the output should contain "│ column │ value  │ count │ percent │ bar"
+    And the output should contain "│ gender │ Female │   482 │    48.2 │ ████████████████████████████████████████ │"
+
+  Scenario: Freq of an unknown column
+    When I run `datu freq fixtures/userdata.parquet --columns nope`
+    Then the command should fail
+    And the output should contain "Column 'nope' not found"
@@ -0,0 +1,58 @@
+Feature: Grep
+  Print the rows of Parquet, Avro, or ORC files with a value matching a pattern.
+
+  Scenario: Grep a Parquet file with row numbers
+    When I run `datu grep -n Evelyn fixtures/userdata.parquet --select first_name,last_name`
+    Then the command should succeed
+    And the output should be:
+      ```
+      _row,first_name,last_name
+      3,Evelyn,Morgan
+      123,Evelyn,Sims
+      794,Evelyn,Gutierrez
+      870,Evelyn,Wood
+      919,Evelyn,Spencer
+      ```
+
+  Scenario: Grep selected columns with a regular expression
+    When I run `datu grep -c ^Eve fixtures/userdata.parquet --columns first_name`
+    Then the command should succeed
+    And the output should be:
+      ```
+      5
+      ```
+
+  Scenario: Count matches ignoring case
+    When I run `datu grep --count -i EVELYN fixtures/userdata.parquet`
+    Then the command should succeed
+    And the output should be:
+      ```
+      5
+      ```
+
+  Scenario: Count matches in several files
+    When I run `datu grep -c -i evelyn fixtures/userdata.parquet fixtures/userdata5.avro`
+    Then the command should succeed
+    And the output should contain "fixtures/userdata.parquet:5"
+    And the output should contain "fixtures/userdata5.avro:7"
+
+  Scenario: Grep several files shows the source file
+    When I run `datu convert fixtures/userdata.parquet $TEMPDIR/copy.avro --select id,email`
+    Then the command should succeed
+    When I run `datu grep -F ajordan0@com.com fixtures/userdata.parquet $TEMPDIR/copy.avro --select id,email -o json`
+    Then the command should succeed
+    And the output should be valid JSON
+    And the output should contain "userdata.parquet"
+    And the output should contain "copy.avro"
+
+  Scenario: Invert match
+    When I run `datu grep -v -c . fixtures/userdata.parquet --columns first_name`
+    Then the command should succeed
+    And the output should be:
+      ```
+      16
+      ```
+
+  Scenario: No matches
+    When I run `datu grep no-such-value fixtures/userdata.parquet`
+    Then the command should fail
@@ -123,3 +123,63 @@ Feature: Head
     And the output should be valid YAML
     And the output should contain "first_name"
     And the output should contain "email"
+
+  Scenario: Head Parquet with --output table
+    When I run `datu head fixtures/userdata.parquet -n 2 --output table --select id,first_name,salary`
+    Then the command should succeed
+    And the output should be:
+      """
+      ┌────┬────────────┬───────────┐
+      │ id │ first_name │ salary    │
+      ├────┼────────────┼───────────┤
+      │  1 │ Amanda     │  49756.53 │
+      │  2 │ Albert     │ 150280.17 │
+      └────┴────────────┴───────────┘
+      """
+
+  Scenario: Head Parquet with --output table and --max-col-width
+    When I run `datu head fixtures/userdata.parquet -n 1 -o table --select id,email --max-col-width 8`
+    Then the command should succeed
+    And the output should contain "│  1 │ ajordan… │"
+
+  Scenario: Head Parquet with --output markdown
+    When I run `datu head fixtures/userdata.parquet -n 2 --output markdown --select id,first_name`
+    Then the command should succeed
+    And the output should be:
+      """
+      |   id | first_name |
+      | ---: | ---------- |
+      |    1 | Amanda     |
+      |    2 | Albert     |
+      """
+
+  Scenario: Head Parquet with --output html
+    When I run `datu head fixtures/userdata.parquet -n 2 -o html --select id,first_name`
+    Then the command should succeed
+    And the output should contain "<!DOCTYPE html>"
+    And the output should contain "<tr><td class="num">1</td><td>Amanda</td></tr>"
+
+  Scenario: Head Parquet with --output csv in a custom dialect
+    When I run `datu head fixtures/table.parquet -n 2 --output csv --select one,two --delimiter | --null-value NULL`
+    Then the command should succeed
+    And the output should contain "NULL|bar"
+
+  Scenario: Head Parquet with --output jsonl
+    When I run `datu head fixtures/table.parquet -n 2 --output jsonl --select one,two`
+    Then the command should succeed
+    And the output should be:
+      ```
+      {"one":-1.0,"two":"foo"}
+      {"two":"bar"}
+      ```
+
+  Scenario: Head Parquet with --output yaml keeps value types
+    When I run `datu head fixtures/userdata.parquet -n 1 --output yaml --select id,cc,salary,registration_dttm`
+    Then the command should succeed
+    And the output should be:
+      ```
+      - id: 1
+        cc: "6759521864920116"
+        salary: 49756.53
+        registration_dttm: 2016-02-03T07:55:29
+      ```
@@ -0,0 +1,69 @@
+Feature: Inspect
+  Display the file-level metadata of an Avro or ORC file.
+
+  Scenario: Inspect Avro default (text output)
+    When I run `datu inspect fixtures/userdata5.avro`
+    Then the command should succeed
+    And the output should contain "Codec: snappy"
+    And the output should contain "Sync marker: ef9a8adf5ea6ac53e03f2999fdceda81"
+    And the output should contain "Records: 1000"
+    And the output should contain "Blocks: 3"
+    And the output should contain "0: offset=1157, records=487, compressed_bytes=43607"
+    And the output should contain ""name": "kylosample""
+    And the output should contain ""doc": "Schema generated by Kite""
+
+  Scenario: Inspect Avro with --output json
+    When I run `datu inspect fixtures/userdata5.avro --output json`
+    Then the command should succeed
+    And the output should be valid JSON
+    And the output should contain ""writer_schema":{"type":"record""
+
+  Scenario: Inspect Avro with --output yaml
+    When I run `datu inspect fixtures/userdata5.avro -o yaml`
+    Then the command should succeed
+    And the output should be valid YAML
+    And the output should contain "number_of_blocks: 3"
+    And the output should contain "name: kylosample"
+
+  Scenario: Inspect Avro written by datu
+    When I run `datu convert fixtures/table.parquet $TEMPDIR/table.avro`
+    Then the command should succeed
+    When I run `datu inspect $TEMPDIR/table.avro`
+    Then the command should succeed
+    And the output should contain "Records: 3"
+
+  Scenario: Inspect ORC default (text output)
+    When I run `datu inspect fixtures/userdata.orc`
+    Then the command should succeed
+    And the output should contain "File format version: 0.12"
+    And the output should contain "Compression: ZLIB"
+    And the output should contain "Rows: 1000"
+    And the output should contain "0: offset=3, rows=1000"
+    And the output should contain "_col1 INTEGER, values=1000, has_null=false, min=1, max=1000, sum=500500"
+
+  Scenario: Inspect ORC with --output json
+    When I run `datu inspect fixtures/userdata.orc --output json`
+    Then the command should succeed
+    And the output should be valid JSON
+    And the output should contain "stripes"
+    And the output should contain "column_statistics"
+    And the output should contain "writer_version"
+
+  Scenario: Inspect ORC with --output yaml
+    When I run `datu inspect fixtures/userdata.orc -o yaml`
+    Then the command should succeed
+    And the output should be valid YAML
+    And the output should contain "compression_block_size: 262144"
+
+  Scenario: Inspect ORC written by datu
+    When I run `datu convert fixtures/userdata5.avro $TEMPDIR/userdata5.orc --select id,first_name --limit 10`
+    Then the command should succeed
+    When I run `datu inspect $TEMPDIR/userdata5.orc`
+    Then the command should succeed
+    And the output should contain "Rows: 10"
+    And the output should contain "Stripes: 1"
+
+  Scenario: Inspect rejects table output formats
+    When I run `datu inspect fixtures/userdata.orc --output csv`
+    Then the command should fail
+    And the output should contain "unknown output type 'csv', expected text, json, json-pretty, or yaml"
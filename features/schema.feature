@@ -64,3 +64,103 @@ Feature: Schema
     Then the command should succeed
     And the output should contain "id"
     And the output should contain "first_name"
+
+  Scenario: Schema Avro with --output avsc keeps the writer schema
+    When I run `datu schema fixtures/userdata5.avro --output avsc`
+    Then the command should succeed
+    And the output should be valid JSON
+    And the output should contain ""name": "kylosample""
+    And the output should contain "Type inferred from"
+
+  Scenario: Schema Parquet with --output avsc and --name
+    When I run `datu schema fixtures/userdata.parquet -o avsc --name User`
+    Then the command should succeed
+    And the output should be valid JSON
+    And the output should contain ""name": "User""
+    And the output should contain ""logicalType": "local-timestamp-nanos""
+
+  Scenario: Schema Parquet with --output json-schema
+    When I run `datu schema fixtures/userdata.parquet -o json-schema`
+    Then the command should succeed
+    And the output should be valid JSON
+    And the output should contain ""title": "userdata""
+    And the output should contain "https://json-schema.org/draft/2020-12/schema"
+
+  Scenario: Schema Parquet with --output sql
+    When I run `datu schema fixtures/userdata.parquet -o sql`
+    Then the command should succeed
+    And the output should be:
+      """
+      CREATE TABLE userdata (
+        registration_dttm TIMESTAMP,
+        id INTEGER,
+        first_name TEXT,
+      """
+    And the output should contain "salary DOUBLE PRECISION,"
+
+  Scenario: Schema Parquet with --output sql --dialect bigquery
+    When I run `datu schema fixtures/userdata.parquet -o sql --dialect bigquery --name users`
+    Then the command should succeed
+    And the output should contain "CREATE TABLE users ("
+    And the output should contain "registration_dttm DATETIME,"
+    And the output should contain "salary FLOAT64,"
+
+  Scenario: Schema with an unknown SQL dialect
+    When I run `datu schema fixtures/userdata.parquet -o sql --dialect oracle`
+    Then the command should fail
+    And the output should contain "unknown SQL dialect"
+
+  Scenario: Schema Avro with --output proto
+    When I run `datu schema fixtures/userdata5.avro -o proto`
+    Then the command should succeed
+    And the output should contain "syntax = "proto3";"
+    And the output should contain "message Userdata5 {"
+    And the output should contain "int64 id = 2;"
+
+  Scenario: Schema Parquet with --output arrow
+    When I run `datu schema fixtures/userdata.parquet -o arrow`
+    Then the command should succeed
+    And the output should contain "registration_dttm"
+
+  Scenario: Schema Parquet with --output table
+    When I run `datu schema fixtures/table.parquet --output table`
+    Then the command should succeed
+    And the output should contain "│ name              │ data_type │ converted_type │ nullable │"
+    And the output should contain "│ two               │ String    │ UTF8           │ true     │"
+    And the output should contain "│ one               │ DOUBLE    │ ∅              │ true     │"
+
+  Scenario: Schema Parquet with --output markdown
+    When I run `datu schema fixtures/table.parquet -o markdown`
+    Then the command should succeed
+    And the output should contain "| name              | data_type | converted_type | nullable |"
+    And the output should contain "| two               | String    | UTF8           | true     |"
+
+  Scenario: Schema Parquet with --tree
+    When I run `datu schema fixtures/table.parquet --tree`
+    Then the command should succeed
+    And the output should contain "fixtures/table.parquet"
+    And the output should contain "├── one: DOUBLE, optional"
+    And the output should contain "├── two: BYTE_ARRAY / String, optional"
+    And the output should contain "└── __index_level_0__: BYTE_ARRAY / String, optional"
+
+  Scenario: Schema Avro with --tree
+    When I run `datu schema fixtures/userdata5.avro --tree`
+    Then the command should succeed
+    And the output should contain "├── id: Int64, required"
+    And the output should contain "├── first_name: Utf8, required"
+
+  Scenario: Schema Parquet with --tree and --output json
+    When I run `datu schema fixtures/table.parquet --tree -o json`
+    Then the command should succeed
+    And the output should contain "{"name":"two","data_type":"BYTE_ARRAY","logical_type":"String","converted_type":"UTF8","repetition":"optional"}"
+
+  Scenario: Schema ORC with --tree and --output yaml
+    When I run `datu schema fixtures/userdata.orc --tree -o yaml`
+    Then the command should succeed
+    And the output should contain "name: _col0"
+    And the output should contain "repetition: optional"
+
+  Scenario: Schema with --tree and --output table
+    When I run `datu schema fixtures/table.parquet --tree -o table`
+    Then the command should fail
+    And the output should contain "--tree is only supported with --output csv, json, json-pretty, jsonl, or yaml"
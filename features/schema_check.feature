@@ -0,0 +1,61 @@
+Feature: Schema check
+  Check the compatibility of two schemas using Avro schema evolution rules.
+
+  Scenario: Backward compatible change
+    When I run `datu schema-check fixtures/user_v1.avsc fixtures/user_v2.avsc`
+    Then the command should succeed
+    And the output should contain "Schemas are compatible (backward)"
+
+  Scenario: Widened type is not forward compatible
+    When I run `datu schema-check fixtures/user_v1.avsc fixtures/user_v2.avsc --mode forward`
+    Then the command should fail
+    And the output should contain "Found 1 breaking change(s) (forward):"
+    And the output should contain "[forward] id: new type long cannot be read as old type int"
+
+  Scenario: Full compatibility lists changes in both directions
+    When I run `datu schema-check fixtures/user_v2.avsc fixtures/user_v3.avsc --mode full`
+    Then the command should fail
+    And the output should contain "[backward] nickname: is nullable in the old schema but not in the new schema"
+    And the output should contain "[forward] name: is missing from the new schema and has no default in the old schema"
+
+  Scenario: Added field without a default
+    When I run `datu schema-check fixtures/user_v1.avsc fixtures/user_v3.avsc --mode backward`
+    Then the command should fail
+    And the output should contain "[backward] email: is missing from the old schema and has no default in the new schema"
+
+  Scenario: Schema check with --output json
+    When I run `datu schema-check fixtures/user_v2.avsc fixtures/user_v3.avsc --mode forward --output json`
+    Then the command should fail
+    And the output should be valid JSON
+    And the output should contain ""compatible":false"
+    And the output should contain ""path":"name""
+
+  Scenario: Schema check with --output yaml
+    When I run `datu schema-check fixtures/user_v1.avsc fixtures/user_v2.avsc -o yaml`
+    Then the command should succeed
+    And the output should be valid YAML
+    And the output should contain "compatible: true"
+
+  Scenario: Schema check between data files
+    When I run `datu convert fixtures/userdata.parquet $TEMPDIR/userdata.avro`
+    Then the command should succeed
+    When I run `datu schema-check fixtures/userdata.parquet $TEMPDIR/userdata.avro --mode full`
+    Then the command should succeed
+    And the output should contain "Schemas are compatible (full)"
+
+  Scenario: Schema check after dropping columns
+    When I run `datu convert fixtures/userdata.parquet $TEMPDIR/subset.parquet --select id,first_name`
+    Then the command should succeed
+    When I run `datu schema-check fixtures/userdata.parquet $TEMPDIR/subset.parquet --mode forward`
+    Then the command should fail
+    And the output should contain "[forward] email: is missing from the new schema and has no default in the old schema"
+
+  Scenario: Schema check with an invalid mode
+    When I run `datu schema-check fixtures/user_v1.avsc fixtures/user_v2.avsc --mode sideways`
+    Then the command should fail
+    And the output should contain "unknown compatibility mode 'sideways'"
+
+  Scenario: Schema check rejects table output formats
+    When I run `datu schema-check fixtures/user_v1.avsc fixtures/user_v2.avsc -o html`
+    Then the command should fail
+    And the output should contain "unknown output type 'html', expected text, json, json-pretty, or yaml"
@@ -0,0 +1,63 @@
+Feature: Split
+  Split a Parquet, Avro, or ORC file into several output files by rows, size, or key.
+
+  Scenario: Split by rows per file
+    When I run `datu split fixtures/userdata.parquet $TEMPDIR/out/part-{n:03}.csv --rows-per-file 400`
+    Then the command should succeed
+    And the output should contain "Splitting fixtures/userdata.parquet into $TEMPDIR/out/part-{n:03}.csv"
+    And the output should contain "Wrote 3 files"
+    And the file "$TEMPDIR/out/part-000.csv" should have 401 lines
+    And the file "$TEMPDIR/out/part-001.csv" should have 401 lines
+    And the file "$TEMPDIR/out/part-002.csv" should have 201 lines
+
+  Scenario: Split by size
+    When I run `datu split fixtures/userdata5.avro $TEMPDIR/part-{n}.parquet --max-bytes 100KB`
+    Then the command should succeed
+    And the file "$TEMPDIR/part-0.parquet" should exist
+    And the file "$TEMPDIR/part-1.parquet" should exist
+    When I run `datu count $TEMPDIR/part-0.parquet`
+    Then the command should succeed
+
+  Scenario: Split by size counts the bytes written
+    When I run `datu split fixtures/userdata.parquet $TEMPDIR/chunk-{n}.csv --max-bytes 50KB`
+    Then the command should succeed
+    And the output should contain "Wrote 3 files"
+    And the file "$TEMPDIR/chunk-0.csv" should be at most 50000 bytes
+    And the file "$TEMPDIR/chunk-1.csv" should be at most 50000 bytes
+    And the file "$TEMPDIR/chunk-2.csv" should be at most 50000 bytes
+
+  Scenario: Split by column value
+    When I run `datu split fixtures/userdata.parquet $TEMPDIR/{key}.csv --by gender --select id,first_name`
+    Then the command should succeed
+    And the output should contain "Wrote 3 files"
+    And the file "$TEMPDIR/Female.csv" should have a first line containing "id,first_name"
+    And the file "$TEMPDIR/Male.csv" should exist
+    And the file "$TEMPDIR/_empty.csv" should exist
+
+  Scenario: Split by column value with a limit on open files
+    When I run `datu cat fixtures/userdata.parquet fixtures/userdata.parquet fixtures/userdata.parquet $TEMPDIR/big.parquet`
+    Then the command should succeed
+    When I run `datu split $TEMPDIR/big.parquet $TEMPDIR/out/{key}-{n}.parquet --by gender --max-open-files 2`
+    Then the command should succeed
+    And the output should contain "Wrote 9 files"
+    And the file "$TEMPDIR/out/Female-0.parquet" should exist
+    And the file "$TEMPDIR/out/Female-3.parquet" should exist
+    When I run `datu split $TEMPDIR/big.parquet $TEMPDIR/keys/{key}.csv --by gender --max-open-files 2`
+    Then the command should fail
+    And the output should contain "Rows with gender 'Female' came after its file was closed to stay within --max-open-files 2; raise the limit or add {n} to the output template"
+
+  Scenario: Template without a part number
+    When I run `datu split fixtures/userdata.parquet $TEMPDIR/part.csv --rows-per-file 10`
+    Then the command should fail
+    And the output should contain "must contain {n}"
+
+  Scenario: A split mode is required
+    When I run `datu split fixtures/userdata.parquet $TEMPDIR/part-{n}.csv`
+    Then the command should fail
+
+  Scenario: Refuse to replace existing parts with --no-clobber
+    When I run `datu split fixtures/userdata.parquet $TEMPDIR/out/part-{n}.csv --rows-per-file 400`
+    Then the command should succeed
+    When I run `datu split fixtures/userdata.parquet $TEMPDIR/out/part-{n}.csv --rows-per-file 400 --no-clobber`
+    Then the command should fail
+    And the output should contain "Output file $TEMPDIR/out/part-0.csv already exists"
@@ -123,3 +123,9 @@ Feature: Tail
     And the output should be valid YAML
     And the output should contain "id"
     And the output should contain "email"
+
+  Scenario: Tail Avro with --output table
+    When I run `datu tail fixtures/userdata5.avro -n 2 -o table --select id,first_name`
+    Then the command should succeed
+    And the output should contain "│  999 │ Elizabeth  │"
+    And the output should contain "│ 1000 │ Susan      │"
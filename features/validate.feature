@@ -0,0 +1,53 @@
+Feature: Validate
+  Fully decode a Parquet, Avro, or ORC file and report any damage.
+
+  Scenario: Validate a healthy Parquet file
+    When I run `datu validate fixtures/userdata.parquet`
+    Then the command should succeed
+    And the output should contain "Row groups checked: 1"
+    And the output should contain "Rows checked: 1000"
+    And the output should contain "Status: OK"
+
+  Scenario: Validate a healthy Avro file
+    When I run `datu validate fixtures/userdata5.avro`
+    Then the command should succeed
+    And the output should contain "Blocks checked: 3"
+    And the output should contain "Rows checked: 1000"
+    And the output should contain "Status: OK"
+
+  Scenario: Validate a healthy ORC file with --output json
+    When I run `datu validate fixtures/userdata.orc --output json`
+    Then the command should succeed
+    And the output should be valid JSON
+    And the output should contain ""healthy":true"
+    And the output should contain ""units_checked":1,"rows_checked":1000"
+
+  Scenario: Validate a file written by datu
+    When I run `datu convert fixtures/userdata.parquet $TEMPDIR/userdata.orc --select id,first_name,salary`
+    Then the command should succeed
+    When I run `datu validate $TEMPDIR/userdata.orc`
+    Then the command should succeed
+    And the output should contain "Status: OK"
+
+  Scenario: Validate a truncated Avro file
+    When I run `datu validate fixtures/truncated.avro`
+    Then the command should fail
+    And the output should contain "Status: DAMAGED"
+    And the output should contain "block 0 (rows 0..): Invalid Avro container file: block 0 at offset 447 is missing its sync marker"
+
+  Scenario: Validate a truncated Avro file with --output yaml
+    When I run `datu validate fixtures/truncated.avro -o yaml`
+    Then the command should fail
+    And the output should be valid YAML
+    And the output should contain "healthy: false"
+    And the output should contain "location: block"
+
+  Scenario: Validate with --output text
+    When I run `datu validate fixtures/userdata.orc --output text`
+    Then the command should succeed
+    And the output should contain "Status: OK"
+
+  Scenario: Validate rejects table output formats
+    When I run `datu validate fixtures/userdata.orc -o table`
+    Then the command should fail
+    And the output should contain "unknown output type 'table', expected text, json, json-pretty, or yaml"
@@ -0,0 +1,12 @@
+Feature: View
+  Browse a Parquet, Avro, or ORC file in a full-screen terminal viewer.
+
+  Scenario: View requires an interactive terminal
+    When I run `datu view fixtures/userdata.parquet`
+    Then the command should fail
+    And the output should contain "datu view needs an interactive terminal"
+
+  Scenario: View help
+    When I run `datu view --help`
+    Then the command should succeed
+    And the output should contain "browse a file in a full-screen terminal viewer"
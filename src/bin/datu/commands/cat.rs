@@ -0,0 +1,192 @@
+//! `datu cat` - concatenate several files into one output file
+
+use anyhow::Result;
+use anyhow::bail;
+use clap::Args;
+use datu::FileType;
+use datu::cli::AvroOptions;
+use datu::cli::CsvOptions;
+use datu::cli::OrcWriteOptions;
+use datu::cli::OverwriteOptions;
+use datu::cli::ParquetWriteOptions;
+use datu::cli::XlsxOptions;
+use datu::pipeline::ReadArgs;
+use datu::pipeline::Step;
+use datu::pipeline::WriteArgs;
+use datu::pipeline::concat::ConcatInput;
+use datu::pipeline::concat::ConcatStep;
+use datu::pipeline::record_batch_filter::SelectColumnsStep;
+use datu::pipeline::xlsx::WriteXlsxSheetsStep;
+use datu::pipeline::xlsx::XlsxSheet;
+use datu::pipeline::xlsx::sheet_name_for_path;
+use datu::utils::parse_select_columns;
+
+use crate::commands::convert::WriteOptions;
+use crate::commands::convert::check_output;
+use crate::commands::convert::execute_writer;
+use crate::commands::convert::get_input_reader_step;
+
+/// Arguments for the `datu cat` command.
+#[derive(Args)]
+pub struct CatArgs {
+    /// Input Parquet, Avro, or ORC files, in the order their rows are written
+    #[arg(required = true, num_args = 1..)]
+    pub inputs: Vec<String>,
+    /// Output file; the format is inferred from the extension
+    pub output: String,
+    #[arg(
+        long,
+        help = "Match columns by name across inputs, filling columns missing from an input with nulls. Without this, all inputs must have identical schemas."
+    )]
+    pub union_by_name: bool,
+    #[arg(
+        long,
+        help = "Add a _source_file column holding the input file of each row."
+    )]
+    pub source_file: bool,
+    #[arg(
+        long,
+        conflicts_with_all = ["union_by_name", "source_file"],
+        help = "For XLSX output: write each input to a worksheet of its own, named after its file or by --sheet-name, rather than concatenating them."
+    )]
+    pub sheet_per_input: bool,
+    #[arg(
+        long,
+        help = "Columns to select. If not specified, all columns will be selected."
+    )]
+    pub select: Option<Vec<String>>,
+    #[arg(
+        long,
+        default_value_t = true,
+        action = clap::ArgAction::Set,
+        help = "For JSON/YAML: omit keys with null/missing values. Default: true. Use --sparse=false to include default values (e.g. empty string)."
+    )]
+    pub sparse: bool,
+    #[arg(
+        long,
+        help = "When writing JSON, format output with indentation and newlines. Ignored for other output formats."
+    )]
+    pub json_pretty: bool,
+    #[command(flatten)]
+    pub overwrite: OverwriteOptions,
+    #[command(flatten)]
+    pub parquet: ParquetWriteOptions,
+    #[command(flatten)]
+    pub avro: AvroOptions,
+    #[command(flatten)]
+    pub orc: OrcWriteOptions,
+    #[command(flatten)]
+    pub csv: CsvOptions,
+    #[command(flatten)]
+    pub xlsx: XlsxOptions,
+}
+
+/// Concatenates the inputs into a single output file, streaming one input at a time.
+pub fn cat(args: CatArgs) -> Result<()> {
+    let output_file_type: FileType = args.output.as_str().try_into()?;
+    check_output(&args.output, &args.inputs, &args.overwrite)?;
+    if args.sheet_per_input {
+        if output_file_type != FileType::Xlsx {
+            bail!("--sheet-per-input requires an .xlsx output file");
+        }
+        return cat_sheets(args);
+    }
+
+    let mut inputs = Vec::with_capacity(args.inputs.len());
+    for path in &args.inputs {
+        let file_type: FileType = path.as_str().try_into()?;
+        let source = get_input_reader_step(
+            file_type,
+            ReadArgs {
+                path: path.clone(),
+                limit: None,
+                offset: None,
+            },
+            &args.avro,
+        )?;
+        inputs.push(ConcatInput {
+            path: path.clone(),
+            source,
+        });
+    }
+
+    println!(
+        "Concatenating {} files into {}",
+        args.inputs.len(),
+        args.output
+    );
+
+    let concat_step = ConcatStep {
+        union_by_name: args.union_by_name,
+        source_file_column: args.source_file,
+    };
+    let mut reader_step = concat_step.execute(inputs)?;
+    if let Some(select) = &args.select {
+        let columns = parse_select_columns(select);
+        let select_step = SelectColumnsStep { columns };
+        reader_step = select_step.execute(reader_step)?;
+    }
+    let options = WriteOptions {
+        sparse: args.sparse,
+        json_pretty: args.json_pretty,
+        parquet: args.parquet.clone(),
+        avro: args.avro.clone(),
+        orc: args.orc.clone(),
+        csv: args.csv.clone(),
+        xlsx: args.xlsx.clone(),
+        no_clobber: args.overwrite.no_clobber,
+    };
+    execute_writer(reader_step, output_file_type, &args.output, &options)
+}
+
+/// Writes each input to a worksheet of its own in one XLSX workbook.
+fn cat_sheets(args: CatArgs) -> Result<()> {
+    if args.xlsx.sheet_names.len() > args.inputs.len() {
+        bail!(
+            "--sheet-name was given {} times, but there are only {} inputs",
+            args.xlsx.sheet_names.len(),
+            args.inputs.len()
+        );
+    }
+    println!(
+        "Writing {} files as sheets of {}",
+        args.inputs.len(),
+        args.output
+    );
+    let mut sheets = Vec::with_capacity(args.inputs.len());
+    // Sheets named after their file avoid the names given with --sheet-name.
+    let mut taken = args.xlsx.sheet_names.clone();
+    for (i, path) in args.inputs.iter().enumerate() {
+        let file_type: FileType = path.as_str().try_into()?;
+        let mut source = get_input_reader_step(
+            file_type,
+            ReadArgs {
+                path: path.clone(),
+                limit: None,
+                offset: None,
+            },
+            &args.avro,
+        )?;
+        if let Some(select) = &args.select {
+            let columns = parse_select_columns(select);
+            source = SelectColumnsStep { columns }.execute(source)?;
+        }
+        let name = match args.xlsx.sheet_names.get(i) {
+            Some(name) => name.clone(),
+            None => {
+                let name = sheet_name_for_path(path, &taken);
+                taken.push(name.clone());
+                name
+            }
+        };
+        sheets.push(XlsxSheet { name, source });
+    }
+    let writer = WriteXlsxSheetsStep {
+        args: WriteArgs {
+            path: args.output.clone(),
+            no_clobber: args.overwrite.no_clobber,
+        },
+    };
+    writer.execute(sheets)?;
+    Ok(())
+}
@@ -1,7 +1,15 @@
+use std::path::Path;
+
 use anyhow::Result;
 use anyhow::bail;
 use clap::Args;
 use datu::FileType;
+use datu::cli::AvroOptions;
+use datu::cli::CsvOptions;
+use datu::cli::OrcWriteOptions;
+use datu::cli::OverwriteOptions;
+use datu::cli::ParquetWriteOptions;
+use datu::cli::XlsxOptions;
 use datu::pipeline::ReadArgs;
 use datu::pipeline::RecordBatchReaderSource;
 use datu::pipeline::Step;
@@ -11,10 +19,12 @@ use datu::pipeline::WriteYamlArgs;
 use datu::pipeline::avro::ReadAvroStep;
 use datu::pipeline::avro::WriteAvroStep;
 use datu::pipeline::csv::WriteCsvStep;
+use datu::pipeline::get_reader_step;
+use datu::pipeline::html::WriteHtmlStep;
+use datu::pipeline::json::WriteJsonLinesStep;
 use datu::pipeline::json::WriteJsonStep;
-use datu::pipeline::orc::ReadOrcStep;
+use datu::pipeline::markdown::WriteMarkdownStep;
 use datu::pipeline::orc::WriteOrcStep;
-use datu::pipeline::parquet::ReadParquetStep;
 use datu::pipeline::parquet::WriteParquetStep;
 use datu::pipeline::record_batch_filter::SelectColumnsStep;
 use datu::pipeline::xlsx::WriteXlsxStep;
@@ -45,6 +55,35 @@ pub struct ConvertArgs {
         help = "When converting to JSON, format output with indentation and newlines. Ignored for other output formats."
     )]
     pub json_pretty: bool,
+    #[command(flatten)]
+    pub overwrite: OverwriteOptions,
+    #[command(flatten)]
+    pub parquet: ParquetWriteOptions,
+    #[command(flatten)]
+    pub avro: AvroOptions,
+    #[command(flatten)]
+    pub orc: OrcWriteOptions,
+    #[command(flatten)]
+    pub csv: CsvOptions,
+    #[command(flatten)]
+    pub xlsx: XlsxOptions,
+}
+
+/// Options for writing the output file, for the formats they apply to.
+#[derive(Clone)]
+pub struct WriteOptions {
+    /// For JSON/YAML: omit keys with null/missing values.
+    pub sparse: bool,
+    /// For JSON: format output with indentation and newlines.
+    pub json_pretty: bool,
+    pub parquet: ParquetWriteOptions,
+    pub avro: AvroOptions,
+    pub orc: OrcWriteOptions,
+    /// For CSV and TSV: the dialect.
+    pub csv: CsvOptions,
+    pub xlsx: XlsxOptions,
+    /// Fail rather than replace an existing output file.
+    pub no_clobber: bool,
 }
 
 /// Converts between file formats; reads from input and writes to output.
@@ -52,68 +91,113 @@ pub fn convert(args: ConvertArgs) -> anyhow::Result<()> {
     let input_file_type: FileType = args.input.as_str().try_into()?;
     let output_file_type: FileType = args.output.as_str().try_into()?;
 
+    check_output(
+        &args.output,
+        std::slice::from_ref(&args.input),
+        &args.overwrite,
+    )?;
+
     println!("Converting {} to {}", args.input, args.output);
 
-    let mut reader_step: RecordBatchReaderSource = get_reader_step(input_file_type, &args)?;
+    let mut reader_step = get_input_reader_step(
+        input_file_type,
+        ReadArgs {
+            path: args.input.clone(),
+            limit: args.limit,
+            offset: None,
+        },
+        &args.avro,
+    )?;
     if let Some(select) = &args.select {
         let columns = parse_select_columns(select);
         let select_step = SelectColumnsStep { columns };
         reader_step = select_step.execute(reader_step)?;
     }
-    let sparse = args.sparse;
-    execute_writer(reader_step, output_file_type, &args, sparse)?;
+    let options = WriteOptions {
+        sparse: args.sparse,
+        json_pretty: args.json_pretty,
+        parquet: args.parquet.clone(),
+        avro: args.avro.clone(),
+        orc: args.orc.clone(),
+        csv: args.csv.clone(),
+        xlsx: args.xlsx.clone(),
+        no_clobber: args.overwrite.no_clobber,
+    };
+    execute_writer(reader_step, output_file_type, &args.output, &options)?;
 
     Ok(())
 }
 
-/// Builds a record batch reader source for the given input file type and convert args.
-fn get_reader_step(
+/// Builds a record batch reader source for an input file. Avro input is read with the
+/// `--avro-schema` reader schema, if any.
+pub fn get_input_reader_step(
     input_file_type: FileType,
-    args: &ConvertArgs,
+    args: ReadArgs,
+    avro: &AvroOptions,
 ) -> Result<RecordBatchReaderSource> {
-    let reader: RecordBatchReaderSource = match input_file_type {
-        FileType::Parquet => Box::new(ReadParquetStep {
-            args: ReadArgs {
-                path: args.input.clone(),
-                limit: args.limit,
-                offset: None,
-            },
-        }),
-        FileType::Avro => Box::new(ReadAvroStep {
-            args: ReadArgs {
-                path: args.input.clone(),
-                limit: args.limit,
-                offset: None,
-            },
-        }),
-        FileType::Orc => Box::new(ReadOrcStep {
-            args: ReadArgs {
-                path: args.input.clone(),
-                limit: args.limit,
-                offset: None,
-            },
-        }),
-        _ => bail!("Only Parquet, Avro, and ORC are supported as input file types"),
-    };
-    Ok(reader)
+    if input_file_type == FileType::Avro {
+        let reader_schema = avro
+            .avro_schema
+            .as_ref()
+            .map(std::fs::read_to_string)
+            .transpose()?;
+        return Ok(Box::new(ReadAvroStep {
+            args,
+            reader_schema,
+        }));
+    }
+    Ok(get_reader_step(input_file_type, args)?)
+}
+
+/// Checks that an output file may be written: with `--no-clobber`, that it does not exist
+/// (checked again when the output is moved into place, as a file may be created meanwhile),
+/// and without `--force`, that it is not one of the inputs. Output files are written to a
+/// temporary file and only then moved into place, so with `--force` an input is replaced
+/// safely, once it has been read.
+pub fn check_output(output: &str, inputs: &[String], overwrite: &OverwriteOptions) -> Result<()> {
+    let output_path = Path::new(output);
+    if !output_path.exists() {
+        return Ok(());
+    }
+    if overwrite.no_clobber {
+        bail!("Output file {output} already exists");
+    }
+    if !overwrite.force {
+        let output_path = output_path.canonicalize()?;
+        for input in inputs {
+            if Path::new(input)
+                .canonicalize()
+                .is_ok_and(|input_path| input_path == output_path)
+            {
+                bail!("Output file {output} is also an input; use --force to replace it");
+            }
+        }
+    }
+    Ok(())
 }
 
 /// Writes record batches from the reader to the output file in the specified format.
-fn execute_writer(
+pub fn execute_writer(
     prev: RecordBatchReaderSource,
     output_file_type: FileType,
-    args: &ConvertArgs,
-    sparse: bool,
+    output: &str,
+    options: &WriteOptions,
 ) -> Result<()> {
-    if output_file_type != FileType::Json && args.json_pretty {
+    if output_file_type != FileType::Json && options.json_pretty {
         eprintln!("Warning: --json-pretty is only supported when converting to JSON");
     }
     match output_file_type {
-        FileType::Csv => {
+        FileType::Csv | FileType::Tsv => {
+            let mut csv = options.csv.clone();
+            if output_file_type == FileType::Tsv && csv.delimiter.is_none() {
+                csv.delimiter = Some(b'\t');
+            }
             let writer = WriteCsvStep {
                 args: WriteArgs {
-                    path: args.output.clone(),
+                    path: output.to_string(),
+                    no_clobber: options.no_clobber,
                 },
+                options: csv,
             };
             writer.execute(prev)?;
             Ok(())
@@ -121,8 +205,10 @@ fn execute_writer(
         FileType::Avro => {
             let writer = WriteAvroStep {
                 args: WriteArgs {
-                    path: args.output.clone(),
+                    path: output.to_string(),
+                    no_clobber: options.no_clobber,
                 },
+                options: options.avro.clone(),
             };
             writer.execute(prev)?;
             Ok(())
@@ -130,8 +216,10 @@ fn execute_writer(
         FileType::Parquet => {
             let writer = WriteParquetStep {
                 args: WriteArgs {
-                    path: args.output.clone(),
+                    path: output.to_string(),
+                    no_clobber: options.no_clobber,
                 },
+                options: options.parquet.clone(),
             };
             writer.execute(prev)?;
             Ok(())
@@ -139,8 +227,10 @@ fn execute_writer(
         FileType::Orc => {
             let writer = WriteOrcStep {
                 args: WriteArgs {
-                    path: args.output.clone(),
+                    path: output.to_string(),
+                    no_clobber: options.no_clobber,
                 },
+                options: options.orc.clone(),
             };
             writer.execute(prev)?;
             Ok(())
@@ -148,9 +238,22 @@ fn execute_writer(
         FileType::Json => {
             let writer = WriteJsonStep {
                 args: WriteJsonArgs {
-                    path: args.output.clone(),
-                    sparse,
-                    pretty: args.json_pretty,
+                    path: output.to_string(),
+                    sparse: options.sparse,
+                    pretty: options.json_pretty,
+                    no_clobber: options.no_clobber,
+                },
+            };
+            writer.execute(prev)?;
+            Ok(())
+        }
+        FileType::Ndjson => {
+            let writer = WriteJsonLinesStep {
+                args: WriteJsonArgs {
+                    path: output.to_string(),
+                    sparse: options.sparse,
+                    pretty: false,
+                    no_clobber: options.no_clobber,
                 },
             };
             writer.execute(prev)?;
@@ -159,8 +262,10 @@ fn execute_writer(
         FileType::Xlsx => {
             let writer = WriteXlsxStep {
                 args: WriteArgs {
-                    path: args.output.clone(),
+                    path: output.to_string(),
+                    no_clobber: options.no_clobber,
                 },
+                options: options.xlsx.clone(),
             };
             writer.execute(prev)?;
             Ok(())
@@ -168,8 +273,29 @@ fn execute_writer(
         FileType::Yaml => {
             let writer = WriteYamlStep {
                 args: WriteYamlArgs {
-                    path: args.output.clone(),
-                    sparse,
+                    path: output.to_string(),
+                    sparse: options.sparse,
+                    no_clobber: options.no_clobber,
+                },
+            };
+            writer.execute(prev)?;
+            Ok(())
+        }
+        FileType::Markdown => {
+            let writer = WriteMarkdownStep {
+                args: WriteArgs {
+                    path: output.to_string(),
+                    no_clobber: options.no_clobber,
+                },
+            };
+            writer.execute(prev)?;
+            Ok(())
+        }
+        FileType::Html => {
+            let writer = WriteHtmlStep {
+                args: WriteArgs {
+                    path: output.to_string(),
+                    no_clobber: options.no_clobber,
                 },
             };
             writer.execute(prev)?;
@@ -198,6 +324,12 @@ mod tests {
             limit: None,
             sparse: true,
             json_pretty: false,
+            overwrite: OverwriteOptions::default(),
+            parquet: ParquetWriteOptions::default(),
+            avro: AvroOptions::default(),
+            orc: OrcWriteOptions::default(),
+            csv: CsvOptions::default(),
+            xlsx: XlsxOptions::default(),
         };
 
         let result = convert(args);
@@ -221,6 +353,12 @@ mod tests {
             limit: None,
             sparse: true,
             json_pretty: false,
+            overwrite: OverwriteOptions::default(),
+            parquet: ParquetWriteOptions::default(),
+            avro: AvroOptions::default(),
+            orc: OrcWriteOptions::default(),
+            csv: CsvOptions::default(),
+            xlsx: XlsxOptions::default(),
         };
 
         let result = convert(args);
@@ -244,6 +382,12 @@ mod tests {
             limit: None,
             sparse: true,
             json_pretty: false,
+            overwrite: OverwriteOptions::default(),
+            parquet: ParquetWriteOptions::default(),
+            avro: AvroOptions::default(),
+            orc: OrcWriteOptions::default(),
+            csv: CsvOptions::default(),
+            xlsx: XlsxOptions::default(),
         };
 
         let result = convert(args);
@@ -267,6 +411,12 @@ mod tests {
             limit: None,
             sparse: true,
             json_pretty: false,
+            overwrite: OverwriteOptions::default(),
+            parquet: ParquetWriteOptions::default(),
+            avro: AvroOptions::default(),
+            orc: OrcWriteOptions::default(),
+            csv: CsvOptions::default(),
+            xlsx: XlsxOptions::default(),
         };
 
         let result = convert(args);
@@ -290,6 +440,12 @@ mod tests {
             limit: None,
             sparse: true,
             json_pretty: false,
+            overwrite: OverwriteOptions::default(),
+            parquet: ParquetWriteOptions::default(),
+            avro: AvroOptions::default(),
+            orc: OrcWriteOptions::default(),
+            csv: CsvOptions::default(),
+            xlsx: XlsxOptions::default(),
         };
 
         let result = convert(args);
@@ -313,6 +469,12 @@ mod tests {
             limit: Some(10),
             sparse: true,
             json_pretty: false,
+            overwrite: OverwriteOptions::default(),
+            parquet: ParquetWriteOptions::default(),
+            avro: AvroOptions::default(),
+            orc: OrcWriteOptions::default(),
+            csv: CsvOptions::default(),
+            xlsx: XlsxOptions::default(),
         };
 
         let result = convert(args);
@@ -337,6 +499,12 @@ mod tests {
             limit: Some(10),
             sparse: true,
             json_pretty: false,
+            overwrite: OverwriteOptions::default(),
+            parquet: ParquetWriteOptions::default(),
+            avro: AvroOptions::default(),
+            orc: OrcWriteOptions::default(),
+            csv: CsvOptions::default(),
+            xlsx: XlsxOptions::default(),
         };
         convert(orc_args).expect("Avro to ORC failed");
 
@@ -354,6 +522,12 @@ mod tests {
             limit: None,
             sparse: true,
             json_pretty: false,
+            overwrite: OverwriteOptions::default(),
+            parquet: ParquetWriteOptions::default(),
+            avro: AvroOptions::default(),
+            orc: OrcWriteOptions::default(),
+            csv: CsvOptions::default(),
+            xlsx: XlsxOptions::default(),
         };
         let result = convert(csv_args);
         assert!(result.is_ok(), "Convert failed: {:?}", result.err());
@@ -376,6 +550,12 @@ mod tests {
             limit: None,
             sparse: true,
             json_pretty: false,
+            overwrite: OverwriteOptions::default(),
+            parquet: ParquetWriteOptions::default(),
+            avro: AvroOptions::default(),
+            orc: OrcWriteOptions::default(),
+            csv: CsvOptions::default(),
+            xlsx: XlsxOptions::default(),
         };
 
         let result = convert(args);
@@ -399,6 +579,12 @@ mod tests {
             limit: None,
             sparse: true,
             json_pretty: false,
+            overwrite: OverwriteOptions::default(),
+            parquet: ParquetWriteOptions::default(),
+            avro: AvroOptions::default(),
+            orc: OrcWriteOptions::default(),
+            csv: CsvOptions::default(),
+            xlsx: XlsxOptions::default(),
         };
 
         let result = convert(args);
@@ -1,29 +1,102 @@
-//! `datu count` - return the number of rows in a Parquet, Avro, or ORC file
+//! `datu count` - return the number of rows in a Parquet, Avro, or ORC file, in total or for
+//! each group of values
+
+use std::fs::File;
+use std::io::BufReader;
 
 use anyhow::Result;
 use anyhow::bail;
 use datu::FileType;
 use datu::cli::CountArgs;
+use datu::cli::DisplayOutputFormat;
 use datu::pipeline::ReadArgs;
 use datu::pipeline::RecordBatchReaderSource;
+use datu::pipeline::Step;
 use datu::pipeline::avro::ReadAvroStep;
+use datu::pipeline::avro::container::AvroBlockReader;
+use datu::pipeline::avro::container::read_avro_header;
+use datu::pipeline::display::DisplayWriterStep;
+use datu::pipeline::display::write_serializable;
+use datu::pipeline::group_count::GroupCountStep;
 use datu::pipeline::orc::ReadOrcStep;
 use datu::pipeline::parquet::ReadParquetStep;
+use datu::utils::parse_select_columns;
+use orc_rust::reader::metadata::read_metadata;
+use parquet::file::metadata::ParquetMetaDataReader;
+use serde::Serialize;
+
+/// The total row count, as printed for JSON and YAML output.
+#[derive(Serialize)]
+struct Count {
+    count: u64,
+}
 
 /// The `datu count` command
 pub fn count(args: CountArgs) -> anyhow::Result<()> {
     let file_type: FileType = args.file.as_str().try_into()?;
-    let mut reader_step: RecordBatchReaderSource = get_reader_step(file_type, &args)?;
+    if let Some(by) = &args.by {
+        return count_by(file_type, parse_select_columns(by), &args);
+    }
 
-    let reader = reader_step.get()?;
-    let mut total: usize = 0;
-    for batch in reader {
-        let batch = batch.map_err(anyhow::Error::from)?;
-        total += batch.num_rows();
+    let total = match file_type {
+        FileType::Parquet => count_parquet(&args.file)?,
+        FileType::Avro => count_avro(&args.file)?,
+        FileType::Orc => count_orc(&args.file)?,
+        _ => bail!("Only Parquet, Avro, and ORC are supported for count"),
+    };
+    if matches!(
+        args.output,
+        DisplayOutputFormat::Csv | DisplayOutputFormat::Table
+    ) {
+        println!("{total}");
+        return Ok(());
     }
+    write_serializable(&Count { count: total }, args.output, std::io::stdout()).map_err(Into::into)
+}
 
-    println!("{total}");
-    Ok(())
+/// Reads the row count from the Parquet footer.
+fn count_parquet(path: &str) -> Result<u64> {
+    let file = File::open(path)?;
+    let metadata = ParquetMetaDataReader::new().parse_and_finish(&file)?;
+    Ok(metadata.file_metadata().num_rows().max(0) as u64)
+}
+
+/// Reads the row count from the ORC footer.
+fn count_orc(path: &str) -> Result<u64> {
+    let mut file = File::open(path)?;
+    let metadata = read_metadata(&mut file)?;
+    Ok(metadata.number_of_rows())
+}
+
+/// Adds up the record counts of the Avro data blocks, without decompressing or decoding them.
+fn count_avro(path: &str) -> Result<u64> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let header = read_avro_header(&mut reader)?;
+    let mut total = 0;
+    for block in AvroBlockReader::new(reader, &header) {
+        total += block?.record_count;
+    }
+    Ok(total)
+}
+
+/// Prints the number of rows for each distinct combination of values in `columns`.
+fn count_by(file_type: FileType, columns: Vec<String>, args: &CountArgs) -> Result<()> {
+    if columns.is_empty() {
+        bail!("--by needs at least one column");
+    }
+    let mut reader_step = get_reader_step(file_type, args)?;
+    let group_step = GroupCountStep {
+        columns,
+        top: args.top,
+    };
+    reader_step = group_step.execute(reader_step)?;
+    let display_step = DisplayWriterStep {
+        output_format: args.output,
+        sparse: args.sparse,
+        max_col_width: args.max_col_width,
+        csv: args.csv.clone(),
+    };
+    display_step.execute(reader_step).map_err(Into::into)
 }
 
 fn get_reader_step(file_type: FileType, args: &CountArgs) -> Result<RecordBatchReaderSource> {
@@ -41,6 +114,7 @@ fn get_reader_step(file_type: FileType, args: &CountArgs) -> Result<RecordBatchR
                 limit: None,
                 offset: None,
             },
+            reader_schema: None,
         }),
         FileType::Orc => Box::new(ReadOrcStep {
             args: ReadArgs {
@@ -58,19 +132,42 @@ fn get_reader_step(file_type: FileType, args: &CountArgs) -> Result<RecordBatchR
 mod tests {
     use super::*;
 
+    fn args(file: &str) -> CountArgs {
+        CountArgs {
+            file: file.to_string(),
+            by: None,
+            top: None,
+            output: DisplayOutputFormat::Csv,
+            sparse: true,
+            max_col_width: None,
+            csv: Default::default(),
+        }
+    }
+
     #[test]
     fn test_count_parquet() {
-        let args = CountArgs {
-            file: "fixtures/table.parquet".to_string(),
-        };
-        let result = count(args);
+        let result = count(args("fixtures/table.parquet"));
         assert!(result.is_ok(), "count failed: {:?}", result.err());
     }
 
     #[test]
     fn test_count_avro() {
+        let result = count(args("fixtures/userdata5.avro"));
+        assert!(result.is_ok(), "count failed: {:?}", result.err());
+    }
+
+    #[test]
+    fn test_metadata_counts() {
+        assert_eq!(count_parquet("fixtures/userdata.parquet").unwrap(), 1000);
+        assert_eq!(count_avro("fixtures/userdata5.avro").unwrap(), 1000);
+    }
+
+    #[test]
+    fn test_count_by() {
         let args = CountArgs {
-            file: "fixtures/userdata5.avro".to_string(),
+            by: Some(vec!["gender".to_string()]),
+            top: Some(2),
+            ..args("fixtures/userdata.parquet")
         };
         let result = count(args);
         assert!(result.is_ok(), "count failed: {:?}", result.err());
@@ -0,0 +1,488 @@
+//! `datu diff` - compare the schema and rows of two Parquet, Avro, or ORC files
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use anyhow::bail;
+use arrow::array::Array;
+use arrow::datatypes::Schema;
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+use arrow::util::display::ArrayFormatter;
+use arrow::util::display::FormatOptions;
+use datu::FileType;
+use datu::cli::DiffArgs;
+use datu::pipeline::ReadArgs;
+use datu::pipeline::display::write_serializable;
+use datu::pipeline::get_reader_step;
+use datu::utils::parse_select_columns;
+use serde::Serialize;
+use serde::Serializer;
+use serde::ser::SerializeMap;
+
+/// All differences between two files.
+#[derive(Serialize)]
+struct DiffReport {
+    schema: SchemaDiff,
+    row_counts: RowCounts,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    key: Option<Vec<String>>,
+    added_rows: Vec<Row>,
+    removed_rows: Vec<Row>,
+    changed_rows: Vec<ChangedRow>,
+}
+
+impl DiffReport {
+    /// Returns true when the files have the same schema and the same rows.
+    fn is_identical(&self) -> bool {
+        self.schema.added.is_empty()
+            && self.schema.removed.is_empty()
+            && self.schema.retyped.is_empty()
+            && self.row_counts.left == self.row_counts.right
+            && self.added_rows.is_empty()
+            && self.removed_rows.is_empty()
+            && self.changed_rows.is_empty()
+    }
+}
+
+/// Columns added to, removed from, or retyped in the right file.
+#[derive(Default, Serialize)]
+struct SchemaDiff {
+    added: Vec<Column>,
+    removed: Vec<Column>,
+    retyped: Vec<RetypedColumn>,
+}
+
+/// A column name and its Arrow data type.
+#[derive(Serialize)]
+struct Column {
+    name: String,
+    data_type: String,
+}
+
+/// A column present in both files with different data types.
+#[derive(Serialize)]
+struct RetypedColumn {
+    name: String,
+    left_type: String,
+    right_type: String,
+}
+
+/// The number of rows in each file.
+#[derive(Serialize)]
+struct RowCounts {
+    left: usize,
+    right: usize,
+}
+
+/// A row matched in both files whose values differ in at least one common column.
+#[derive(Serialize)]
+struct ChangedRow {
+    /// The key column values (when `--key` is given).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    key: Option<Row>,
+    /// The zero-based row number (when rows are compared by position).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    row: Option<usize>,
+    changes: Vec<ValueChange>,
+}
+
+/// The left and right values of a single column in a changed row.
+#[derive(Serialize)]
+struct ValueChange {
+    column: String,
+    left: Option<String>,
+    right: Option<String>,
+}
+
+/// Column values of a row, serialized as a mapping in column order; nulls are `None`.
+struct Row(Vec<(String, Option<String>)>);
+
+impl Serialize for Row {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (k, v) in &self.0 {
+            map.serialize_entry(k, v)?;
+        }
+        map.end()
+    }
+}
+
+/// Formats the cells of a record batch as strings for comparison and display.
+struct FormattedBatch<'a> {
+    batch: &'a RecordBatch,
+    formatters: Vec<ArrayFormatter<'a>>,
+}
+
+impl<'a> FormattedBatch<'a> {
+    fn try_new(batch: &'a RecordBatch) -> Result<Self> {
+        let options = FormatOptions::default();
+        let formatters = batch
+            .columns()
+            .iter()
+            .map(|array| ArrayFormatter::try_new(array.as_ref(), &options))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(Self { batch, formatters })
+    }
+
+    /// Returns the value at the given column and row, or `None` if it is null.
+    fn value(&self, col: usize, row: usize) -> Option<String> {
+        if self.batch.column(col).is_null(row) {
+            None
+        } else {
+            Some(self.formatters[col].value(row).to_string())
+        }
+    }
+
+    /// Returns all values of a row, keyed by column name.
+    fn row(&self, row: usize) -> Row {
+        Row(self
+            .batch
+            .schema()
+            .fields()
+            .iter()
+            .enumerate()
+            .map(|(col, field)| (field.name().clone(), self.value(col, row)))
+            .collect())
+    }
+}
+
+/// The `datu diff` command. Returns `Ok(true)` when the files are identical.
+pub fn diff(args: DiffArgs) -> Result<bool> {
+    let left = read_all(&args.left)?;
+    let right = read_all(&args.right)?;
+    let key = args.key.as_deref().map(parse_select_columns);
+    let report = diff_batches(&left, &right, key.as_deref())?;
+    match args.output.serialized_format() {
+        None => print_diff_summary(&report, &args),
+        Some(format) => write_serializable(&report, format, std::io::stdout())?,
+    }
+    Ok(report.is_identical())
+}
+
+/// Reads all rows of a Parquet, Avro, or ORC file into a single record batch.
+fn read_all(path: &str) -> Result<RecordBatch> {
+    let file_type: FileType = path.try_into()?;
+    let mut source = get_reader_step(
+        file_type,
+        ReadArgs {
+            path: path.to_string(),
+            limit: None,
+            offset: None,
+        },
+    )?;
+    let reader = source.get()?;
+    let schema = reader.schema();
+    let batches = reader.collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(arrow::compute::concat_batches(&schema, &batches)?)
+}
+
+/// Compares the schemas of two files by column name.
+fn diff_schemas(left: &Schema, right: &Schema) -> SchemaDiff {
+    let mut diff = SchemaDiff::default();
+    for field in left.fields() {
+        match right.field_with_name(field.name()) {
+            Ok(other) if other.data_type() != field.data_type() => {
+                diff.retyped.push(RetypedColumn {
+                    name: field.name().clone(),
+                    left_type: field.data_type().to_string(),
+                    right_type: other.data_type().to_string(),
+                });
+            }
+            Ok(_) => {}
+            Err(_) => diff.removed.push(Column {
+                name: field.name().clone(),
+                data_type: field.data_type().to_string(),
+            }),
+        }
+    }
+    for field in right.fields() {
+        if left.field_with_name(field.name()).is_err() {
+            diff.added.push(Column {
+                name: field.name().clone(),
+                data_type: field.data_type().to_string(),
+            });
+        }
+    }
+    diff
+}
+
+/// Compares two tables. Rows are matched by the `key` columns when given, otherwise by
+/// position. Values are compared by their display representation over the columns common to
+/// both tables, so a retyped column only reports a change when its values actually differ.
+fn diff_batches(
+    left: &RecordBatch,
+    right: &RecordBatch,
+    key: Option<&[String]>,
+) -> Result<DiffReport> {
+    let left_schema: SchemaRef = left.schema();
+    let right_schema: SchemaRef = right.schema();
+    let schema = diff_schemas(&left_schema, &right_schema);
+
+    // (column name, left index, right index) of every column present in both files.
+    let common: Vec<(String, usize, usize)> = left_schema
+        .fields()
+        .iter()
+        .enumerate()
+        .filter_map(|(l, field)| {
+            right_schema
+                .index_of(field.name())
+                .ok()
+                .map(|r| (field.name().clone(), l, r))
+        })
+        .collect();
+
+    let left_rows = FormattedBatch::try_new(left)?;
+    let right_rows = FormattedBatch::try_new(right)?;
+    let compare = |l: usize, r: usize| -> Vec<ValueChange> {
+        common
+            .iter()
+            .filter_map(|(name, lc, rc)| {
+                let lv = left_rows.value(*lc, l);
+                let rv = right_rows.value(*rc, r);
+                (lv != rv).then(|| ValueChange {
+                    column: name.clone(),
+                    left: lv,
+                    right: rv,
+                })
+            })
+            .collect()
+    };
+
+    let mut added_rows = Vec::new();
+    let mut removed_rows = Vec::new();
+    let mut changed_rows = Vec::new();
+
+    match key {
+        Some(key) => {
+            let mut key_indices = Vec::with_capacity(key.len());
+            for name in key {
+                match common.iter().find(|(n, _, _)| n == name) {
+                    Some((_, l, r)) => key_indices.push((name.clone(), *l, *r)),
+                    None => bail!("Key column '{name}' must be present in both files"),
+                }
+            }
+            let left_key = |row: usize| -> Vec<Option<String>> {
+                key_indices
+                    .iter()
+                    .map(|(_, l, _)| left_rows.value(*l, row))
+                    .collect()
+            };
+            let right_key = |row: usize| -> Vec<Option<String>> {
+                key_indices
+                    .iter()
+                    .map(|(_, _, r)| right_rows.value(*r, row))
+                    .collect()
+            };
+
+            let mut left_index: HashMap<Vec<Option<String>>, usize> =
+                HashMap::with_capacity(left.num_rows());
+            for row in 0..left.num_rows() {
+                if left_index.insert(left_key(row), row).is_some() {
+                    bail!("Duplicate key {:?} in left file", left_key(row));
+                }
+            }
+            let mut matched = vec![false; left.num_rows()];
+            let mut right_seen = std::collections::HashSet::with_capacity(right.num_rows());
+            for row in 0..right.num_rows() {
+                let k = right_key(row);
+                if !right_seen.insert(k.clone()) {
+                    bail!("Duplicate key {k:?} in right file");
+                }
+                match left_index.get(&k) {
+                    Some(&l) => {
+                        matched[l] = true;
+                        let changes = compare(l, row);
+                        if !changes.is_empty() {
+                            let key_row = key_indices
+                                .iter()
+                                .zip(k)
+                                .map(|((name, _, _), v)| (name.clone(), v))
+                                .collect();
+                            changed_rows.push(ChangedRow {
+                                key: Some(Row(key_row)),
+                                row: None,
+                                changes,
+                            });
+                        }
+                    }
+                    None => added_rows.push(right_rows.row(row)),
+                }
+            }
+            for (row, matched) in matched.iter().enumerate() {
+                if !matched {
+                    removed_rows.push(left_rows.row(row));
+                }
+            }
+        }
+        None => {
+            let common_rows = left.num_rows().min(right.num_rows());
+            for row in 0..common_rows {
+                let changes = compare(row, row);
+                if !changes.is_empty() {
+                    changed_rows.push(ChangedRow {
+                        key: None,
+                        row: Some(row),
+                        changes,
+                    });
+                }
+            }
+            removed_rows.extend((common_rows..left.num_rows()).map(|row| left_rows.row(row)));
+            added_rows.extend((common_rows..right.num_rows()).map(|row| right_rows.row(row)));
+        }
+    }
+
+    Ok(DiffReport {
+        schema,
+        row_counts: RowCounts {
+            left: left.num_rows(),
+            right: right.num_rows(),
+        },
+        key: key.map(<[String]>::to_vec),
+        added_rows,
+        removed_rows,
+        changed_rows,
+    })
+}
+
+/// Prints a human-readable summary of the differences.
+fn print_diff_summary(report: &DiffReport, args: &DiffArgs) {
+    println!("--- {}", args.left);
+    println!("+++ {}", args.right);
+    let schema = &report.schema;
+    println!(
+        "Schema: {} added, {} removed, {} retyped",
+        schema.added.len(),
+        schema.removed.len(),
+        schema.retyped.len()
+    );
+    for c in &schema.added {
+        println!("  + {}: {}", c.name, c.data_type);
+    }
+    for c in &schema.removed {
+        println!("  - {}: {}", c.name, c.data_type);
+    }
+    for c in &schema.retyped {
+        println!("  ~ {}: {} -> {}", c.name, c.left_type, c.right_type);
+    }
+    println!(
+        "Rows: {} -> {}",
+        report.row_counts.left, report.row_counts.right
+    );
+    match &report.key {
+        Some(key) => println!("Rows matched by key: {}", key.join(", ")),
+        None => println!("Rows matched by position"),
+    }
+    println!("Added rows: {}", report.added_rows.len());
+    println!("Removed rows: {}", report.removed_rows.len());
+    println!("Changed rows: {}", report.changed_rows.len());
+    let mut changed_columns: Vec<(&str, usize)> = Vec::new();
+    for change in report.changed_rows.iter().flat_map(|r| &r.changes) {
+        match changed_columns
+            .iter_mut()
+            .find(|(name, _)| *name == change.column)
+        {
+            Some((_, count)) => *count += 1,
+            None => changed_columns.push((&change.column, 1)),
+        }
+    }
+    for (name, count) in changed_columns {
+        println!("  {name}: {count} changed");
+    }
+    if report.is_identical() {
+        println!("Files are identical");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::array::Int32Array;
+    use arrow::array::Int64Array;
+    use arrow::array::StringArray;
+    use arrow::datatypes::DataType;
+    use arrow::datatypes::Field;
+
+    use super::*;
+
+    fn left_batch() -> RecordBatch {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("old", DataType::Utf8, true),
+        ]);
+        RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3])),
+                Arc::new(StringArray::from(vec![Some("alice"), Some("bob"), None])),
+                Arc::new(StringArray::from(vec!["x", "y", "z"])),
+            ],
+        )
+        .unwrap()
+    }
+
+    fn right_batch() -> RecordBatch {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("new", DataType::Utf8, true),
+        ]);
+        RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int64Array::from(vec![4, 2, 1])),
+                Arc::new(StringArray::from(vec![
+                    Some("dave"),
+                    Some("robert"),
+                    Some("alice"),
+                ])),
+                Arc::new(StringArray::from(vec!["a", "b", "c"])),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_diff_by_key() {
+        let key = vec!["id".to_string()];
+        let report = diff_batches(&left_batch(), &right_batch(), Some(&key)).unwrap();
+        assert_eq!(report.schema.added[0].name, "new");
+        assert_eq!(report.schema.removed[0].name, "old");
+        assert_eq!(report.schema.retyped[0].name, "id");
+        assert_eq!(report.added_rows.len(), 1);
+        assert_eq!(report.added_rows[0].0[0].1.as_deref(), Some("4"));
+        assert_eq!(report.removed_rows.len(), 1);
+        assert_eq!(report.removed_rows[0].0[0].1.as_deref(), Some("3"));
+        assert_eq!(report.changed_rows.len(), 1);
+        let changed = &report.changed_rows[0];
+        assert_eq!(changed.changes.len(), 1);
+        assert_eq!(changed.changes[0].column, "name");
+        assert_eq!(changed.changes[0].left.as_deref(), Some("bob"));
+        assert_eq!(changed.changes[0].right.as_deref(), Some("robert"));
+        assert!(!report.is_identical());
+    }
+
+    #[test]
+    fn test_diff_by_position() {
+        let report = diff_batches(&left_batch(), &right_batch(), None).unwrap();
+        assert!(report.added_rows.is_empty());
+        assert!(report.removed_rows.is_empty());
+        assert_eq!(report.changed_rows.len(), 3);
+        assert_eq!(report.changed_rows[2].row, Some(2));
+    }
+
+    #[test]
+    fn test_diff_identical() {
+        let key = vec!["id".to_string()];
+        let report = diff_batches(&left_batch(), &left_batch(), Some(&key)).unwrap();
+        assert!(report.is_identical());
+    }
+
+    #[test]
+    fn test_diff_missing_key_column() {
+        let key = vec!["old".to_string()];
+        assert!(diff_batches(&left_batch(), &right_batch(), Some(&key)).is_err());
+    }
+}
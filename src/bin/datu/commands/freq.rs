@@ -0,0 +1,62 @@
+//! `datu freq` - print the most common values of columns, or histograms of numeric and
+//! temporal columns
+
+use anyhow::Result;
+use anyhow::bail;
+use datu::FileType;
+use datu::cli::FreqArgs;
+use datu::pipeline::ReadArgs;
+use datu::pipeline::Step;
+use datu::pipeline::display::DisplayWriterStep;
+use datu::pipeline::frequency::FrequencyStep;
+use datu::pipeline::frequency::SourceFactory;
+use datu::pipeline::get_reader_step;
+use datu::utils::parse_select_columns;
+
+/// Width of the longest bar drawn with `--bars`.
+const BAR_WIDTH: usize = 40;
+
+/// The `datu freq` command
+pub fn freq(args: FreqArgs) -> Result<()> {
+    let file_type: FileType = args.file.as_str().try_into()?;
+    if !matches!(
+        file_type,
+        FileType::Parquet | FileType::Avro | FileType::Orc
+    ) {
+        bail!("Only Parquet, Avro, and ORC are supported for freq");
+    }
+    let columns = parse_select_columns(&args.columns);
+    if columns.is_empty() {
+        bail!("--columns needs at least one column");
+    }
+    if args.bins == 0 {
+        bail!("--bins must be at least 1");
+    }
+
+    let path = args.file.clone();
+    let input: SourceFactory = Box::new(move || {
+        get_reader_step(
+            file_type,
+            ReadArgs {
+                path: path.clone(),
+                limit: None,
+                offset: None,
+            },
+        )
+    });
+    let frequency_step = FrequencyStep {
+        columns,
+        top: args.top,
+        bins: args.bins,
+        categorical: args.categorical,
+        bar_width: args.bars.then_some(BAR_WIDTH),
+    };
+    let reader_step = frequency_step.execute(input)?;
+    let display_step = DisplayWriterStep {
+        output_format: args.output,
+        sparse: args.sparse,
+        max_col_width: args.max_col_width,
+        csv: args.csv.clone(),
+    };
+    display_step.execute(reader_step).map_err(Into::into)
+}
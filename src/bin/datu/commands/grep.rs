@@ -0,0 +1,175 @@
+//! `datu grep` - print the rows of one or more files with a value matching a pattern
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use anyhow::Result;
+use datu::FileType;
+use datu::cli::DisplayOutputFormat;
+use datu::cli::GrepArgs;
+use datu::pipeline::ReadArgs;
+use datu::pipeline::RecordBatchReaderSource;
+use datu::pipeline::Step;
+use datu::pipeline::concat::ConcatInput;
+use datu::pipeline::concat::ConcatStep;
+use datu::pipeline::display::DisplayWriterStep;
+use datu::pipeline::display::write_serializable;
+use datu::pipeline::get_reader_step;
+use datu::pipeline::grep::GrepStep;
+use datu::pipeline::grep::ROW_NUMBER_COLUMN;
+use datu::pipeline::record_batch_filter::SelectColumnsStep;
+use datu::utils::parse_select_columns;
+use regex::Regex;
+use regex::RegexBuilder;
+use serde::Serialize;
+
+/// Number of matching rows in a file, for `--count`.
+#[derive(Serialize)]
+struct FileCount {
+    file: String,
+    count: usize,
+}
+
+/// The `datu grep` command. Returns whether any row matched.
+pub fn grep(args: GrepArgs) -> Result<bool> {
+    let pattern = if args.fixed_strings {
+        regex::escape(&args.pattern)
+    } else {
+        args.pattern.clone()
+    };
+    let regex = RegexBuilder::new(&pattern)
+        .case_insensitive(args.ignore_case)
+        .build()?;
+    let matched = Rc::new(Cell::new(0));
+
+    if args.count {
+        let mut counts = Vec::with_capacity(args.files.len());
+        for file in &args.files {
+            let before = matched.get();
+            let reader = grep_file(file, &args, regex.clone(), matched.clone())?.get()?;
+            for batch in reader {
+                batch?;
+            }
+            counts.push(FileCount {
+                file: file.clone(),
+                count: matched.get() - before,
+            });
+        }
+        print_counts(&counts, args.output)?;
+        return Ok(matched.get() > 0);
+    }
+
+    let mut inputs = Vec::with_capacity(args.files.len());
+    for file in &args.files {
+        inputs.push(ConcatInput {
+            path: file.clone(),
+            source: grep_file(file, &args, regex.clone(), matched.clone())?,
+        });
+    }
+    // Matches from several files are printed together, with the file each came from.
+    let concat_step = ConcatStep {
+        union_by_name: true,
+        source_file_column: args.files.len() > 1,
+    };
+    let reader_step = concat_step.execute(inputs)?;
+    let display_step = DisplayWriterStep {
+        output_format: args.output,
+        sparse: args.sparse,
+        max_col_width: None,
+        csv: args.csv.clone(),
+    };
+    display_step.execute(reader_step)?;
+    Ok(matched.get() > 0)
+}
+
+/// Builds the pipeline that reads a file and keeps its matching rows.
+fn grep_file(
+    file: &str,
+    args: &GrepArgs,
+    regex: Regex,
+    matched: Rc<Cell<usize>>,
+) -> Result<RecordBatchReaderSource> {
+    let file_type: FileType = file.try_into()?;
+    let reader_step = get_reader_step(
+        file_type,
+        ReadArgs {
+            path: file.to_string(),
+            limit: None,
+            offset: None,
+        },
+    )?;
+    let grep_step = GrepStep {
+        regex,
+        columns: args.columns.as_deref().map(parse_select_columns),
+        invert: args.invert,
+        row_numbers: args.row_numbers,
+        matched,
+    };
+    let mut reader_step = grep_step.execute(reader_step)?;
+    if let Some(select) = &args.select {
+        let mut columns = Vec::new();
+        if args.row_numbers {
+            columns.push(ROW_NUMBER_COLUMN.to_string());
+        }
+        columns.extend(parse_select_columns(select));
+        let select_step = SelectColumnsStep { columns };
+        reader_step = select_step.execute(reader_step)?;
+    }
+    Ok(reader_step)
+}
+
+/// Prints the match counts: like grep(1) for csv and table (`N`, or `file:N` for several
+/// files), or as a list of `{file, count}` records otherwise.
+fn print_counts(counts: &[FileCount], output: DisplayOutputFormat) -> Result<()> {
+    if !matches!(
+        output,
+        DisplayOutputFormat::Csv | DisplayOutputFormat::Table
+    ) {
+        return write_serializable(&counts, output, std::io::stdout()).map_err(Into::into);
+    }
+    match counts {
+        [count] => println!("{}", count.count),
+        _ => {
+            for count in counts {
+                println!("{}:{}", count.file, count.count);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(pattern: &str, files: &[&str]) -> GrepArgs {
+        GrepArgs {
+            pattern: pattern.to_string(),
+            files: files.iter().map(|f| f.to_string()).collect(),
+            columns: None,
+            fixed_strings: false,
+            ignore_case: false,
+            invert: false,
+            count: true,
+            row_numbers: false,
+            select: None,
+            output: DisplayOutputFormat::Csv,
+            sparse: true,
+            csv: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_grep_count() {
+        assert!(grep(args("ajordan0@com.com", &["fixtures/userdata.parquet"])).unwrap());
+        assert!(!grep(args("no such value", &["fixtures/userdata.parquet"])).unwrap());
+    }
+
+    #[test]
+    fn test_grep_fixed_strings() {
+        // As a regex, "." matches any character; as a literal, only a dot.
+        let mut grep_args = args("a.j", &["fixtures/userdata.parquet"]);
+        grep_args.fixed_strings = true;
+        assert!(!grep(grep_args).unwrap());
+    }
+}
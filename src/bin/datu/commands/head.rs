@@ -25,6 +25,8 @@ pub fn head(args: HeadsOrTails) -> Result<()> {
     let display_step = DisplayWriterStep {
         output_format: args.output,
         sparse,
+        max_col_width: args.max_col_width,
+        csv: args.csv.clone(),
     };
     display_step.execute(reader_step).map_err(Into::into)
 }
@@ -48,6 +50,7 @@ fn get_reader_step(
                 limit: Some(args.number),
                 offset: None,
             },
+            reader_schema: None,
         }),
         FileType::Orc => Box::new(ReadOrcStep {
             args: ReadArgs {
@@ -0,0 +1,591 @@
+//! `datu inspect` - display the file-level metadata of an Avro or ORC file
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+
+use anyhow::Result;
+use anyhow::bail;
+use datu::FileType;
+use datu::cli::InspectArgs;
+use datu::cli::ReportOutputFormat;
+use datu::pipeline::avro::container::AvroBlockReader;
+use datu::pipeline::avro::container::read_avro_header;
+use datu::pipeline::display::write_serializable;
+use orc_rust::reader::metadata::FileMetadata;
+use orc_rust::reader::metadata::read_metadata;
+use orc_rust::schema::DataType as OrcDataType;
+use orc_rust::statistics::ColumnStatistics;
+use orc_rust::statistics::TypeStatistics;
+use serde::Serialize;
+use serde::Serializer;
+use serde_json::json;
+
+/// Header and block layout of an Avro object container file.
+#[derive(Serialize)]
+struct AvroFileInfo {
+    codec: String,
+    sync_marker: String,
+    number_of_blocks: usize,
+    number_of_records: u64,
+    blocks: Vec<AvroBlockInfo>,
+    user_metadata: BTreeMap<String, String>,
+    writer_schema: RawJson,
+}
+
+/// Size and record count of a single Avro data block.
+#[derive(Serialize)]
+struct AvroBlockInfo {
+    index: usize,
+    offset: u64,
+    number_of_records: u64,
+    compressed_bytes: usize,
+}
+
+/// A JSON document that serializes with its original key order (unlike `serde_json::Value`,
+/// which sorts object keys).
+struct RawJson(String);
+
+impl Serialize for RawJson {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut deserializer = serde_json::Deserializer::from_str(&self.0);
+        serde_transcode::Transcoder::new(&mut deserializer).serialize(serializer)
+    }
+}
+
+/// File-level metadata of an ORC file: postscript, footer, stripes, and statistics.
+#[derive(Serialize)]
+struct OrcFileInfo {
+    file_format_version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    writer_version: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    writer_version_name: Option<String>,
+    compression: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    compression_block_size: Option<u64>,
+    number_of_rows: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    row_index_stride: Option<usize>,
+    stripes: Vec<OrcStripeInfo>,
+    column_statistics: Vec<OrcColumnStatistics>,
+    user_metadata: BTreeMap<String, String>,
+}
+
+/// Layout of a single ORC stripe.
+#[derive(Serialize)]
+struct OrcStripeInfo {
+    index: usize,
+    offset: u64,
+    number_of_rows: u64,
+    index_length: u64,
+    data_length: u64,
+    footer_length: u64,
+    total_length: u64,
+}
+
+/// File-level statistics for a single ORC column (column 0 is the root struct).
+///
+/// For lists and maps, `min`, `max` and `sum` describe the number of child elements.
+#[derive(Serialize)]
+struct OrcColumnStatistics {
+    column: usize,
+    name: String,
+    data_type: String,
+    number_of_values: u64,
+    has_null: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sum: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    true_count: Option<u64>,
+}
+
+/// Fields of the ORC postscript that are not exposed by `orc_rust`'s `FileMetadata`.
+#[derive(Debug, Default, PartialEq)]
+struct OrcPostScript {
+    compression_block_size: Option<u64>,
+    writer_version: Option<u32>,
+}
+
+/// The `datu inspect` command
+pub fn inspect(args: InspectArgs) -> Result<()> {
+    let file_type: FileType = args.file.as_str().try_into()?;
+    match file_type {
+        FileType::Avro => inspect_avro(&args.file, args.output),
+        FileType::Orc => inspect_orc(&args.file, args.output),
+        _ => bail!("inspect is only supported for Avro and ORC files"),
+    }
+}
+
+/// Reads and prints the header (writer schema, codec, sync marker, metadata) and the block
+/// layout of an Avro file.
+fn inspect_avro(path: &str, output: ReportOutputFormat) -> Result<()> {
+    let file = File::open(path)?;
+    let mut reader = BufReader::new(file);
+    let header = read_avro_header(&mut reader)?;
+    let writer_schema = RawJson(header.schema()?.to_string());
+    // Fail early with a clear message rather than mid-way through serialization.
+    serde_json::from_str::<serde::de::IgnoredAny>(&writer_schema.0)
+        .map_err(|e| anyhow::anyhow!("Writer schema is not valid JSON: {e}"))?;
+
+    let blocks = AvroBlockReader::new(reader, &header)
+        .map(|block| {
+            block.map(|b| AvroBlockInfo {
+                index: b.index,
+                offset: b.offset,
+                number_of_records: b.record_count,
+                compressed_bytes: b.data.len(),
+            })
+        })
+        .collect::<datu::Result<Vec<_>>>()?;
+
+    let info = AvroFileInfo {
+        codec: header.codec(),
+        sync_marker: header.sync.iter().map(|b| format!("{b:02x}")).collect(),
+        number_of_blocks: blocks.len(),
+        number_of_records: blocks.iter().map(|b| b.number_of_records).sum(),
+        blocks,
+        user_metadata: header
+            .user_metadata()
+            .map(|(k, v)| (k.to_string(), String::from_utf8_lossy(v).into_owned()))
+            .collect(),
+        writer_schema,
+    };
+    match output.serialized_format() {
+        None => print_avro_file_info(&info)?,
+        Some(format) => write_serializable(&info, format, std::io::stdout())?,
+    }
+    Ok(())
+}
+
+/// Prints the Avro file info in a human-readable, line-oriented format.
+fn print_avro_file_info(info: &AvroFileInfo) -> Result<()> {
+    println!("Codec: {}", info.codec);
+    println!("Sync marker: {}", info.sync_marker);
+    println!("Records: {}", info.number_of_records);
+    println!("Blocks: {}", info.number_of_blocks);
+    for b in &info.blocks {
+        println!(
+            "  {}: offset={}, records={}, compressed_bytes={}",
+            b.index, b.offset, b.number_of_records, b.compressed_bytes
+        );
+    }
+    if !info.user_metadata.is_empty() {
+        println!("User metadata:");
+        for (k, v) in &info.user_metadata {
+            println!("  {k}: {v}");
+        }
+    }
+    println!("Writer schema:");
+    println!("{}", serde_json::to_string_pretty(&info.writer_schema)?);
+    Ok(())
+}
+
+/// Reads and prints the stripe layout, compression, statistics and metadata of an ORC file.
+fn inspect_orc(path: &str, output: ReportOutputFormat) -> Result<()> {
+    let mut file = File::open(path)?;
+    let metadata = read_metadata(&mut file)?;
+    let postscript = read_orc_postscript(&mut file)?;
+    let info = orc_file_info(&metadata, &postscript);
+    match output.serialized_format() {
+        None => print_orc_file_info(&info),
+        Some(format) => write_serializable(&info, format, std::io::stdout())?,
+    }
+    Ok(())
+}
+
+/// Builds the displayable file info from the ORC metadata and postscript.
+fn orc_file_info(metadata: &FileMetadata, postscript: &OrcPostScript) -> OrcFileInfo {
+    let compression = metadata
+        .compression()
+        .map(|c| c.compression_type().to_string().to_uppercase())
+        .unwrap_or_else(|| "NONE".to_string());
+
+    let stripes = metadata
+        .stripe_metadatas()
+        .iter()
+        .enumerate()
+        .map(|(index, stripe)| OrcStripeInfo {
+            index,
+            offset: stripe.offset(),
+            number_of_rows: stripe.number_of_rows(),
+            index_length: stripe.index_length(),
+            data_length: stripe.data_length(),
+            footer_length: stripe.footer_length(),
+            total_length: stripe.index_length() + stripe.data_length() + stripe.footer_length(),
+        })
+        .collect();
+
+    let mut columns = vec![(0, String::new(), "STRUCT".to_string())];
+    for child in metadata.root_data_type().children() {
+        collect_orc_columns(child.name(), child.data_type(), &mut columns);
+    }
+    let column_statistics = metadata
+        .column_file_statistics()
+        .iter()
+        .enumerate()
+        .map(|(column, stats)| {
+            let (name, data_type) = columns
+                .iter()
+                .find(|(index, _, _)| *index == column)
+                .map(|(_, name, data_type)| (name.clone(), data_type.clone()))
+                .unwrap_or_default();
+            orc_column_statistics(column, name, data_type, stats)
+        })
+        .collect();
+
+    let user_metadata = metadata
+        .user_custom_metadata()
+        .iter()
+        .map(|(k, v)| (k.clone(), String::from_utf8_lossy(v).into_owned()))
+        .collect();
+
+    OrcFileInfo {
+        file_format_version: metadata.file_format_version().to_string(),
+        writer_version: postscript.writer_version,
+        writer_version_name: postscript.writer_version.map(orc_writer_version_name),
+        compression,
+        compression_block_size: postscript.compression_block_size,
+        number_of_rows: metadata.number_of_rows(),
+        row_index_stride: metadata.row_index_stride(),
+        stripes,
+        column_statistics,
+        user_metadata,
+    }
+}
+
+/// Recursively collects `(column index, dotted name, type)` for an ORC column and its children.
+fn collect_orc_columns(
+    name: &str,
+    data_type: &OrcDataType,
+    out: &mut Vec<(usize, String, String)>,
+) {
+    out.push((
+        data_type.column_index(),
+        name.to_string(),
+        orc_type_name(data_type),
+    ));
+    match data_type {
+        OrcDataType::Struct { children, .. } => {
+            for child in children {
+                collect_orc_columns(&format!("{name}.{}", child.name()), child.data_type(), out);
+            }
+        }
+        OrcDataType::List { child, .. } => {
+            collect_orc_columns(&format!("{name}.item"), child, out);
+        }
+        OrcDataType::Map { key, value, .. } => {
+            collect_orc_columns(&format!("{name}.key"), key, out);
+            collect_orc_columns(&format!("{name}.value"), value, out);
+        }
+        OrcDataType::Union { variants, .. } => {
+            for (i, variant) in variants.iter().enumerate() {
+                collect_orc_columns(&format!("{name}.{i}"), variant, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Returns a single-line name for an ORC type (compound types omit their children).
+fn orc_type_name(data_type: &OrcDataType) -> String {
+    match data_type {
+        OrcDataType::Struct { .. } => "STRUCT".to_string(),
+        OrcDataType::List { .. } => "LIST".to_string(),
+        OrcDataType::Map { .. } => "MAP".to_string(),
+        OrcDataType::Union { .. } => "UNION".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Converts `orc_rust` column statistics into their displayable form.
+fn orc_column_statistics(
+    column: usize,
+    name: String,
+    data_type: String,
+    stats: &ColumnStatistics,
+) -> OrcColumnStatistics {
+    let mut result = OrcColumnStatistics {
+        column,
+        name,
+        data_type,
+        number_of_values: stats.number_of_values(),
+        has_null: stats.has_null(),
+        min: None,
+        max: None,
+        sum: None,
+        true_count: None,
+    };
+    match stats.type_statistics() {
+        Some(TypeStatistics::Integer { min, max, sum }) => {
+            result.min = Some(json!(min));
+            result.max = Some(json!(max));
+            result.sum = sum.map(|s| json!(s));
+        }
+        Some(TypeStatistics::Double { min, max, sum }) => {
+            result.min = Some(json!(min));
+            result.max = Some(json!(max));
+            result.sum = sum.map(|s| json!(s));
+        }
+        Some(TypeStatistics::String {
+            lower_bound,
+            upper_bound,
+            sum,
+            ..
+        }) => {
+            // Truncated statistics only bound the values; the bounds are shown as min and max.
+            result.min = Some(json!(lower_bound));
+            result.max = Some(json!(upper_bound));
+            result.sum = Some(json!(sum));
+        }
+        Some(TypeStatistics::Bucket { true_count }) => {
+            result.true_count = Some(*true_count);
+        }
+        Some(TypeStatistics::Decimal { min, max, sum }) => {
+            result.min = Some(json!(min));
+            result.max = Some(json!(max));
+            result.sum = Some(json!(sum));
+        }
+        Some(TypeStatistics::Date { min, max }) => {
+            result.min = Some(json!(format_orc_date(*min)));
+            result.max = Some(json!(format_orc_date(*max)));
+        }
+        Some(TypeStatistics::Binary { sum }) => {
+            result.sum = Some(json!(sum));
+        }
+        Some(TypeStatistics::Timestamp {
+            min_utc, max_utc, ..
+        }) => {
+            result.min = Some(json!(format_orc_timestamp(*min_utc)));
+            result.max = Some(json!(format_orc_timestamp(*max_utc)));
+        }
+        Some(TypeStatistics::Collection {
+            min_children,
+            max_children,
+            total_children,
+        }) => {
+            result.min = Some(json!(min_children));
+            result.max = Some(json!(max_children));
+            result.sum = Some(json!(total_children));
+        }
+        None => {}
+    }
+    result
+}
+
+/// Formats days since the UNIX epoch as an ISO 8601 date.
+fn format_orc_date(days: i32) -> String {
+    chrono::DateTime::from_timestamp(i64::from(days) * 86_400, 0)
+        .map(|dt| dt.date_naive().to_string())
+        .unwrap_or_else(|| days.to_string())
+}
+
+/// Formats milliseconds since the UNIX epoch as an ISO 8601 UTC timestamp.
+fn format_orc_timestamp(millis: i64) -> String {
+    chrono::DateTime::from_timestamp_millis(millis)
+        .map(|dt| dt.to_rfc3339_opts(chrono::SecondsFormat::Millis, true))
+        .unwrap_or_else(|| millis.to_string())
+}
+
+/// Returns the name of an ORC writer version, as defined by the ORC specification.
+fn orc_writer_version_name(version: u32) -> String {
+    match version {
+        0 => "ORIGINAL",
+        1 => "HIVE-8732",
+        2 => "HIVE-4243",
+        3 => "HIVE-12055",
+        4 => "HIVE-13083",
+        5 => "ORC-101",
+        6 => "ORC-135",
+        7 => "ORC-517",
+        8 => "ORC-203",
+        9 => "ORC-14",
+        _ => "FUTURE",
+    }
+    .to_string()
+}
+
+/// Reads the postscript at the end of an ORC file. The postscript is never compressed and its
+/// length is stored in the last byte of the file.
+fn read_orc_postscript<R: Read + Seek>(reader: &mut R) -> Result<OrcPostScript> {
+    let file_len = reader.seek(SeekFrom::End(0))?;
+    if file_len == 0 {
+        bail!("ORC file is empty");
+    }
+    reader.seek(SeekFrom::End(-1))?;
+    let mut len_byte = [0u8; 1];
+    reader.read_exact(&mut len_byte)?;
+    let postscript_len = u64::from(len_byte[0]);
+    if postscript_len + 1 > file_len {
+        bail!("ORC postscript length {postscript_len} exceeds file length {file_len}");
+    }
+    reader.seek(SeekFrom::End(-1 - postscript_len as i64))?;
+    let mut buf = vec![0u8; postscript_len as usize];
+    reader.read_exact(&mut buf)?;
+    parse_orc_postscript(&buf)
+}
+
+/// Decodes the postscript protobuf message, keeping only the fields `datu` displays.
+fn parse_orc_postscript(buf: &[u8]) -> Result<OrcPostScript> {
+    let mut postscript = OrcPostScript::default();
+    let mut pos = 0;
+    while pos < buf.len() {
+        let Some(key) = read_varint(buf, &mut pos) else {
+            bail!("Malformed ORC postscript");
+        };
+        let field = key >> 3;
+        match key & 0x7 {
+            0 => {
+                let Some(value) = read_varint(buf, &mut pos) else {
+                    bail!("Malformed ORC postscript");
+                };
+                match field {
+                    3 => postscript.compression_block_size = Some(value),
+                    6 => postscript.writer_version = Some(value as u32),
+                    _ => {}
+                }
+            }
+            1 => pos += 8,
+            2 => {
+                let Some(len) = read_varint(buf, &mut pos) else {
+                    bail!("Malformed ORC postscript");
+                };
+                pos += len as usize;
+            }
+            5 => pos += 4,
+            wire_type => bail!("Unsupported protobuf wire type {wire_type} in ORC postscript"),
+        }
+    }
+    Ok(postscript)
+}
+
+/// Reads a base-128 varint from `buf` at `pos`, advancing `pos` past it.
+fn read_varint(buf: &[u8], pos: &mut usize) -> Option<u64> {
+    let mut result = 0u64;
+    let mut shift = 0;
+    while let Some(&byte) = buf.get(*pos) {
+        *pos += 1;
+        result |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Some(result);
+        }
+        shift += 7;
+        if shift >= 64 {
+            return None;
+        }
+    }
+    None
+}
+
+/// Prints the ORC file info in a human-readable, line-oriented format.
+fn print_orc_file_info(info: &OrcFileInfo) {
+    println!("File format version: {}", info.file_format_version);
+    match (&info.writer_version, &info.writer_version_name) {
+        (Some(version), Some(name)) => println!("Writer version: {version} ({name})"),
+        _ => println!("Writer version: unknown"),
+    }
+    println!("Compression: {}", info.compression);
+    if let Some(block_size) = info.compression_block_size {
+        println!("Compression block size: {block_size}");
+    }
+    println!("Rows: {}", info.number_of_rows);
+    if let Some(stride) = info.row_index_stride {
+        println!("Row index stride: {stride}");
+    }
+    println!("Stripes: {}", info.stripes.len());
+    for s in &info.stripes {
+        println!(
+            "  {}: offset={}, rows={}, index_length={}, data_length={}, footer_length={}, total_length={}",
+            s.index,
+            s.offset,
+            s.number_of_rows,
+            s.index_length,
+            s.data_length,
+            s.footer_length,
+            s.total_length
+        );
+    }
+    println!("Column statistics:");
+    for c in &info.column_statistics {
+        let name = if c.name.is_empty() { "<root>" } else { &c.name };
+        let mut line = format!(
+            "  {}: {} {}, values={}, has_null={}",
+            c.column, name, c.data_type, c.number_of_values, c.has_null
+        );
+        for (label, value) in [("min", &c.min), ("max", &c.max), ("sum", &c.sum)] {
+            if let Some(value) = value {
+                match value {
+                    serde_json::Value::String(s) => line.push_str(&format!(", {label}={s}")),
+                    other => line.push_str(&format!(", {label}={other}")),
+                }
+            }
+        }
+        if let Some(true_count) = c.true_count {
+            line.push_str(&format!(", true_count={true_count}"));
+        }
+        println!("{line}");
+    }
+    if !info.user_metadata.is_empty() {
+        println!("User metadata:");
+        for (k, v) in &info.user_metadata {
+            println!("  {k}: {v}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_orc_postscript() {
+        // footer_length=1, compression=ZLIB, block size=262144, version=[0, 12], writer_version=6
+        let buf = [
+            0x08, 0x01, 0x10, 0x01, 0x18, 0x80, 0x80, 0x10, 0x22, 0x02, 0x00, 0x0c, 0x30, 0x06,
+        ];
+        let postscript = parse_orc_postscript(&buf).expect("Failed to parse postscript");
+        assert_eq!(
+            postscript,
+            OrcPostScript {
+                compression_block_size: Some(262_144),
+                writer_version: Some(6),
+            }
+        );
+    }
+
+    #[test]
+    fn test_inspect_avro() {
+        let args = InspectArgs {
+            file: "fixtures/userdata5.avro".to_string(),
+            output: ReportOutputFormat::Yaml,
+        };
+        let result = inspect(args);
+        assert!(result.is_ok(), "inspect failed: {:?}", result.err());
+    }
+
+    #[test]
+    fn test_raw_json_preserves_key_order() {
+        let raw = RawJson(r#"{"type":"record","name":"r","fields":[]}"#.to_string());
+        assert_eq!(
+            serde_json::to_string(&raw).unwrap(),
+            r#"{"type":"record","name":"r","fields":[]}"#
+        );
+    }
+
+    #[test]
+    fn test_inspect_orc() {
+        let args = InspectArgs {
+            file: "fixtures/userdata.orc".to_string(),
+            output: ReportOutputFormat::Json,
+        };
+        let result = inspect(args);
+        assert!(result.is_ok(), "inspect failed: {:?}", result.err());
+    }
+}
@@ -1,13 +1,31 @@
-//! CLI command implementations (convert, count, head, schema, tail).
+//! CLI command implementations (cat, convert, count, diff, freq, grep, head, inspect, schema, schema-check, split, tail, validate, view).
 
+pub mod cat;
 pub mod convert;
 mod count;
+mod diff;
+mod freq;
+mod grep;
 mod head;
+mod inspect;
 mod schema;
+mod schema_check;
+mod split;
 mod tail;
+mod validate;
+mod view;
 
+pub use cat::cat;
 pub use convert::convert;
 pub use count::count;
+pub use diff::diff;
+pub use freq::freq;
+pub use grep::grep;
 pub use head::head;
+pub use inspect::inspect;
 pub use schema::schema;
+pub use schema_check::schema_check;
+pub use split::split;
 pub use tail::tail;
+pub use validate::validate;
+pub use view::view;
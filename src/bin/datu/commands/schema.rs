@@ -1,17 +1,36 @@
-//! `datu schema` - display the schema of a Parquet, Avro, or ORC file
+//! `datu schema` - display the schema of a Parquet, Avro, or ORC file, or export it as Avro,
+//! JSON Schema, SQL, Arrow, or protobuf
 
 use std::fmt::Display;
 use std::fs::File;
 use std::io::BufReader;
+use std::io::IsTerminal;
+use std::path::Path;
 use std::sync::Arc;
 
 use anyhow::Result;
 use anyhow::bail;
 use arrow::array::RecordBatchReader;
 use arrow_avro::reader::ReaderBuilder;
+use arrow_avro::schema::SCHEMA_METADATA_KEY;
 use datu::FileType;
 use datu::cli::DisplayOutputFormat;
 use datu::cli::SchemaArgs;
+use datu::cli::SchemaOutputFormat;
+use datu::pipeline::ReadArgs;
+use datu::pipeline::avro::container::read_avro_header;
+use datu::pipeline::display::table::Align;
+use datu::pipeline::display::table::Table;
+use datu::pipeline::display::table::TableOptions;
+use datu::pipeline::display::write_serializable;
+use datu::pipeline::get_reader_step;
+use datu::schema::to_avsc;
+use datu::schema::to_json_schema;
+use datu::schema::to_proto;
+use datu::schema::to_sql;
+use datu::schema::tree;
+use datu::schema::tree::SerializeSchemaNode;
+use datu::schema::write_arrow_ipc_schema;
 use orc_rust::arrow_reader::ArrowReaderBuilder;
 use parquet::basic::ConvertedType;
 use parquet::file::metadata::ParquetMetaDataReader;
@@ -199,6 +218,19 @@ fn print_schema_json_pretty(fields: &[SchemaField], sparse: bool) -> Result<()>
     Ok(())
 }
 
+/// Prints schema fields as JSON Lines, one field per line.
+fn print_schema_json_lines(fields: &[SchemaField], sparse: bool) -> Result<()> {
+    for field in fields {
+        let json = if sparse {
+            serde_json::to_string(field)?
+        } else {
+            serde_json::to_string(&SchemaFieldFull::from(field))?
+        };
+        println!("{json}");
+    }
+    Ok(())
+}
+
 /// Prints schema fields as YAML.
 fn print_schema_yaml(fields: &[SchemaField], sparse: bool) -> Result<()> {
     let yaml_fields: Vec<Yaml<'static>> =
@@ -211,66 +243,201 @@ fn print_schema_yaml(fields: &[SchemaField], sparse: bool) -> Result<()> {
     Ok(())
 }
 
+/// Builds a table of schema fields with one row per field.
+fn schema_table(fields: &[SchemaField]) -> Table {
+    let header = ["name", "data_type", "converted_type", "nullable"];
+    let mut table = Table::new(
+        header.iter().map(|h| h.to_string()).collect(),
+        vec![Align::Left; header.len()],
+    );
+    for f in fields {
+        table.push_row(vec![
+            Some(f.name.clone()),
+            Some(f.data_type.clone()),
+            f.converted_type.clone(),
+            Some(f.nullable.to_string()),
+        ]);
+    }
+    table
+}
+
 /// Prints schema fields in the specified output format.
-fn print_schema(fields: &[SchemaField], output: DisplayOutputFormat, sparse: bool) -> Result<()> {
+fn print_schema(
+    fields: &[SchemaField],
+    output: DisplayOutputFormat,
+    args: &SchemaArgs,
+) -> Result<()> {
     match output {
         DisplayOutputFormat::Csv => print_schema_csv(fields),
-        DisplayOutputFormat::Json => print_schema_json(fields, sparse),
-        DisplayOutputFormat::JsonPretty => print_schema_json_pretty(fields, sparse),
-        DisplayOutputFormat::Yaml => print_schema_yaml(fields, sparse),
+        DisplayOutputFormat::Json => print_schema_json(fields, args.sparse),
+        DisplayOutputFormat::JsonPretty => print_schema_json_pretty(fields, args.sparse),
+        DisplayOutputFormat::JsonLines => print_schema_json_lines(fields, args.sparse),
+        DisplayOutputFormat::Yaml => print_schema_yaml(fields, args.sparse),
+        DisplayOutputFormat::Table => {
+            let options = TableOptions::for_stdout(args.max_col_width);
+            Ok(schema_table(fields).write(std::io::stdout(), &options)?)
+        }
+        DisplayOutputFormat::Markdown => {
+            Ok(schema_table(fields).write_markdown(std::io::stdout())?)
+        }
+        DisplayOutputFormat::Html => {
+            let title = format!("Schema of {}", args.file);
+            Ok(schema_table(fields).write_html(std::io::stdout(), &title)?)
+        }
     }
 }
 
 /// Extracts and prints the schema of an Avro file.
-fn schema_avro(path: &str, output: DisplayOutputFormat, sparse: bool) -> Result<()> {
+fn schema_avro(path: &str) -> Result<Vec<SchemaField>> {
     let file = File::open(path)?;
     let reader = BufReader::new(file);
     let arrow_reader = ReaderBuilder::new().build(reader)?;
     let schema = arrow_reader.schema();
-    let fields: Vec<SchemaField> = schema
+    let fields = schema
         .fields()
         .iter()
         .map(|f| SchemaField {
             name: f.name().to_string(),
-            data_type: format!("{:?}", f.data_type()),
+            data_type: f.data_type().to_string(),
             converted_type: None,
             nullable: f.is_nullable(),
         })
         .collect();
-    print_schema(&fields, output, sparse)
+    Ok(fields)
 }
 
 /// The `datu schema` command
 pub fn schema(args: SchemaArgs) -> Result<()> {
     let file_type: FileType = args.file.as_str().try_into()?;
-    match file_type {
-        FileType::Parquet => schema_parquet(&args.file, args.output, args.sparse),
-        FileType::Avro => schema_avro(&args.file, args.output, args.sparse),
-        FileType::Orc => schema_orc(&args.file, args.output, args.sparse),
+    let Some(output) = args.output.display_format() else {
+        if args.tree {
+            bail!("--tree is only supported with --output csv, json, json-pretty, jsonl, or yaml");
+        }
+        return export_schema(&args, file_type);
+    };
+    if args.tree {
+        return print_schema_tree(&args, file_type, output);
+    }
+    let fields = match file_type {
+        FileType::Parquet => schema_parquet(&args.file)?,
+        FileType::Avro => schema_avro(&args.file)?,
+        FileType::Orc => schema_orc(&args.file)?,
         _ => bail!("schema is only supported for Parquet, Avro, and ORC files"),
+    };
+    print_schema(&fields, output, &args)
+}
+
+/// Prints the schema as a tree of nested fields: Parquet as stored, Avro and ORC as their Arrow
+/// equivalent.
+fn print_schema_tree(
+    args: &SchemaArgs,
+    file_type: FileType,
+    output: DisplayOutputFormat,
+) -> Result<()> {
+    let nodes = match file_type {
+        FileType::Parquet => {
+            let file = File::open(&args.file)?;
+            let metadata = ParquetMetaDataReader::new().parse_and_finish(&file)?;
+            tree::from_parquet(metadata.file_metadata().schema_descr().root_schema())
+        }
+        FileType::Avro | FileType::Orc => {
+            let mut reader_step = get_reader_step(
+                file_type,
+                ReadArgs {
+                    path: args.file.clone(),
+                    limit: Some(0),
+                    offset: None,
+                },
+            )?;
+            tree::from_arrow(&reader_step.get()?.schema())
+        }
+        _ => bail!("schema is only supported for Parquet, Avro, and ORC files"),
+    };
+    match output {
+        DisplayOutputFormat::Csv => Ok(tree::write_tree(&nodes, &args.file, std::io::stdout())?),
+        DisplayOutputFormat::Json
+        | DisplayOutputFormat::JsonPretty
+        | DisplayOutputFormat::JsonLines
+        | DisplayOutputFormat::Yaml => {
+            let nodes: Vec<SerializeSchemaNode> = nodes
+                .iter()
+                .map(|node| SerializeSchemaNode {
+                    node,
+                    sparse: args.sparse,
+                })
+                .collect();
+            Ok(write_serializable(&nodes, output, std::io::stdout())?)
+        }
+        _ => bail!("--tree is only supported with --output csv, json, json-pretty, jsonl, or yaml"),
+    }
+}
+
+/// Prints the Arrow schema of a file in the schema language selected by `--output`.
+fn export_schema(args: &SchemaArgs, file_type: FileType) -> Result<()> {
+    let mut reader_step = get_reader_step(
+        file_type,
+        ReadArgs {
+            path: args.file.clone(),
+            limit: Some(0),
+            offset: None,
+        },
+    )?;
+    let mut schema = reader_step.get()?.schema().as_ref().clone();
+    if file_type == FileType::Avro {
+        // Keep the writer schema as written, with its names, docs, and defaults.
+        let mut reader = BufReader::new(File::open(&args.file)?);
+        let header = read_avro_header(&mut reader)?;
+        schema.metadata.insert(
+            SCHEMA_METADATA_KEY.to_string(),
+            header.schema()?.to_string(),
+        );
     }
+    let name = match &args.name {
+        Some(name) => name.clone(),
+        None => Path::new(&args.file)
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "data".to_string()),
+    };
+    let text = match args.output {
+        SchemaOutputFormat::Avsc => to_avsc(&schema, args.name.as_deref())?,
+        SchemaOutputFormat::JsonSchema => to_json_schema(&schema, &name)?,
+        SchemaOutputFormat::Sql => to_sql(&schema, &name, args.dialect)?,
+        SchemaOutputFormat::Proto => to_proto(&schema, &name)?,
+        SchemaOutputFormat::Arrow => {
+            let stdout = std::io::stdout();
+            if stdout.is_terminal() {
+                bail!("--output arrow writes binary data; redirect it to a file");
+            }
+            write_arrow_ipc_schema(&schema, stdout.lock())?;
+            return Ok(());
+        }
+        _ => unreachable!("listing formats are handled by schema()"),
+    };
+    println!("{}", text.trim_end());
+    Ok(())
 }
 
 /// Extracts and prints the schema of an ORC file.
-fn schema_orc(path: &str, output: DisplayOutputFormat, sparse: bool) -> Result<()> {
+fn schema_orc(path: &str) -> Result<Vec<SchemaField>> {
     let file = File::open(path)?;
     let arrow_reader = ArrowReaderBuilder::try_new(file)?.build();
     let schema = arrow_reader.schema();
-    let fields: Vec<SchemaField> = schema
+    let fields = schema
         .fields()
         .iter()
         .map(|f| SchemaField {
             name: f.name().to_string(),
-            data_type: format!("{:?}", f.data_type()),
+            data_type: f.data_type().to_string(),
             converted_type: None,
             nullable: f.is_nullable(),
         })
         .collect();
-    print_schema(&fields, output, sparse)
+    Ok(fields)
 }
 
 /// Extracts and prints the schema of a Parquet file.
-fn schema_parquet(path: &str, output: DisplayOutputFormat, sparse: bool) -> Result<()> {
+fn schema_parquet(path: &str) -> Result<Vec<SchemaField>> {
     let file = File::open(path)?;
     let metadata = ParquetMetaDataReader::new().parse_and_finish(&file)?;
 
@@ -283,6 +450,6 @@ fn schema_parquet(path: &str, output: DisplayOutputFormat, sparse: bool) -> Resu
         .map(column_to_schema_output)
         .collect();
 
-    let fields: Vec<SchemaField> = columns.iter().map(SchemaOutput::to_schema_field).collect();
-    print_schema(&fields, output, sparse)
+    let fields = columns.iter().map(SchemaOutput::to_schema_field).collect();
+    Ok(fields)
 }
@@ -0,0 +1,97 @@
+//! `datu schema-check` - check the compatibility of two schemas using Avro evolution rules
+
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+use anyhow::Result;
+use anyhow::bail;
+use arrow_avro::schema::AvroSchema;
+use datu::FileType;
+use datu::cli::CompatibilityMode;
+use datu::cli::SchemaCheckArgs;
+use datu::pipeline::ReadArgs;
+use datu::pipeline::avro::compatibility::AvroSchemaDef;
+use datu::pipeline::avro::compatibility::BreakingChange;
+use datu::pipeline::avro::compatibility::check_compatibility;
+use datu::pipeline::avro::container::read_avro_header;
+use datu::pipeline::display::write_serializable;
+use datu::pipeline::get_reader_step;
+use serde::Serialize;
+
+/// The result of a compatibility check.
+#[derive(Serialize)]
+struct SchemaCheckReport {
+    mode: String,
+    compatible: bool,
+    breaking_changes: Vec<BreakingChange>,
+}
+
+/// The `datu schema-check` command. Returns `Ok(true)` when the schemas are compatible.
+pub fn schema_check(args: SchemaCheckArgs) -> Result<bool> {
+    let old = load_schema(&args.old)?;
+    let new = load_schema(&args.new)?;
+    let breaking_changes = check_compatibility(&old, &new, args.mode);
+    let report = SchemaCheckReport {
+        mode: args.mode.to_string(),
+        compatible: breaking_changes.is_empty(),
+        breaking_changes,
+    };
+    match args.output.serialized_format() {
+        None => print_report(&report, args.mode),
+        Some(format) => write_serializable(&report, format, std::io::stdout())?,
+    }
+    Ok(report.compatible)
+}
+
+/// Loads the Avro schema of a file. Avro schema files (`.avsc`, `.json`) are parsed as is;
+/// Avro data files use the writer schema from the file header; Parquet and ORC files use
+/// the Avro equivalent of their Arrow schema.
+fn load_schema(path: &str) -> Result<AvroSchemaDef> {
+    let extension = Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase);
+    if matches!(extension.as_deref(), Some("avsc" | "json")) {
+        let json = std::fs::read_to_string(path)?;
+        return Ok(AvroSchemaDef::parse(&json)?);
+    }
+    let json = match FileType::try_from(path)? {
+        FileType::Avro => {
+            let mut reader = BufReader::new(File::open(path)?);
+            let header = read_avro_header(&mut reader)?;
+            header.schema()?.to_string()
+        }
+        file_type @ (FileType::Parquet | FileType::Orc) => {
+            let mut source = get_reader_step(
+                file_type,
+                ReadArgs {
+                    path: path.to_string(),
+                    limit: None,
+                    offset: None,
+                },
+            )?;
+            let schema = source.get()?.schema();
+            AvroSchema::try_from(schema.as_ref())?.json_string
+        }
+        _ => bail!(
+            "schema-check only supports Parquet, Avro, and ORC files, or Avro schema files (.avsc, .json)"
+        ),
+    };
+    Ok(AvroSchemaDef::parse(&json)?)
+}
+
+/// Prints the breaking changes, one per line.
+fn print_report(report: &SchemaCheckReport, mode: CompatibilityMode) {
+    if report.compatible {
+        println!("Schemas are compatible ({mode})");
+        return;
+    }
+    println!(
+        "Found {} breaking change(s) ({mode}):",
+        report.breaking_changes.len()
+    );
+    for change in &report.breaking_changes {
+        println!("  {change}");
+    }
+}
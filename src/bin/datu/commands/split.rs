@@ -0,0 +1,388 @@
+//! `datu split` - split a file into several output files by rows, size, or key
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::mpsc::Receiver;
+use std::sync::mpsc::SyncSender;
+use std::sync::mpsc::sync_channel;
+use std::thread;
+use std::thread::JoinHandle;
+
+use anyhow::Result;
+use anyhow::bail;
+use arrow::array::RecordBatchReader;
+use arrow::datatypes::SchemaRef;
+use arrow::error::ArrowError;
+use arrow::record_batch::RecordBatch;
+use datu::FileType;
+use datu::cli::OverwriteOptions;
+use datu::cli::SplitArgs;
+use datu::pipeline::ReadArgs;
+use datu::pipeline::RecordBatchReaderSource;
+use datu::pipeline::Source;
+use datu::pipeline::Step;
+use datu::pipeline::output::persist;
+use datu::pipeline::output::temp_path_for;
+use datu::pipeline::record_batch_filter::SelectColumnsStep;
+use datu::pipeline::split::KeyPartitioner;
+use datu::pipeline::split::OutputTemplate;
+use datu::pipeline::split::SplitLimit;
+use datu::pipeline::split::SplitParts;
+use datu::utils::parse_select_columns;
+use tempfile::TempPath;
+
+use crate::commands::convert::WriteOptions;
+use crate::commands::convert::check_output;
+use crate::commands::convert::execute_writer;
+use crate::commands::convert::get_input_reader_step;
+
+/// Splits the input into several output files named by the output template.
+pub fn split(args: SplitArgs) -> Result<()> {
+    let input_file_type: FileType = args.input.as_str().try_into()?;
+    let output_file_type: FileType = args.output.as_str().try_into()?;
+    let template = OutputTemplate::parse(&args.output)?;
+    if args.by.is_some() {
+        if !template.has_key() && !template.has_number() {
+            bail!(
+                "Output template '{}' must contain {{key}} or {{n}} to name each file",
+                args.output
+            );
+        }
+    } else if !template.has_number() {
+        bail!(
+            "Output template '{}' must contain {{n}} to number each file",
+            args.output
+        );
+    }
+
+    println!("Splitting {} into {}", args.input, args.output);
+
+    let mut source = get_input_reader_step(
+        input_file_type,
+        ReadArgs {
+            path: args.input.clone(),
+            limit: None,
+            offset: None,
+        },
+        &args.avro,
+    )?;
+    let columns = args.select.as_deref().map(parse_select_columns);
+    let mut writer = PartWriter {
+        input: args.input.clone(),
+        overwrite: args.overwrite.clone(),
+        output_file_type,
+        columns,
+        options: WriteOptions {
+            sparse: args.sparse,
+            json_pretty: args.json_pretty,
+            parquet: args.parquet.clone(),
+            avro: args.avro.clone(),
+            orc: args.orc.clone(),
+            csv: args.csv.clone(),
+            xlsx: args.xlsx.clone(),
+            no_clobber: false,
+        },
+        written: HashSet::new(),
+    };
+
+    if let Some(by) = &args.by {
+        if args.max_open_files == 0 {
+            bail!("--max-open-files must be greater than zero");
+        }
+        // Columns are selected by each file's writer, so the --by column need not be written.
+        let reader = source.get()?;
+        let schema = reader.schema();
+        let partitioner = KeyPartitioner::try_new(&schema, by)?;
+        let mut open = OpenParts {
+            by: by.clone(),
+            max_open_files: args.max_open_files,
+            no_clobber: args.overwrite.no_clobber,
+            schema,
+            parts: HashMap::new(),
+            closed: HashSet::new(),
+            next_number: 0,
+            clock: 0,
+        };
+        for batch in reader {
+            for (key, rows) in partitioner.partition(&batch?)? {
+                open.write(&mut writer, &template, key, rows)?;
+            }
+        }
+        open.finish()?;
+    } else {
+        let limit = match (args.rows_per_file, args.max_bytes) {
+            (Some(0), _) => bail!("--rows-per-file must be greater than zero"),
+            (Some(rows), _) => SplitLimit::Rows(rows),
+            (None, Some(bytes)) => SplitLimit::Bytes(bytes),
+            (None, None) => bail!("One of --rows-per-file, --max-bytes, or --by is required"),
+        };
+        if let Some(columns) = writer.columns.take() {
+            source = SelectColumnsStep { columns }.execute(source)?;
+        }
+        let mut parts = SplitParts::new(source.get()?, limit);
+        for n in 0.. {
+            // Each part is written to a temporary file beside its path, as many times as it
+            // takes to fit the limit, and then moved there.
+            let path = template.render(n, None);
+            let mut scratch: Option<TempPath> = None;
+            let more = parts.write_next_part(|part| {
+                if scratch.is_none() {
+                    writer.prepare(&path)?;
+                }
+                let temp = temp_path_for(&path)?;
+                // The empty temporary file is replaced.
+                writer.write(part, &temp.to_string_lossy(), false)?;
+                let size = std::fs::metadata(&temp)?.len();
+                scratch = Some(temp);
+                Ok::<_, anyhow::Error>(size)
+            })?;
+            match scratch {
+                Some(temp) if more => persist(temp, &path, args.overwrite.no_clobber)?,
+                _ => break,
+            }
+        }
+    }
+
+    println!("Wrote {} files", writer.written.len());
+    Ok(())
+}
+
+/// Writes each part with the writer step for the output format.
+struct PartWriter {
+    input: String,
+    overwrite: OverwriteOptions,
+    output_file_type: FileType,
+    columns: Option<Vec<String>>,
+    options: WriteOptions,
+    written: HashSet<String>,
+}
+
+impl PartWriter {
+    /// Checks that the file `path` may be written and creates its directory.
+    fn prepare(&mut self, path: &str) -> Result<()> {
+        if !self.written.insert(path.to_string()) {
+            bail!("Output template produced the file name '{path}' more than once");
+        }
+        check_output(path, std::slice::from_ref(&self.input), &self.overwrite)?;
+        if let Some(parent) = Path::new(path).parent()
+            && !parent.as_os_str().is_empty()
+        {
+            std::fs::create_dir_all(parent)?;
+        }
+        Ok(())
+    }
+
+    /// Writes `part` to `path`; with `no_clobber`, fails if a file exists there by the end.
+    fn write(&self, part: RecordBatchReaderSource, path: &str, no_clobber: bool) -> Result<()> {
+        let options = WriteOptions {
+            no_clobber,
+            ..self.options.clone()
+        };
+        write_part(
+            part,
+            self.columns.clone(),
+            self.output_file_type,
+            path,
+            &options,
+        )
+    }
+
+    /// Starts writing `path` on a thread of its own, from record batches sent to the returned
+    /// part.
+    fn open(&mut self, path: String, schema: SchemaRef, no_clobber: bool) -> Result<OpenPart> {
+        self.prepare(&path)?;
+        let (sender, receiver) = sync_channel(OPEN_PART_BATCHES);
+        let columns = self.columns.clone();
+        let output_file_type = self.output_file_type;
+        let options = WriteOptions {
+            no_clobber,
+            ..self.options.clone()
+        };
+        let thread_path = path.clone();
+        let handle = thread::spawn(move || {
+            let part = Box::new(ChannelSource {
+                receiver: Some(receiver),
+                schema,
+            });
+            write_part(part, columns, output_file_type, &thread_path, &options)
+        });
+        Ok(OpenPart {
+            path,
+            sender: Some(sender),
+            handle: Some(handle),
+            last_written: 0,
+        })
+    }
+}
+
+fn write_part(
+    mut part: RecordBatchReaderSource,
+    columns: Option<Vec<String>>,
+    output_file_type: FileType,
+    path: &str,
+    options: &WriteOptions,
+) -> Result<()> {
+    if let Some(columns) = columns {
+        part = SelectColumnsStep { columns }.execute(part)?;
+    }
+    execute_writer(part, output_file_type, path, options)
+}
+
+/// Record batches that may be queued for an open file before its writer catches up.
+const OPEN_PART_BATCHES: usize = 2;
+
+/// The files being written for `--by`, at most `max_open_files` at once. When another file is
+/// needed, the least recently written one is closed.
+struct OpenParts {
+    by: String,
+    max_open_files: usize,
+    no_clobber: bool,
+    schema: SchemaRef,
+    parts: HashMap<String, OpenPart>,
+    /// Keys whose file was closed to make room for another.
+    closed: HashSet<String>,
+    next_number: usize,
+    clock: u64,
+}
+
+impl OpenParts {
+    /// Sends the rows for `key` to its file, opening the file first if needed.
+    fn write(
+        &mut self,
+        writer: &mut PartWriter,
+        template: &OutputTemplate,
+        key: String,
+        rows: RecordBatch,
+    ) -> Result<()> {
+        self.clock += 1;
+        if !self.parts.contains_key(&key) {
+            if self.closed.contains(&key) && !template.has_number() {
+                bail!(
+                    "Rows with {} '{key}' came after its file was closed to stay within \
+                     --max-open-files {}; raise the limit or add {{n}} to the output template",
+                    self.by,
+                    self.max_open_files
+                );
+            }
+            if self.parts.len() >= self.max_open_files
+                && let Some(oldest) = self
+                    .parts
+                    .iter()
+                    .min_by_key(|(_, part)| part.last_written)
+                    .map(|(key, _)| key.clone())
+                && let Some(mut part) = self.parts.remove(&oldest)
+            {
+                part.finish()?;
+                self.closed.insert(oldest);
+            }
+            let path = template.render(self.next_number, Some(&key));
+            self.next_number += 1;
+            let part = writer.open(path, self.schema.clone(), self.no_clobber)?;
+            self.parts.insert(key.clone(), part);
+        }
+        let part = self
+            .parts
+            .get_mut(&key)
+            .expect("the file for the key was just opened");
+        part.last_written = self.clock;
+        part.send(rows)
+    }
+
+    /// Finishes writing every open file.
+    fn finish(self) -> Result<()> {
+        for (_, mut part) in self.parts {
+            part.finish()?;
+        }
+        Ok(())
+    }
+}
+
+/// A file being written on its own thread.
+struct OpenPart {
+    path: String,
+    sender: Option<SyncSender<arrow::error::Result<RecordBatch>>>,
+    handle: Option<JoinHandle<Result<()>>>,
+    last_written: u64,
+}
+
+impl OpenPart {
+    fn send(&mut self, rows: RecordBatch) -> Result<()> {
+        if let Some(sender) = &self.sender
+            && sender.send(Ok(rows)).is_ok()
+        {
+            return Ok(());
+        }
+        // The writer only stops early on an error, which finishing it returns.
+        self.finish()?;
+        bail!(
+            "Stopped writing {} before all its rows were written",
+            self.path
+        )
+    }
+
+    /// Closes the file and waits for its writer to finish.
+    fn finish(&mut self) -> Result<()> {
+        self.sender = None;
+        match self.handle.take() {
+            Some(handle) => match handle.join() {
+                Ok(result) => result,
+                Err(_) => bail!("The writer for {} panicked", self.path),
+            },
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for OpenPart {
+    /// A file that was not finished, because the split failed, is abandoned rather than
+    /// committed with the rows it has so far.
+    fn drop(&mut self) {
+        if let Some(sender) = self.sender.take() {
+            let _ = sender.send(Err(ArrowError::ExternalError("split failed".into())));
+            drop(sender);
+            if let Some(handle) = self.handle.take() {
+                let _ = handle.join();
+            }
+        }
+    }
+}
+
+/// Source of the record batches sent to an open file.
+struct ChannelSource {
+    receiver: Option<Receiver<arrow::error::Result<RecordBatch>>>,
+    schema: SchemaRef,
+}
+
+impl Source<dyn RecordBatchReader + 'static> for ChannelSource {
+    fn get(&mut self) -> datu::Result<Box<dyn RecordBatchReader + 'static>> {
+        let receiver = self
+            .receiver
+            .take()
+            .ok_or_else(|| datu::Error::GenericError("Reader already taken".to_string()))?;
+        Ok(Box::new(ChannelReader {
+            receiver,
+            schema: self.schema.clone(),
+        }))
+    }
+}
+
+/// Record batch reader that yields the batches sent to it until its sender is dropped.
+struct ChannelReader {
+    receiver: Receiver<arrow::error::Result<RecordBatch>>,
+    schema: SchemaRef,
+}
+
+impl RecordBatchReader for ChannelReader {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+impl Iterator for ChannelReader {
+    type Item = arrow::error::Result<RecordBatch>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.receiver.recv().ok()
+    }
+}
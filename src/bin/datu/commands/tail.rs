@@ -4,6 +4,7 @@ use anyhow::Result;
 use anyhow::bail;
 use datu::Error;
 use datu::FileType;
+use datu::cli::CsvOptions;
 use datu::cli::HeadsOrTails;
 use datu::pipeline::ReadArgs;
 use datu::pipeline::RecordBatchReaderSource;
@@ -55,6 +56,8 @@ fn tail_parquet(args: HeadsOrTails) -> Result<()> {
     let display_step = DisplayWriterStep {
         output_format: args.output,
         sparse,
+        max_col_width: args.max_col_width,
+        csv: args.csv.clone(),
     };
     display_step.execute(reader_step).map_err(Into::into)
 }
@@ -65,6 +68,8 @@ fn tail_from_reader(
     number: usize,
     output: datu::cli::DisplayOutputFormat,
     sparse: bool,
+    max_col_width: Option<usize>,
+    csv: CsvOptions,
 ) -> Result<()> {
     let reader = reader_step.get()?;
     let batches: Vec<arrow::record_batch::RecordBatch> = reader
@@ -99,6 +104,8 @@ fn tail_from_reader(
     let display_step = DisplayWriterStep {
         output_format: output,
         sparse,
+        max_col_width,
+        csv,
     };
     display_step.execute(reader_step).map_err(Into::into)
 }
@@ -111,6 +118,7 @@ fn tail_avro(args: HeadsOrTails) -> Result<()> {
             limit: None,
             offset: None,
         },
+        reader_schema: None,
     });
     if let Some(select) = &args.select {
         let columns = parse_select_columns(select);
@@ -118,7 +126,14 @@ fn tail_avro(args: HeadsOrTails) -> Result<()> {
         reader_step = select_step.execute(reader_step)?;
     }
     let sparse = args.sparse;
-    tail_from_reader(reader_step, args.number, args.output, sparse)
+    tail_from_reader(
+        reader_step,
+        args.number,
+        args.output,
+        sparse,
+        args.max_col_width,
+        args.csv.clone(),
+    )
 }
 
 /// Prints the last N lines of an ORC file.
@@ -145,6 +160,8 @@ fn tail_orc(args: HeadsOrTails) -> Result<()> {
     let display_step = DisplayWriterStep {
         output_format: args.output,
         sparse,
+        max_col_width: args.max_col_width,
+        csv: args.csv.clone(),
     };
     display_step.execute(reader_step).map_err(Into::into)
 }
@@ -0,0 +1,478 @@
+//! `datu validate` - fully decode a Parquet, Avro, or ORC file and report structural damage
+
+use std::fs::File;
+use std::io::BufReader;
+use std::io::Read;
+use std::panic::AssertUnwindSafe;
+
+use anyhow::Result;
+use arrow::array::RecordBatchReader;
+use arrow_avro::reader::ReaderBuilder;
+use datu::FileType;
+use datu::cli::ValidateArgs;
+use datu::pipeline::avro::container::AvroBlockReader;
+use datu::pipeline::avro::container::read_avro_header;
+use datu::pipeline::display::write_serializable;
+use orc_rust::arrow_reader::ArrowReaderBuilder;
+use parquet::arrow::arrow_reader::ArrowReaderMetadata;
+use parquet::arrow::arrow_reader::ArrowReaderOptions;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::file::reader::FileReader;
+use parquet::file::reader::SerializedFileReader;
+use serde::Serialize;
+
+/// The result of validating a file.
+#[derive(Serialize)]
+struct ValidationReport {
+    file: String,
+    format: String,
+    healthy: bool,
+    /// The kind of unit checked: row groups, blocks, or stripes.
+    unit: String,
+    units_checked: usize,
+    rows_checked: u64,
+    problems: Vec<Problem>,
+}
+
+/// Damage found in a file.
+#[derive(Serialize)]
+struct Problem {
+    /// Where the damage is: `file`, `row_group`, `column_chunk`, `block`, or `stripe`.
+    location: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    index: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    column: Option<String>,
+    /// The affected rows.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rows: Option<RowRange>,
+    message: String,
+}
+
+/// A half-open range of rows; `end` is unknown when the damage prevents counting rows.
+#[derive(Clone, Copy, Serialize)]
+struct RowRange {
+    start: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end: Option<u64>,
+}
+
+impl Problem {
+    fn file(message: impl ToString) -> Self {
+        Self {
+            location: "file".to_string(),
+            index: None,
+            column: None,
+            rows: None,
+            message: message.to_string(),
+        }
+    }
+
+    fn unit(location: &str, index: usize, rows: Option<RowRange>, message: impl ToString) -> Self {
+        Self {
+            location: location.to_string(),
+            index: Some(index),
+            column: None,
+            rows,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.location.replace('_', " "))?;
+        if let Some(index) = self.index {
+            write!(f, " {index}")?;
+        }
+        if let Some(column) = &self.column {
+            write!(f, ", column '{column}'")?;
+        }
+        if let Some(rows) = self.rows {
+            match rows.end {
+                Some(end) => write!(f, " (rows {}..{end})", rows.start)?,
+                None => write!(f, " (rows {}..)", rows.start)?,
+            }
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// Problems and coverage collected while validating a file.
+struct Validation {
+    unit: &'static str,
+    units_checked: usize,
+    rows_checked: u64,
+    problems: Vec<Problem>,
+}
+
+impl Validation {
+    fn new(unit: &'static str) -> Self {
+        Self {
+            unit,
+            units_checked: 0,
+            rows_checked: 0,
+            problems: Vec::new(),
+        }
+    }
+}
+
+/// The `datu validate` command. Returns `Ok(true)` when no damage was found.
+pub fn validate(args: ValidateArgs) -> Result<bool> {
+    let file_type: FileType = args.file.as_str().try_into()?;
+    // Decoder panics are reported as problems, so keep the default hook from printing them.
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(|_| {}));
+    let result = match file_type {
+        FileType::Parquet => validate_parquet(&args.file).map(|v| ("parquet", v)),
+        FileType::Avro => validate_avro(&args.file).map(|v| ("avro", v)),
+        FileType::Orc => validate_orc(&args.file).map(|v| ("orc", v)),
+        _ => Err(anyhow::anyhow!(
+            "Only Parquet, Avro, and ORC files can be validated"
+        )),
+    };
+    std::panic::set_hook(hook);
+    let (format, validation) = result?;
+    let report = ValidationReport {
+        file: args.file.clone(),
+        format: format.to_string(),
+        healthy: validation.problems.is_empty(),
+        unit: validation.unit.to_string(),
+        units_checked: validation.units_checked,
+        rows_checked: validation.rows_checked,
+        problems: validation.problems,
+    };
+    match args.output.serialized_format() {
+        None => print_report(&report),
+        Some(format) => write_serializable(&report, format, std::io::stdout())?,
+    }
+    Ok(report.healthy)
+}
+
+/// Reads every page of every column chunk (verifying page checksums and decompressing), then
+/// decodes every row group into record batches.
+fn validate_parquet(path: &str) -> Result<Validation> {
+    let mut validation = Validation::new("row_groups");
+    let file = File::open(path)?;
+    let reader = match SerializedFileReader::new(file.try_clone()?) {
+        Ok(reader) => reader,
+        Err(e) => {
+            validation
+                .problems
+                .push(Problem::file(format!("unreadable footer: {e}")));
+            return Ok(validation);
+        }
+    };
+    let arrow_metadata = ArrowReaderMetadata::load(&file, ArrowReaderOptions::default());
+
+    let metadata = reader.metadata();
+    let mut start = 0u64;
+    for (i, row_group) in metadata.row_groups().iter().enumerate() {
+        let num_rows = row_group.num_rows() as u64;
+        let rows = Some(RowRange {
+            start,
+            end: Some(start + num_rows),
+        });
+        start += num_rows;
+        validation.units_checked += 1;
+        let problems_before = validation.problems.len();
+
+        match reader.get_row_group(i) {
+            Ok(row_group_reader) => {
+                for (j, column) in row_group.columns().iter().enumerate() {
+                    let pages = catch_panic(|| {
+                        let mut pages = row_group_reader
+                            .get_column_page_reader(j)
+                            .map_err(|e| e.to_string())?;
+                        while pages.get_next_page().map_err(|e| e.to_string())?.is_some() {}
+                        Ok(())
+                    });
+                    if let Err(e) = pages {
+                        validation.problems.push(Problem {
+                            location: "column_chunk".to_string(),
+                            index: Some(i),
+                            column: Some(column.column_path().string()),
+                            rows,
+                            message: e.to_string(),
+                        });
+                    }
+                }
+            }
+            Err(e) => validation
+                .problems
+                .push(Problem::unit("row_group", i, rows, e)),
+        }
+        // Decoding a row group with a damaged column chunk would only repeat the same error.
+        if validation.problems.len() > problems_before {
+            continue;
+        }
+
+        let decoded = match &arrow_metadata {
+            Ok(arrow_metadata) => ParquetRecordBatchReaderBuilder::new_with_metadata(
+                file.try_clone()?,
+                arrow_metadata.clone(),
+            )
+            .with_row_groups(vec![i])
+            .build()
+            .map_err(|e| e.to_string())
+            .and_then(count_rows),
+            Err(e) => Err(e.to_string()),
+        };
+        check_decoded("row_group", i, rows, decoded, &mut validation);
+    }
+    Ok(validation)
+}
+
+/// Checks every block's framing and sync marker, then decompresses and decodes each block.
+fn validate_avro(path: &str) -> Result<Validation> {
+    let mut validation = Validation::new("blocks");
+    let mut reader = BufReader::new(File::open(path)?);
+    let header = match read_avro_header(&mut reader) {
+        Ok(header) => header,
+        Err(e) => {
+            validation.problems.push(Problem::file(e));
+            return Ok(validation);
+        }
+    };
+    // Each block is decoded on its own, behind a copy of the file header.
+    let mut header_bytes = vec![0u8; header.length as usize];
+    File::open(path)?.read_exact(&mut header_bytes)?;
+
+    let mut start = 0u64;
+    let mut index = 0;
+    for block in AvroBlockReader::new(reader, &header) {
+        let block = match block {
+            Ok(block) => block,
+            Err(e) => {
+                // The block reader stops after the first framing error.
+                validation.problems.push(Problem::unit(
+                    "block",
+                    index,
+                    Some(RowRange { start, end: None }),
+                    e,
+                ));
+                break;
+            }
+        };
+        index = block.index + 1;
+        validation.units_checked += 1;
+        let rows = Some(RowRange {
+            start,
+            end: Some(start + block.record_count),
+        });
+        start += block.record_count;
+        if block.sync != header.sync {
+            validation.problems.push(Problem::unit(
+                "block",
+                block.index,
+                rows,
+                format!(
+                    "sync marker mismatch at offset {}: the block is corrupt or not part of this file",
+                    block.offset
+                ),
+            ));
+            continue;
+        }
+
+        let mut bytes = header_bytes.clone();
+        bytes.extend_from_slice(&block.to_bytes());
+        let decoded = ReaderBuilder::new()
+            .build(std::io::Cursor::new(bytes))
+            .map_err(|e| e.to_string())
+            .and_then(count_rows);
+        check_decoded("block", block.index, rows, decoded, &mut validation);
+    }
+    Ok(validation)
+}
+
+/// Decodes each stripe on its own.
+fn validate_orc(path: &str) -> Result<Validation> {
+    let mut validation = Validation::new("stripes");
+    let builder = match ArrowReaderBuilder::try_new(File::open(path)?) {
+        Ok(builder) => builder,
+        Err(e) => {
+            validation
+                .problems
+                .push(Problem::file(format!("unreadable footer: {e}")));
+            return Ok(validation);
+        }
+    };
+    let stripes = builder.file_metadata().stripe_metadatas().to_vec();
+
+    let mut start = 0u64;
+    for (i, stripe) in stripes.iter().enumerate() {
+        validation.units_checked += 1;
+        let rows = Some(RowRange {
+            start,
+            end: Some(start + stripe.number_of_rows()),
+        });
+        start += stripe.number_of_rows();
+        // A byte range containing only this stripe's offset selects just this stripe.
+        let offset = stripe.offset() as usize;
+        let decoded = ArrowReaderBuilder::try_new(File::open(path)?)
+            .map_err(|e| e.to_string())
+            .and_then(|builder| {
+                let reader = builder.with_file_byte_range(offset..offset + 1).build();
+                count_rows(reader)
+            });
+        check_decoded("stripe", i, rows, decoded, &mut validation);
+    }
+    Ok(validation)
+}
+
+/// Reads all batches, returning the number of rows decoded.
+fn count_rows(reader: impl RecordBatchReader) -> std::result::Result<u64, String> {
+    catch_panic(|| {
+        let mut rows = 0u64;
+        for batch in reader {
+            rows += batch.map_err(|e| e.to_string())?.num_rows() as u64;
+        }
+        Ok(rows)
+    })
+}
+
+/// Runs a decoding function, turning a panic on malformed input into an error so that the
+/// remaining units can still be checked.
+fn catch_panic<T>(
+    f: impl FnOnce() -> std::result::Result<T, String>,
+) -> std::result::Result<T, String> {
+    std::panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown error".to_string());
+        Err(format!("decoder panicked: {message}"))
+    })
+}
+
+/// Records a decoding error, or a mismatch between the decoded and expected row counts.
+fn check_decoded(
+    location: &str,
+    index: usize,
+    rows: Option<RowRange>,
+    decoded: std::result::Result<u64, String>,
+    validation: &mut Validation,
+) {
+    let expected = rows
+        .and_then(|r| Some(r.end? - r.start))
+        .unwrap_or_default();
+    match decoded {
+        Ok(n) if n == expected => validation.rows_checked += n,
+        Ok(n) => validation.problems.push(Problem::unit(
+            location,
+            index,
+            rows,
+            format!("decoded {n} rows, expected {expected}"),
+        )),
+        Err(e) => validation
+            .problems
+            .push(Problem::unit(location, index, rows, e)),
+    }
+}
+
+/// Prints a human-readable validation report.
+fn print_report(report: &ValidationReport) {
+    println!("File: {}", report.file);
+    println!("Format: {}", report.format);
+    let unit = report.unit.replace('_', " ");
+    let mut chars = unit.chars();
+    let unit = chars
+        .next()
+        .map(|c| c.to_uppercase().chain(chars).collect::<String>())
+        .unwrap_or_default();
+    println!("{unit} checked: {}", report.units_checked);
+    println!("Rows checked: {}", report.rows_checked);
+    if report.healthy {
+        println!("Status: OK");
+        return;
+    }
+    println!("Status: DAMAGED");
+    println!("Problems ({}):", report.problems.len());
+    for problem in &report.problems {
+        println!("  {problem}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Copies a fixture to a temporary file, applying `corrupt` to its bytes.
+    fn corrupted_copy(
+        fixture: &str,
+        corrupt: impl FnOnce(&mut Vec<u8>),
+    ) -> (tempfile::TempDir, String) {
+        let mut bytes = std::fs::read(fixture).expect("Failed to read fixture");
+        corrupt(&mut bytes);
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let name = std::path::Path::new(fixture).file_name().unwrap();
+        let path = dir.path().join(name);
+        std::fs::write(&path, bytes).expect("Failed to write file");
+        (dir, path.to_str().unwrap().to_string())
+    }
+
+    #[test]
+    fn test_validate_healthy_files() {
+        let parquet = validate_parquet("fixtures/userdata.parquet").unwrap();
+        assert!(parquet.problems.is_empty());
+        assert_eq!(parquet.rows_checked, 1000);
+        let avro = validate_avro("fixtures/userdata5.avro").unwrap();
+        assert!(avro.problems.is_empty());
+        assert_eq!(avro.units_checked, 3);
+        assert_eq!(avro.rows_checked, 1000);
+        let orc = validate_orc("fixtures/userdata.orc").unwrap();
+        assert!(orc.problems.is_empty());
+        assert_eq!(orc.rows_checked, 1000);
+    }
+
+    #[test]
+    fn test_validate_truncated_files() {
+        let (_dir, path) = corrupted_copy("fixtures/userdata.parquet", |b| b.truncate(b.len() / 2));
+        let parquet = validate_parquet(&path).unwrap();
+        assert_eq!(parquet.problems[0].location, "file");
+
+        let (_dir, path) = corrupted_copy("fixtures/userdata5.avro", |b| b.truncate(b.len() - 100));
+        let avro = validate_avro(&path).unwrap();
+        assert_eq!(avro.units_checked, 2);
+        let problem = &avro.problems[0];
+        assert_eq!(problem.location, "block");
+        assert_eq!(problem.index, Some(2));
+        assert!(problem.message.contains("truncated"));
+    }
+
+    #[test]
+    fn test_validate_avro_bad_sync_marker() {
+        let (_dir, path) = corrupted_copy("fixtures/userdata5.avro", |b| {
+            // Overwrite the last byte of the final block's sync marker.
+            let last = b.len() - 1;
+            b[last] ^= 0xff;
+        });
+        let avro = validate_avro(&path).unwrap();
+        assert_eq!(avro.problems.len(), 1);
+        assert_eq!(avro.problems[0].index, Some(2));
+        assert!(avro.problems[0].message.contains("sync marker mismatch"));
+    }
+
+    #[test]
+    fn test_validate_parquet_corrupt_page() {
+        let metadata = SerializedFileReader::new(File::open("fixtures/userdata.parquet").unwrap())
+            .unwrap()
+            .metadata()
+            .clone();
+        let column = metadata.row_group(0).column(1);
+        let (start, _) = column.byte_range();
+        let (_dir, path) = corrupted_copy("fixtures/userdata.parquet", |b| {
+            // Overwrite the start of the column chunk, which holds its first page header.
+            let start = start as usize;
+            b[start..start + 16].fill(0xff);
+        });
+        let parquet = validate_parquet(&path).unwrap();
+        assert!(!parquet.problems.is_empty());
+        let problem = &parquet.problems[0];
+        assert_eq!(problem.location, "column_chunk");
+        assert_eq!(problem.column.as_deref(), Some("id"));
+        assert_eq!(problem.index, Some(0));
+        assert_eq!(problem.rows.and_then(|r| r.end), Some(1000));
+    }
+}
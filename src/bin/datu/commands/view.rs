@@ -0,0 +1,756 @@
+//! `datu view` - browse a Parquet, Avro, or ORC file in a full-screen terminal viewer
+
+use std::fs::File;
+use std::io::BufReader;
+use std::io::IsTerminal;
+
+use anyhow::Result;
+use anyhow::bail;
+use arrow::array::Array;
+use arrow::array::ArrayRef;
+use arrow::array::make_comparator;
+use arrow::compute::SortOptions;
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+use arrow::util::display::ArrayFormatter;
+use arrow::util::display::FormatOptions;
+use datu::Error;
+use datu::FileType;
+use datu::cli::ViewArgs;
+use datu::pipeline::ReadArgs;
+use datu::pipeline::avro::container::AvroBlockReader;
+use datu::pipeline::avro::container::read_avro_header;
+use datu::pipeline::get_reader_step;
+use orc_rust::reader::metadata::read_metadata;
+use parquet::file::metadata::ParquetMetaDataReader;
+use ratatui::DefaultTerminal;
+use ratatui::Frame;
+use ratatui::crossterm::event;
+use ratatui::crossterm::event::Event;
+use ratatui::crossterm::event::KeyCode;
+use ratatui::crossterm::event::KeyEvent;
+use ratatui::crossterm::event::KeyEventKind;
+use ratatui::crossterm::event::KeyModifiers;
+use ratatui::layout::Constraint;
+use ratatui::layout::Layout;
+use ratatui::layout::Rect;
+use ratatui::style::Modifier;
+use ratatui::style::Style;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::widgets::Block;
+use ratatui::widgets::Borders;
+use ratatui::widgets::Cell;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::Row;
+use ratatui::widgets::Table;
+
+/// Maximum width of a table column, in characters; longer values are truncated.
+const MAX_COLUMN_WIDTH: usize = 30;
+/// Number of pages of rows read around the visible rows, so nearby scrolling needs no reads.
+const PAGES_PER_READ: usize = 3;
+/// Key help shown in the status line.
+const HELP: &str = "↑↓ rows  ←→ columns  PgUp/PgDn  g/G top/bottom  : row  / search  n next  s schema  t stats  q quit";
+
+/// The `datu view` command
+pub fn view(args: ViewArgs) -> Result<()> {
+    if !std::io::stdout().is_terminal() {
+        bail!("datu view needs an interactive terminal; use head, tail, or convert instead");
+    }
+    let mut viewer = Viewer::open(&args.file)?;
+    let mut terminal = ratatui::init();
+    let result = run(&mut terminal, &mut viewer);
+    ratatui::restore();
+    result
+}
+
+fn run(terminal: &mut DefaultTerminal, viewer: &mut Viewer) -> Result<()> {
+    loop {
+        let size = terminal.size()?;
+        // The status line, the table borders, and the header row take four lines.
+        viewer.height = (size.height as usize).saturating_sub(4).max(1);
+        viewer.load_visible()?;
+        terminal.draw(|frame| viewer.render(frame))?;
+        // Slow work runs after a redraw, so its progress message is visible.
+        if let Some(pending) = viewer.pending.take() {
+            viewer.run_pending(pending);
+            continue;
+        }
+        if let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+            && !viewer.handle_key(key)
+        {
+            return Ok(());
+        }
+    }
+}
+
+/// Side panel shown next to the table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Panel {
+    None,
+    Schema,
+    Stats,
+}
+
+/// Text being typed at the status line.
+#[derive(Debug, PartialEq, Eq)]
+enum Prompt {
+    Row(String),
+    Search(String),
+}
+
+/// Work that runs after the next redraw.
+#[derive(Debug, PartialEq, Eq)]
+enum Pending {
+    Search(String),
+    Stats,
+}
+
+/// A window of formatted rows read from the file; `None` is a null value.
+struct Page {
+    offset: usize,
+    rows: Vec<Vec<Option<String>>>,
+}
+
+/// Summary statistics of a column, computed by scanning the whole file.
+struct ColumnStats {
+    non_null: usize,
+    nulls: usize,
+    min: Option<String>,
+    max: Option<String>,
+}
+
+/// State of the viewer: the file being viewed, the position in it, and what is shown.
+struct Viewer {
+    path: String,
+    file_type: FileType,
+    schema: SchemaRef,
+    total_rows: usize,
+    /// Number of rows that fit in the table.
+    height: usize,
+    /// First row shown in the table.
+    top: usize,
+    /// Selected row.
+    cursor: usize,
+    /// First column shown in the table.
+    column: usize,
+    page: Option<Page>,
+    panel: Panel,
+    stats: Option<Vec<ColumnStats>>,
+    prompt: Option<Prompt>,
+    pending: Option<Pending>,
+    last_search: Option<String>,
+    message: Option<String>,
+}
+
+impl Viewer {
+    fn open(path: &str) -> Result<Self> {
+        let file_type: FileType = path.try_into()?;
+        let total_rows = count_rows(path, file_type)?;
+        let schema = get_reader_step(file_type, read_args(path, None, Some(0)))?
+            .get()?
+            .schema();
+        Ok(Self {
+            path: path.to_string(),
+            file_type,
+            schema,
+            total_rows,
+            height: 20,
+            top: 0,
+            cursor: 0,
+            column: 0,
+            page: None,
+            panel: Panel::None,
+            stats: None,
+            prompt: None,
+            pending: None,
+            last_search: None,
+            message: None,
+        })
+    }
+
+    /// Makes sure the visible rows are in the page, reading a new window around them if not.
+    fn load_visible(&mut self) -> Result<()> {
+        self.scroll_to_cursor();
+        let end = (self.top + self.height).min(self.total_rows);
+        if let Some(page) = &self.page
+            && page.offset <= self.top
+            && page.offset + page.rows.len() >= end
+        {
+            return Ok(());
+        }
+        let offset = self.top.saturating_sub(self.height);
+        let limit = self.height * PAGES_PER_READ;
+        let mut reader = get_reader_step(
+            self.file_type,
+            read_args(&self.path, Some(offset), Some(limit)),
+        )?
+        .get()?;
+        let mut rows = Vec::with_capacity(limit);
+        for batch in &mut reader {
+            rows.extend(format_rows(&batch?)?);
+        }
+        self.page = Some(Page { offset, rows });
+        Ok(())
+    }
+
+    /// Returns the formatted row at `index` if it has been read.
+    fn row(&self, index: usize) -> Option<&[Option<String>]> {
+        let page = self.page.as_ref()?;
+        let i = index.checked_sub(page.offset)?;
+        page.rows.get(i).map(Vec::as_slice)
+    }
+
+    /// Scrolls the table so the selected row is visible.
+    fn scroll_to_cursor(&mut self) {
+        if self.cursor < self.top {
+            self.top = self.cursor;
+        } else if self.cursor >= self.top + self.height {
+            self.top = self.cursor + 1 - self.height;
+        }
+    }
+
+    fn move_cursor(&mut self, delta: isize) {
+        let last = self.total_rows.saturating_sub(1);
+        self.cursor = self.cursor.saturating_add_signed(delta).min(last);
+    }
+
+    /// Selects the row with the given zero-based index, clamped to the file.
+    fn jump_to(&mut self, row: usize) {
+        self.cursor = row.min(self.total_rows.saturating_sub(1));
+        // Put the row at the top of the table, as far as the end of the file allows.
+        self.top = self.cursor.min(self.total_rows.saturating_sub(self.height));
+    }
+
+    fn scroll_columns(&mut self, delta: isize) {
+        let last = self.schema.fields().len().saturating_sub(1);
+        self.column = self.column.saturating_add_signed(delta).min(last);
+    }
+
+    /// Handles a key press; returns false when the viewer should exit.
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        if let Some(prompt) = self.prompt.take() {
+            self.handle_prompt_key(prompt, key);
+            return true;
+        }
+        self.message = None;
+        let page = self.height as isize;
+        match key.code {
+            KeyCode::Char('q') => return false,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return false,
+            KeyCode::Esc if self.panel != Panel::None => self.panel = Panel::None,
+            KeyCode::Esc => return false,
+            KeyCode::Down | KeyCode::Char('j') => self.move_cursor(1),
+            KeyCode::Up | KeyCode::Char('k') => self.move_cursor(-1),
+            KeyCode::PageDown | KeyCode::Char(' ') => self.move_cursor(page),
+            KeyCode::PageUp | KeyCode::Char('b') => self.move_cursor(-page),
+            KeyCode::Home | KeyCode::Char('g') => self.jump_to(0),
+            KeyCode::End | KeyCode::Char('G') => self.jump_to(self.total_rows),
+            KeyCode::Right | KeyCode::Char('l') => self.scroll_columns(1),
+            KeyCode::Left | KeyCode::Char('h') => self.scroll_columns(-1),
+            KeyCode::Char(':') => self.prompt = Some(Prompt::Row(String::new())),
+            KeyCode::Char('/') => self.prompt = Some(Prompt::Search(String::new())),
+            KeyCode::Char('n') => match &self.last_search {
+                Some(needle) => self.pending = Some(Pending::Search(needle.clone())),
+                None => self.message = Some("No previous search".to_string()),
+            },
+            KeyCode::Char('s') => self.toggle_panel(Panel::Schema),
+            KeyCode::Char('t') => {
+                self.toggle_panel(Panel::Stats);
+                if self.panel == Panel::Stats && self.stats.is_none() {
+                    self.pending = Some(Pending::Stats);
+                }
+            }
+            _ => {}
+        }
+        true
+    }
+
+    fn handle_prompt_key(&mut self, mut prompt: Prompt, key: KeyEvent) {
+        let text = match &mut prompt {
+            Prompt::Row(text) | Prompt::Search(text) => text,
+        };
+        match key.code {
+            KeyCode::Esc => return,
+            KeyCode::Enter => {
+                match prompt {
+                    Prompt::Row(text) => match text.trim().parse::<usize>() {
+                        Ok(row) if row > 0 => self.jump_to(row - 1),
+                        _ => self.message = Some(format!("Not a row number: {text}")),
+                    },
+                    Prompt::Search(text) if !text.is_empty() => {
+                        self.last_search = Some(text.clone());
+                        self.pending = Some(Pending::Search(text));
+                    }
+                    Prompt::Search(_) => {}
+                }
+                return;
+            }
+            KeyCode::Backspace => {
+                text.pop();
+            }
+            KeyCode::Char(c) => text.push(c),
+            _ => {}
+        }
+        self.prompt = Some(prompt);
+    }
+
+    fn toggle_panel(&mut self, panel: Panel) {
+        self.panel = if self.panel == panel {
+            Panel::None
+        } else {
+            panel
+        };
+    }
+
+    fn run_pending(&mut self, pending: Pending) {
+        let result = match pending {
+            Pending::Search(needle) => self.search(&needle).map(|found| match found {
+                Some(row) => {
+                    self.cursor = row;
+                    self.message = Some(format!("Found \"{needle}\" in row {}", row + 1));
+                }
+                None => self.message = Some(format!("Not found: {needle}")),
+            }),
+            Pending::Stats => self.compute_stats(),
+        };
+        if let Err(e) = result {
+            self.message = Some(format!("Error: {e}"));
+        }
+    }
+
+    /// Finds the next row after the selected one with a value containing `needle` (ignoring
+    /// case), continuing from the start of the file if needed.
+    fn search(&self, needle: &str) -> Result<Option<usize>> {
+        let needle = needle.to_lowercase();
+        let start = (self.cursor + 1).min(self.total_rows);
+        if let Some(row) = self.search_range(&needle, start, self.total_rows)? {
+            return Ok(Some(row));
+        }
+        self.search_range(&needle, 0, start)
+    }
+
+    fn search_range(&self, needle: &str, start: usize, end: usize) -> Result<Option<usize>> {
+        if start >= end {
+            return Ok(None);
+        }
+        let reader = get_reader_step(
+            self.file_type,
+            read_args(&self.path, Some(start), Some(end - start)),
+        )?
+        .get()?;
+        let mut row = start;
+        for batch in reader {
+            for values in format_rows(&batch?)? {
+                let found = values
+                    .iter()
+                    .flatten()
+                    .any(|value| value.to_lowercase().contains(needle));
+                if found {
+                    return Ok(Some(row));
+                }
+                row += 1;
+            }
+        }
+        Ok(None)
+    }
+
+    /// Scans the whole file for the non-null and null counts and the range of each column.
+    fn compute_stats(&mut self) -> Result<()> {
+        let columns = self.schema.fields().len();
+        let mut counts = vec![(0usize, 0usize); columns];
+        let mut ranges: Vec<Option<(ArrayRef, ArrayRef)>> = vec![None; columns];
+        let mut comparable = vec![true; columns];
+        let reader = get_reader_step(self.file_type, read_args(&self.path, None, None))?.get()?;
+        for batch in reader {
+            let batch = batch?;
+            for (i, array) in batch.columns().iter().enumerate() {
+                counts[i].0 += array.len() - array.null_count();
+                counts[i].1 += array.null_count();
+                if comparable[i] {
+                    match update_range(ranges[i].take(), array) {
+                        Ok(range) => ranges[i] = range,
+                        Err(_) => comparable[i] = false,
+                    }
+                }
+            }
+        }
+        let options = FormatOptions::default();
+        let format = |array: &ArrayRef| -> Result<String> {
+            Ok(ArrayFormatter::try_new(array.as_ref(), &options)?
+                .value(0)
+                .to_string())
+        };
+        let mut stats = Vec::with_capacity(columns);
+        for ((non_null, nulls), range) in counts.into_iter().zip(ranges) {
+            let (min, max) = match range {
+                Some((min, max)) => (Some(format(&min)?), Some(format(&max)?)),
+                None => (None, None),
+            };
+            stats.push(ColumnStats {
+                non_null,
+                nulls,
+                min,
+                max,
+            });
+        }
+        self.stats = Some(stats);
+        Ok(())
+    }
+
+    fn render(&self, frame: &mut Frame) {
+        let [main, status] =
+            Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+        let table_area = if self.panel == Panel::None {
+            main
+        } else {
+            let [table, panel] =
+                Layout::horizontal([Constraint::Percentage(65), Constraint::Percentage(35)])
+                    .areas(main);
+            self.render_panel(frame, panel);
+            table
+        };
+        self.render_table(frame, table_area);
+        self.render_status(frame, status);
+    }
+
+    fn render_table(&self, frame: &mut Frame, area: Rect) {
+        let end = (self.top + self.height).min(self.total_rows);
+        let visible: Vec<(usize, &[Option<String>])> = (self.top..end)
+            .filter_map(|i| self.row(i).map(|row| (i, row)))
+            .collect();
+        let number_width = self.total_rows.max(1).to_string().len();
+
+        // Show as many columns as fit, starting from the first scrolled-to column.
+        let fields = self.schema.fields();
+        let mut available = (area.width as usize).saturating_sub(2 + number_width);
+        let mut columns = Vec::new();
+        for (i, field) in fields.iter().enumerate().skip(self.column) {
+            let width = visible
+                .iter()
+                .filter_map(|(_, row)| row[i].as_ref())
+                .map(|value| value.chars().count())
+                .chain([field.name().chars().count(), 4])
+                .max()
+                .unwrap_or(0)
+                .min(MAX_COLUMN_WIDTH);
+            if !columns.is_empty() && width + 1 > available {
+                break;
+            }
+            available = available.saturating_sub(width + 1);
+            columns.push((i, width));
+        }
+
+        let header = Row::new(
+            std::iter::once(Cell::from("#")).chain(
+                columns
+                    .iter()
+                    .map(|&(i, width)| Cell::from(truncate(fields[i].name(), width))),
+            ),
+        )
+        .style(Style::new().add_modifier(Modifier::BOLD));
+        let null_style = Style::new().add_modifier(Modifier::DIM | Modifier::ITALIC);
+        let rows = visible.iter().map(|&(index, row)| {
+            let cells = columns.iter().map(|&(i, width)| match &row[i] {
+                Some(value) => Cell::from(truncate(value, width)),
+                None => Cell::from("null").style(null_style),
+            });
+            let style = if index == self.cursor {
+                Style::new().add_modifier(Modifier::REVERSED)
+            } else {
+                Style::new()
+            };
+            Row::new(
+                std::iter::once(Cell::from((index + 1).to_string()).style(null_style)).chain(cells),
+            )
+            .style(style)
+        });
+        let widths = std::iter::once(Constraint::Length(number_width as u16)).chain(
+            columns
+                .iter()
+                .map(|&(_, width)| Constraint::Length(width as u16)),
+        );
+        let title = match (columns.first(), columns.last()) {
+            (Some((first, _)), Some((last, _))) => format!(
+                " {}: rows {}-{} of {}, columns {}-{} of {} ",
+                self.path,
+                (self.top + 1).min(end),
+                end,
+                self.total_rows,
+                first + 1,
+                last + 1,
+                fields.len()
+            ),
+            _ => format!(" {}: {} rows, no columns ", self.path, self.total_rows),
+        };
+        let table = Table::new(rows, widths)
+            .header(header)
+            .block(Block::new().borders(Borders::ALL).title(title));
+        frame.render_widget(table, area);
+    }
+
+    fn render_panel(&self, frame: &mut Frame, area: Rect) {
+        let fields = self.schema.fields();
+        let bold = Style::new().add_modifier(Modifier::BOLD);
+        let (title, lines): (&str, Vec<Line>) = match self.panel {
+            Panel::Schema => (
+                " Schema ",
+                fields
+                    .iter()
+                    .enumerate()
+                    .skip(self.column)
+                    .map(|(i, field)| {
+                        Line::from(vec![
+                            Span::styled(format!("{}. {}", i + 1, field.name()), bold),
+                            Span::raw(format!(
+                                ": {}{}",
+                                field.data_type(),
+                                if field.is_nullable() { "" } else { " not null" }
+                            )),
+                        ])
+                    })
+                    .collect(),
+            ),
+            Panel::Stats => match &self.stats {
+                None => (" Statistics ", vec![Line::from("Computing statistics...")]),
+                Some(stats) => (
+                    " Statistics ",
+                    fields
+                        .iter()
+                        .zip(stats)
+                        .skip(self.column)
+                        .flat_map(|(field, stats)| {
+                            let mut lines = vec![
+                                Line::styled(field.name().to_string(), bold),
+                                Line::from(format!(
+                                    "  values: {}  nulls: {}",
+                                    stats.non_null, stats.nulls
+                                )),
+                            ];
+                            if let (Some(min), Some(max)) = (&stats.min, &stats.max) {
+                                lines.push(Line::from(format!("  min: {min}")));
+                                lines.push(Line::from(format!("  max: {max}")));
+                            }
+                            lines
+                        })
+                        .collect(),
+                ),
+            },
+            Panel::None => return,
+        };
+        let panel = Paragraph::new(lines).block(Block::new().borders(Borders::ALL).title(title));
+        frame.render_widget(panel, area);
+    }
+
+    fn render_status(&self, frame: &mut Frame, area: Rect) {
+        let line = match (&self.prompt, &self.pending, &self.message) {
+            (Some(Prompt::Row(text)), _, _) => Line::from(format!("Go to row: {text}")),
+            (Some(Prompt::Search(text)), _, _) => Line::from(format!("/{text}")),
+            (None, Some(Pending::Search(needle)), _) => {
+                Line::from(format!("Searching for \"{needle}\"..."))
+            }
+            (None, _, Some(message)) => Line::from(message.as_str()),
+            _ => Line::styled(HELP, Style::new().add_modifier(Modifier::DIM)),
+        };
+        frame.render_widget(Paragraph::new(line), area);
+    }
+}
+
+fn read_args(path: &str, offset: Option<usize>, limit: Option<usize>) -> ReadArgs {
+    ReadArgs {
+        path: path.to_string(),
+        limit,
+        offset,
+    }
+}
+
+/// Returns the number of rows in the file, from the file metadata (Parquet, ORC) or the block
+/// headers (Avro), without decoding any rows.
+fn count_rows(path: &str, file_type: FileType) -> Result<usize> {
+    let mut file = File::open(path).map_err(Error::IoError)?;
+    let rows = match file_type {
+        FileType::Parquet => {
+            let metadata = ParquetMetaDataReader::new()
+                .parse_and_finish(&file)
+                .map_err(Error::ParquetError)?;
+            metadata.file_metadata().num_rows().max(0) as usize
+        }
+        FileType::Orc => read_metadata(&mut file)
+            .map_err(Error::OrcError)?
+            .number_of_rows() as usize,
+        FileType::Avro => {
+            let mut reader = BufReader::new(file);
+            let header = read_avro_header(&mut reader)?;
+            let mut rows = 0;
+            for block in AvroBlockReader::new(reader, &header) {
+                rows += block?.record_count as usize;
+            }
+            rows
+        }
+        _ => bail!("Only Parquet, Avro, and ORC are supported for view"),
+    };
+    Ok(rows)
+}
+
+/// Formats every value of every row of the batch for display.
+fn format_rows(batch: &RecordBatch) -> Result<Vec<Vec<Option<String>>>> {
+    let options = FormatOptions::default();
+    let formatters = batch
+        .columns()
+        .iter()
+        .map(|array| ArrayFormatter::try_new(array.as_ref(), &options))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok((0..batch.num_rows())
+        .map(|row| {
+            batch
+                .columns()
+                .iter()
+                .zip(&formatters)
+                .map(|(array, formatter)| {
+                    (!array.is_null(row)).then(|| formatter.value(row).to_string())
+                })
+                .collect()
+        })
+        .collect())
+}
+
+/// Extends the (min, max) range of a column, each held as a one-element array, with the
+/// non-null values of `array`. Fails for types that cannot be ordered.
+fn update_range(
+    range: Option<(ArrayRef, ArrayRef)>,
+    array: &ArrayRef,
+) -> Result<Option<(ArrayRef, ArrayRef)>> {
+    let compare = make_comparator(array.as_ref(), array.as_ref(), SortOptions::default())?;
+    let mut indices = (0..array.len()).filter(|&i| array.is_valid(i));
+    let Some(first) = indices.next() else {
+        return Ok(range);
+    };
+    let (mut min, mut max) = (first, first);
+    for i in indices {
+        if compare(i, min).is_lt() {
+            min = i;
+        }
+        if compare(i, max).is_gt() {
+            max = i;
+        }
+    }
+    let (min, max) = (array.slice(min, 1), array.slice(max, 1));
+    let Some((old_min, old_max)) = range else {
+        return Ok(Some((min, max)));
+    };
+    let min =
+        if make_comparator(min.as_ref(), old_min.as_ref(), SortOptions::default())?(0, 0).is_lt() {
+            min
+        } else {
+            old_min
+        };
+    let max =
+        if make_comparator(max.as_ref(), old_max.as_ref(), SortOptions::default())?(0, 0).is_gt() {
+            max
+        } else {
+            old_max
+        };
+    Ok(Some((min, max)))
+}
+
+/// Shortens `value` to at most `width` characters, ending it with an ellipsis if cut, and
+/// replaces control characters (such as newlines) that would break the table layout.
+fn truncate(value: &str, width: usize) -> String {
+    let value: String = value
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect();
+    if value.chars().count() <= width {
+        return value;
+    }
+    let mut truncated: String = value.chars().take(width.saturating_sub(1)).collect();
+    truncated.push('…');
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn first_value(viewer: &Viewer, row: usize, column: &str) -> Option<String> {
+        let i = viewer.schema.index_of(column).unwrap();
+        viewer.row(row).and_then(|values| values[i].clone())
+    }
+
+    #[test]
+    fn test_view_pages_through_parquet() {
+        let mut viewer = Viewer::open("fixtures/userdata.parquet").unwrap();
+        assert_eq!(viewer.total_rows, 1000);
+        viewer.height = 10;
+        viewer.load_visible().unwrap();
+        assert_eq!(first_value(&viewer, 0, "id").as_deref(), Some("1"));
+
+        viewer.handle_key(key(KeyCode::PageDown));
+        viewer.handle_key(key(KeyCode::PageDown));
+        viewer.load_visible().unwrap();
+        assert_eq!(viewer.cursor, 20);
+        assert_eq!(first_value(&viewer, 20, "id").as_deref(), Some("21"));
+
+        viewer.handle_key(key(KeyCode::End));
+        viewer.load_visible().unwrap();
+        assert_eq!(viewer.cursor, 999);
+        assert_eq!(viewer.top, 990);
+        assert_eq!(first_value(&viewer, 999, "id").as_deref(), Some("1000"));
+    }
+
+    #[test]
+    fn test_view_jumps_to_row_in_avro() {
+        let mut viewer = Viewer::open("fixtures/userdata5.avro").unwrap();
+        assert_eq!(viewer.total_rows, 1000);
+        viewer.height = 10;
+        for code in [
+            KeyCode::Char(':'),
+            KeyCode::Char('5'),
+            KeyCode::Char('0'),
+            KeyCode::Char('0'),
+            KeyCode::Enter,
+        ] {
+            viewer.handle_key(key(code));
+        }
+        viewer.load_visible().unwrap();
+        assert_eq!(viewer.cursor, 499);
+        assert_eq!(first_value(&viewer, 499, "id").as_deref(), Some("500"));
+    }
+
+    #[test]
+    fn test_view_search() {
+        let mut viewer = Viewer::open("fixtures/userdata.parquet").unwrap();
+        assert_eq!(viewer.search("evelyn").unwrap(), Some(2));
+        viewer.cursor = 2;
+        let next = viewer.search("Evelyn").unwrap().unwrap();
+        assert!(next > 2);
+        // Searching wraps around to the start of the file.
+        viewer.cursor = 999;
+        assert_eq!(viewer.search("ajordan0@com.com").unwrap(), Some(0));
+        assert_eq!(viewer.search("no such value").unwrap(), None);
+    }
+
+    #[test]
+    fn test_view_stats() {
+        let mut viewer = Viewer::open("fixtures/userdata.parquet").unwrap();
+        viewer.compute_stats().unwrap();
+        let stats = viewer.stats.as_ref().unwrap();
+        let id = &stats[viewer.schema.index_of("id").unwrap()];
+        assert_eq!(id.non_null, 1000);
+        assert_eq!(id.nulls, 0);
+        assert_eq!(id.min.as_deref(), Some("1"));
+        assert_eq!(id.max.as_deref(), Some("1000"));
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(truncate("a longer value", 8), "a longe…");
+        assert_eq!(truncate("two\nlines", 20), "two lines");
+    }
+}
@@ -5,12 +5,22 @@ use clap::Subcommand;
 
 mod commands;
 
+use commands::cat;
 use commands::convert;
 use commands::count;
+use commands::diff;
+use commands::freq;
+use commands::grep;
 use commands::head;
+use commands::inspect;
 use commands::schema;
+use commands::schema_check;
+use commands::split;
 use commands::tail;
+use commands::validate;
+use commands::view;
 
+use crate::commands::cat::CatArgs;
 use crate::commands::convert::ConvertArgs;
 
 /// Top-level CLI structure that parses command-line arguments.
@@ -27,14 +37,32 @@ struct Cli {
 pub enum Command {
     /// convert between file formats
     Convert(ConvertArgs),
+    /// concatenate several files into one output file
+    Cat(CatArgs),
+    /// split a file into several files by rows, size, or key
+    Split(datu::cli::SplitArgs),
     /// return the number of rows in a file
     Count(datu::cli::CountArgs),
+    /// print the most common values or a histogram of columns
+    Freq(datu::cli::FreqArgs),
     /// print the first n lines of a file
     Head(datu::cli::HeadsOrTails),
     /// print the last n lines of a file
     Tail(datu::cli::HeadsOrTails),
     /// display the schema of a file
     Schema(datu::cli::SchemaArgs),
+    /// check that a schema can evolve into another without breaking readers
+    SchemaCheck(datu::cli::SchemaCheckArgs),
+    /// display the file-level metadata of a file
+    Inspect(datu::cli::InspectArgs),
+    /// fully decode a file and report any damage
+    Validate(datu::cli::ValidateArgs),
+    /// compare the schema and rows of two files
+    Diff(datu::cli::DiffArgs),
+    /// print the rows with a value matching a pattern
+    Grep(datu::cli::GrepArgs),
+    /// browse a file in a full-screen terminal viewer
+    View(datu::cli::ViewArgs),
     /// print the datu version
     Version,
 }
@@ -43,11 +71,44 @@ pub enum Command {
 fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
     match cli.command {
+        Command::Cat(args) => cat(args),
         Command::Convert(args) => convert(args),
+        Command::Split(args) => split(args),
         Command::Count(args) => count(args),
+        Command::Diff(args) => {
+            // Like diff(1), exit with status 1 when the files differ.
+            if !diff(args)? {
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+        Command::Freq(args) => freq(args),
+        Command::Grep(args) => {
+            // Like grep(1), exit with status 1 when no rows match.
+            if !grep(args)? {
+                std::process::exit(1);
+            }
+            Ok(())
+        }
         Command::Head(args) => head(args),
+        Command::Inspect(args) => inspect(args),
         Command::Schema(args) => schema(args),
+        Command::SchemaCheck(args) => {
+            // Exit with status 1 when there are breaking changes, for use in CI.
+            if !schema_check(args)? {
+                std::process::exit(1);
+            }
+            Ok(())
+        }
         Command::Tail(args) => tail(args),
+        Command::Validate(args) => {
+            // Exit with status 1 when the file is damaged.
+            if !validate(args)? {
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+        Command::View(args) => view(args),
         Command::Version => {
             println!("datu v{}", datu::VERSION);
             Ok(())
@@ -4,14 +4,23 @@ use std::str::FromStr;
 
 use clap::Args;
 
-/// Output format for schema, head, and tail commands (csv, json, json-pretty, yaml).
+/// Output format for schema, head, and tail commands (csv, json, json-pretty, jsonl, yaml,
+/// table, markdown, html).
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
 pub enum DisplayOutputFormat {
     #[default]
     Csv,
     Json,
     JsonPretty,
+    /// JSON Lines: one JSON object per line.
+    JsonLines,
     Yaml,
+    /// A bordered text table for reading in a terminal.
+    Table,
+    /// A Markdown table.
+    Markdown,
+    /// A standalone HTML page with a sortable table.
+    Html,
 }
 
 impl TryFrom<&str> for DisplayOutputFormat {
@@ -22,9 +31,13 @@ impl TryFrom<&str> for DisplayOutputFormat {
             "csv" => Ok(DisplayOutputFormat::Csv),
             "json" => Ok(DisplayOutputFormat::Json),
             "json-pretty" => Ok(DisplayOutputFormat::JsonPretty),
+            "jsonl" | "json-lines" | "ndjson" => Ok(DisplayOutputFormat::JsonLines),
             "yaml" => Ok(DisplayOutputFormat::Yaml),
+            "table" => Ok(DisplayOutputFormat::Table),
+            "markdown" | "md" => Ok(DisplayOutputFormat::Markdown),
+            "html" => Ok(DisplayOutputFormat::Html),
             _ => Err(format!(
-                "unknown output type '{s}', expected csv, json, json-pretty, or yaml"
+                "unknown output type '{s}', expected csv, json, json-pretty, jsonl, yaml, table, markdown, or html"
             )),
         }
     }
@@ -36,7 +49,11 @@ impl std::fmt::Display for DisplayOutputFormat {
             DisplayOutputFormat::Csv => write!(f, "csv"),
             DisplayOutputFormat::Json => write!(f, "json"),
             DisplayOutputFormat::JsonPretty => write!(f, "json-pretty"),
+            DisplayOutputFormat::JsonLines => write!(f, "jsonl"),
             DisplayOutputFormat::Yaml => write!(f, "yaml"),
+            DisplayOutputFormat::Table => write!(f, "table"),
+            DisplayOutputFormat::Markdown => write!(f, "markdown"),
+            DisplayOutputFormat::Html => write!(f, "html"),
         }
     }
 }
@@ -49,19 +66,286 @@ impl FromStr for DisplayOutputFormat {
     }
 }
 
+/// Output format for the inspect, validate, diff, and schema-check reports (text, json,
+/// json-pretty, yaml).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ReportOutputFormat {
+    /// A human-readable summary.
+    #[default]
+    Text,
+    Json,
+    JsonPretty,
+    Yaml,
+}
+
+impl ReportOutputFormat {
+    /// The format used to serialize the report, or `None` for the text summary.
+    pub fn serialized_format(self) -> Option<DisplayOutputFormat> {
+        match self {
+            ReportOutputFormat::Text => None,
+            ReportOutputFormat::Json => Some(DisplayOutputFormat::Json),
+            ReportOutputFormat::JsonPretty => Some(DisplayOutputFormat::JsonPretty),
+            ReportOutputFormat::Yaml => Some(DisplayOutputFormat::Yaml),
+        }
+    }
+}
+
+impl TryFrom<&str> for ReportOutputFormat {
+    type Error = String;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(ReportOutputFormat::Text),
+            "json" => Ok(ReportOutputFormat::Json),
+            "json-pretty" => Ok(ReportOutputFormat::JsonPretty),
+            "yaml" => Ok(ReportOutputFormat::Yaml),
+            _ => Err(format!(
+                "unknown output type '{s}', expected text, json, json-pretty, or yaml"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for ReportOutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReportOutputFormat::Text => write!(f, "text"),
+            ReportOutputFormat::Json => write!(f, "json"),
+            ReportOutputFormat::JsonPretty => write!(f, "json-pretty"),
+            ReportOutputFormat::Yaml => write!(f, "yaml"),
+        }
+    }
+}
+
+impl FromStr for ReportOutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
+    }
+}
+
+/// Schema compatibility mode for the `datu schema-check` command.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum CompatibilityMode {
+    /// The new schema can read data written with the old schema.
+    #[default]
+    Backward,
+    /// The old schema can read data written with the new schema.
+    Forward,
+    /// Both backward and forward.
+    Full,
+}
+
+impl TryFrom<&str> for CompatibilityMode {
+    type Error = String;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s.to_lowercase().as_str() {
+            "backward" => Ok(CompatibilityMode::Backward),
+            "forward" => Ok(CompatibilityMode::Forward),
+            "full" => Ok(CompatibilityMode::Full),
+            _ => Err(format!(
+                "unknown compatibility mode '{s}', expected backward, forward, or full"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for CompatibilityMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CompatibilityMode::Backward => write!(f, "backward"),
+            CompatibilityMode::Forward => write!(f, "forward"),
+            CompatibilityMode::Full => write!(f, "full"),
+        }
+    }
+}
+
+impl FromStr for CompatibilityMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
+    }
+}
+
+/// Output format for the `datu schema` command: a field listing in one of the display
+/// formats, or the schema in another schema language.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum SchemaOutputFormat {
+    #[default]
+    Csv,
+    Json,
+    JsonPretty,
+    /// JSON Lines: one JSON object per line.
+    JsonLines,
+    Yaml,
+    Table,
+    Markdown,
+    Html,
+    /// Avro schema JSON.
+    Avsc,
+    /// JSON Schema (draft 2020-12) of the rows as written to JSON.
+    JsonSchema,
+    /// A `CREATE TABLE` statement.
+    Sql,
+    /// A serialized Arrow IPC schema.
+    Arrow,
+    /// A proto3 message definition.
+    Proto,
+}
+
+impl SchemaOutputFormat {
+    /// Returns the display format for a field listing, or `None` for a schema language.
+    pub fn display_format(self) -> Option<DisplayOutputFormat> {
+        match self {
+            SchemaOutputFormat::Csv => Some(DisplayOutputFormat::Csv),
+            SchemaOutputFormat::Json => Some(DisplayOutputFormat::Json),
+            SchemaOutputFormat::JsonPretty => Some(DisplayOutputFormat::JsonPretty),
+            SchemaOutputFormat::JsonLines => Some(DisplayOutputFormat::JsonLines),
+            SchemaOutputFormat::Yaml => Some(DisplayOutputFormat::Yaml),
+            SchemaOutputFormat::Table => Some(DisplayOutputFormat::Table),
+            SchemaOutputFormat::Markdown => Some(DisplayOutputFormat::Markdown),
+            SchemaOutputFormat::Html => Some(DisplayOutputFormat::Html),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<&str> for SchemaOutputFormat {
+    type Error = String;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(SchemaOutputFormat::Csv),
+            "json" => Ok(SchemaOutputFormat::Json),
+            "json-pretty" => Ok(SchemaOutputFormat::JsonPretty),
+            "jsonl" | "json-lines" | "ndjson" => Ok(SchemaOutputFormat::JsonLines),
+            "yaml" => Ok(SchemaOutputFormat::Yaml),
+            "table" => Ok(SchemaOutputFormat::Table),
+            "markdown" | "md" => Ok(SchemaOutputFormat::Markdown),
+            "html" => Ok(SchemaOutputFormat::Html),
+            "avsc" => Ok(SchemaOutputFormat::Avsc),
+            "json-schema" => Ok(SchemaOutputFormat::JsonSchema),
+            "sql" => Ok(SchemaOutputFormat::Sql),
+            "arrow" => Ok(SchemaOutputFormat::Arrow),
+            "proto" => Ok(SchemaOutputFormat::Proto),
+            _ => Err(format!(
+                "unknown output type '{s}', expected csv, json, json-pretty, jsonl, yaml, table, markdown, html, avsc, json-schema, sql, arrow, or proto"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for SchemaOutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchemaOutputFormat::Csv => write!(f, "csv"),
+            SchemaOutputFormat::Json => write!(f, "json"),
+            SchemaOutputFormat::JsonPretty => write!(f, "json-pretty"),
+            SchemaOutputFormat::JsonLines => write!(f, "jsonl"),
+            SchemaOutputFormat::Yaml => write!(f, "yaml"),
+            SchemaOutputFormat::Table => write!(f, "table"),
+            SchemaOutputFormat::Markdown => write!(f, "markdown"),
+            SchemaOutputFormat::Html => write!(f, "html"),
+            SchemaOutputFormat::Avsc => write!(f, "avsc"),
+            SchemaOutputFormat::JsonSchema => write!(f, "json-schema"),
+            SchemaOutputFormat::Sql => write!(f, "sql"),
+            SchemaOutputFormat::Arrow => write!(f, "arrow"),
+            SchemaOutputFormat::Proto => write!(f, "proto"),
+        }
+    }
+}
+
+impl FromStr for SchemaOutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
+    }
+}
+
+/// SQL dialect for `datu schema --output sql`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum SqlDialect {
+    #[default]
+    Postgres,
+    BigQuery,
+    Snowflake,
+    DuckDb,
+}
+
+impl TryFrom<&str> for SqlDialect {
+    type Error = String;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s.to_lowercase().as_str() {
+            "postgres" | "postgresql" => Ok(SqlDialect::Postgres),
+            "bigquery" => Ok(SqlDialect::BigQuery),
+            "snowflake" => Ok(SqlDialect::Snowflake),
+            "duckdb" => Ok(SqlDialect::DuckDb),
+            _ => Err(format!(
+                "unknown SQL dialect '{s}', expected postgres, bigquery, snowflake, or duckdb"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for SqlDialect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SqlDialect::Postgres => write!(f, "postgres"),
+            SqlDialect::BigQuery => write!(f, "bigquery"),
+            SqlDialect::Snowflake => write!(f, "snowflake"),
+            SqlDialect::DuckDb => write!(f, "duckdb"),
+        }
+    }
+}
+
+impl FromStr for SqlDialect {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
+    }
+}
+
 /// Arguments for the `datu schema` command.
 #[derive(Args)]
 pub struct SchemaArgs {
-    /// Path to the Parquet or Avro file
+    /// Path to the Parquet, Avro, or ORC file
     pub file: String,
     #[arg(
         long,
         short,
-        default_value_t = DisplayOutputFormat::Csv,
-        value_parser = clap::value_parser!(DisplayOutputFormat),
-        help = "Output format: csv, json, json-pretty, or yaml"
+        default_value_t = SchemaOutputFormat::Csv,
+        value_parser = clap::value_parser!(SchemaOutputFormat),
+        help = "Output format: csv, json, json-pretty, jsonl, yaml, table, markdown, or html to list the fields; avsc, json-schema, sql, arrow, or proto for the schema in that language"
     )]
-    pub output: DisplayOutputFormat,
+    pub output: SchemaOutputFormat,
+    #[arg(
+        long,
+        default_value_t = SqlDialect::Postgres,
+        value_parser = clap::value_parser!(SqlDialect),
+        help = "SQL dialect for --output sql: postgres, bigquery, snowflake, or duckdb"
+    )]
+    pub dialect: SqlDialect,
+    #[arg(
+        long,
+        help = "Table, record, or message name for --output avsc, json-schema, sql, or proto. Default: the file name without its extension"
+    )]
+    pub name: Option<String>,
+    #[arg(
+        long,
+        help = "For table output: maximum width of a column; longer values are truncated with an ellipsis."
+    )]
+    pub max_col_width: Option<usize>,
+    #[arg(
+        long,
+        help = "Show nested fields as a tree (csv) or as nested objects (json, json-pretty, jsonl, yaml), with list elements, map keys and values, and repetition"
+    )]
+    pub tree: bool,
     #[arg(
         long,
         default_value_t = true,
@@ -75,25 +359,77 @@ pub struct SchemaArgs {
 pub struct CountArgs {
     /// Path to the Parquet, Avro, or ORC file
     pub file: String,
+    #[arg(
+        long,
+        help = "Columns to group by, e.g. --by status or --by country,status. Prints the number of rows for each distinct combination of values, largest first."
+    )]
+    pub by: Option<Vec<String>>,
+    #[arg(
+        long,
+        requires = "by",
+        help = "With --by: print only the N largest groups."
+    )]
+    pub top: Option<usize>,
+    #[arg(
+        long,
+        short,
+        default_value_t = DisplayOutputFormat::Csv,
+        value_parser = clap::value_parser!(DisplayOutputFormat),
+        help = "Output format: csv, json, json-pretty, jsonl, yaml, table, markdown, or html"
+    )]
+    pub output: DisplayOutputFormat,
+    #[arg(
+        long,
+        default_value_t = true,
+        action = clap::ArgAction::Set,
+        help = "For JSON/YAML: omit keys with null/missing values. Default: true. Use --sparse=false to include default values."
+    )]
+    pub sparse: bool,
+    #[arg(
+        long,
+        help = "For table output: maximum width of a column; longer values are truncated with an ellipsis."
+    )]
+    pub max_col_width: Option<usize>,
+    #[command(flatten)]
+    pub csv: CsvOptions,
 }
 
-/// Arguments for the `datu head` and `datu tail` commands.
+/// Arguments for the `datu freq` command.
 #[derive(Args)]
-pub struct HeadsOrTails {
-    pub input: String,
+pub struct FreqArgs {
+    /// Path to the Parquet, Avro, or ORC file
+    pub file: String,
+    #[arg(
+        long,
+        required = true,
+        help = "Columns to describe, e.g. --columns status or --columns status,created_at"
+    )]
+    pub columns: Vec<String>,
     #[arg(
-        short = 'n',
         long,
         default_value_t = 10,
-        help = "Number of lines to print."
+        help = "For categorical columns: number of most common values to print; the rest are summed up as (other)."
     )]
-    pub number: usize,
+    pub top: usize,
+    #[arg(
+        long,
+        default_value_t = 10,
+        help = "For numeric and temporal columns: number of histogram buckets."
+    )]
+    pub bins: usize,
+    #[arg(
+        long,
+        help = "Count the distinct values of numeric and temporal columns instead of binning them."
+    )]
+    pub categorical: bool,
+    #[arg(long, help = "Add a bar column drawing each count as a bar.")]
+    pub bars: bool,
     #[arg(
         long,
         short,
         default_value_t = DisplayOutputFormat::Csv,
         value_parser = clap::value_parser!(DisplayOutputFormat),
-        help = "Output format: csv, json, json-pretty, or yaml"
+        help = "Output format: csv, json, json-pretty, jsonl, yaml, table, markdown, or html"
     )]
     pub output: DisplayOutputFormat,
     #[arg(
@@ -105,7 +441,770 @@ pub struct HeadsOrTails {
     pub sparse: bool,
     #[arg(
         long,
-        help = "Columns to select. If not specified, all columns will be printed."
+        help = "For table output: maximum width of a column; longer values are truncated with an ellipsis."
+    )]
+    pub max_col_width: Option<usize>,
+    #[command(flatten)]
+    pub csv: CsvOptions,
+}
+
+/// Arguments for the `datu inspect` command.
+#[derive(Args)]
+pub struct InspectArgs {
+    /// Path to the Avro or ORC file
+    pub file: String,
+    #[arg(
+        long,
+        short,
+        default_value_t = ReportOutputFormat::Text,
+        value_parser = clap::value_parser!(ReportOutputFormat),
+        help = "Output format: text (a human-readable summary), json, json-pretty, or yaml"
+    )]
+    pub output: ReportOutputFormat,
+}
+
+/// Arguments for the `datu validate` command.
+#[derive(Args)]
+pub struct ValidateArgs {
+    /// Path to the Parquet, Avro, or ORC file
+    pub file: String,
+    #[arg(
+        long,
+        short,
+        default_value_t = ReportOutputFormat::Text,
+        value_parser = clap::value_parser!(ReportOutputFormat),
+        help = "Output format: text (a human-readable report), json, json-pretty, or yaml"
+    )]
+    pub output: ReportOutputFormat,
+}
+
+/// Arguments for the `datu diff` command.
+#[derive(Args)]
+pub struct DiffArgs {
+    /// Path to the left (old) Parquet, Avro, or ORC file
+    pub left: String,
+    /// Path to the right (new) Parquet, Avro, or ORC file
+    pub right: String,
+    #[arg(
+        long,
+        help = "Key columns used to match rows between the files. If not specified, rows are compared by position."
+    )]
+    pub key: Option<Vec<String>>,
+    #[arg(
+        long,
+        short,
+        default_value_t = ReportOutputFormat::Text,
+        value_parser = clap::value_parser!(ReportOutputFormat),
+        help = "Output format: text (a summary), or json, json-pretty, or yaml (a listing of every difference)"
+    )]
+    pub output: ReportOutputFormat,
+}
+
+/// Arguments for the `datu schema-check` command.
+#[derive(Args)]
+pub struct SchemaCheckArgs {
+    /// Path to the old data file (Parquet, Avro, or ORC) or Avro schema file (.avsc or .json)
+    pub old: String,
+    /// Path to the new data file (Parquet, Avro, or ORC) or Avro schema file (.avsc or .json)
+    pub new: String,
+    #[arg(
+        long,
+        default_value_t = CompatibilityMode::Backward,
+        value_parser = clap::value_parser!(CompatibilityMode),
+        help = "Compatibility mode: backward (new schema reads old data), forward (old schema reads new data), or full (both)"
+    )]
+    pub mode: CompatibilityMode,
+    #[arg(
+        long,
+        short,
+        default_value_t = ReportOutputFormat::Text,
+        value_parser = clap::value_parser!(ReportOutputFormat),
+        help = "Output format: text (a list of breaking changes), json, json-pretty, or yaml"
+    )]
+    pub output: ReportOutputFormat,
+}
+
+/// Arguments for the `datu grep` command.
+#[derive(Args)]
+pub struct GrepArgs {
+    /// Regular expression to search for (a literal string with --fixed-strings)
+    pub pattern: String,
+    /// Parquet, Avro, or ORC files to search
+    #[arg(required = true)]
+    pub files: Vec<String>,
+    #[arg(
+        long,
+        help = "Columns to search. If not specified, all columns are searched."
+    )]
+    pub columns: Option<Vec<String>>,
+    #[arg(
+        short = 'F',
+        long,
+        help = "Treat the pattern as a literal string rather than a regular expression."
+    )]
+    pub fixed_strings: bool,
+    #[arg(short, long, help = "Ignore case when matching.")]
+    pub ignore_case: bool,
+    #[arg(short = 'v', long, help = "Print the rows that do not match.")]
+    pub invert: bool,
+    #[arg(
+        short,
+        long,
+        help = "Print the number of matching rows in each file instead of the rows."
+    )]
+    pub count: bool,
+    #[arg(
+        short = 'n',
+        long,
+        help = "Add a _row column with the one-based row number of each match."
+    )]
+    pub row_numbers: bool,
+    #[arg(
+        long,
+        help = "Columns to print. If not specified, all columns will be printed."
     )]
     pub select: Option<Vec<String>>,
+    #[arg(
+        long,
+        short,
+        default_value_t = DisplayOutputFormat::Csv,
+        value_parser = clap::value_parser!(DisplayOutputFormat),
+        help = "Output format: csv, json, json-pretty, jsonl, yaml, table, markdown, or html"
+    )]
+    pub output: DisplayOutputFormat,
+    #[arg(
+        long,
+        default_value_t = true,
+        action = clap::ArgAction::Set,
+        help = "For JSON/YAML: omit keys with null/missing values. Default: true. Use --sparse=false to include default values."
+    )]
+    pub sparse: bool,
+    #[command(flatten)]
+    pub csv: CsvOptions,
+}
+
+/// Arguments for the `datu view` command.
+#[derive(Args)]
+pub struct ViewArgs {
+    /// Path to the Parquet, Avro, or ORC file
+    pub file: String,
+}
+
+/// Arguments for the `datu head` and `datu tail` commands.
+#[derive(Args)]
+pub struct HeadsOrTails {
+    pub input: String,
+    #[arg(
+        short = 'n',
+        long,
+        default_value_t = 10,
+        help = "Number of lines to print."
+    )]
+    pub number: usize,
+    #[arg(
+        long,
+        short,
+        default_value_t = DisplayOutputFormat::Csv,
+        value_parser = clap::value_parser!(DisplayOutputFormat),
+        help = "Output format: csv, json, json-pretty, jsonl, yaml, table, markdown, or html"
+    )]
+    pub output: DisplayOutputFormat,
+    #[arg(
+        long,
+        default_value_t = true,
+        action = clap::ArgAction::Set,
+        help = "For JSON/YAML: omit keys with null/missing values. Default: true. Use --sparse=false to include default values."
+    )]
+    pub sparse: bool,
+    #[arg(
+        long,
+        help = "Columns to select. If not specified, all columns will be printed."
+    )]
+    pub select: Option<Vec<String>>,
+    #[arg(
+        long,
+        help = "For table output: maximum width of a column; longer values are truncated with an ellipsis."
+    )]
+    pub max_col_width: Option<usize>,
+    #[command(flatten)]
+    pub csv: CsvOptions,
+}
+
+/// Arguments for the `datu split` command.
+#[derive(Args)]
+#[command(group(clap::ArgGroup::new("split_by").required(true).args(["rows_per_file", "max_bytes", "by"])))]
+pub struct SplitArgs {
+    /// Path to the Parquet, Avro, or ORC file
+    pub input: String,
+    /// Output file name template, e.g. out/part-{n:05}.parquet. {n} is the part number and {key} the --by value; the extension sets the output format.
+    pub output: String,
+    #[arg(long, help = "Maximum number of rows in each output file.")]
+    pub rows_per_file: Option<usize>,
+    #[arg(
+        long,
+        value_parser = crate::utils::parse_byte_size,
+        help = "Maximum size of each output file, e.g. 256MB or 1GiB. A file is rewritten with fewer rows if it comes out larger."
+    )]
+    pub max_bytes: Option<usize>,
+    #[arg(long, help = "Column to split by; writes one file per distinct value.")]
+    pub by: Option<String>,
+    #[arg(
+        long,
+        default_value_t = 64,
+        help = "With --by, the most output files kept open at once. When another is needed, the least recently written is closed; rows for its value that come later go to a new file, numbered by {n}."
+    )]
+    pub max_open_files: usize,
+    #[arg(
+        long,
+        help = "Columns to select. If not specified, all columns will be selected."
+    )]
+    pub select: Option<Vec<String>>,
+    #[arg(
+        long,
+        default_value_t = true,
+        action = clap::ArgAction::Set,
+        help = "For JSON/YAML: omit keys with null/missing values. Default: true. Use --sparse=false to include default values (e.g. empty string)."
+    )]
+    pub sparse: bool,
+    #[arg(
+        long,
+        help = "When writing JSON, format output with indentation and newlines. Ignored for other output formats."
+    )]
+    pub json_pretty: bool,
+    #[command(flatten)]
+    pub overwrite: OverwriteOptions,
+    #[command(flatten)]
+    pub parquet: ParquetWriteOptions,
+    #[command(flatten)]
+    pub avro: AvroOptions,
+    #[command(flatten)]
+    pub orc: OrcWriteOptions,
+    #[command(flatten)]
+    pub csv: CsvOptions,
+    #[command(flatten)]
+    pub xlsx: XlsxOptions,
+}
+
+/// Compression codec for Parquet output.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ParquetCompression {
+    #[default]
+    Uncompressed,
+    Snappy,
+    Gzip,
+    Lz4,
+    Zstd,
+    Brotli,
+}
+
+impl TryFrom<&str> for ParquetCompression {
+    type Error = String;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s.to_lowercase().as_str() {
+            "uncompressed" | "none" => Ok(ParquetCompression::Uncompressed),
+            "snappy" => Ok(ParquetCompression::Snappy),
+            "gzip" => Ok(ParquetCompression::Gzip),
+            "lz4" => Ok(ParquetCompression::Lz4),
+            "zstd" => Ok(ParquetCompression::Zstd),
+            "brotli" => Ok(ParquetCompression::Brotli),
+            _ => Err(format!(
+                "unknown compression '{s}', expected uncompressed, snappy, gzip, lz4, zstd, or brotli"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for ParquetCompression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParquetCompression::Uncompressed => write!(f, "uncompressed"),
+            ParquetCompression::Snappy => write!(f, "snappy"),
+            ParquetCompression::Gzip => write!(f, "gzip"),
+            ParquetCompression::Lz4 => write!(f, "lz4"),
+            ParquetCompression::Zstd => write!(f, "zstd"),
+            ParquetCompression::Brotli => write!(f, "brotli"),
+        }
+    }
+}
+
+impl FromStr for ParquetCompression {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
+    }
+}
+
+/// Parquet format version to write: v2 enables the newer data page and encodings.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ParquetWriterVersion {
+    #[default]
+    V1,
+    V2,
+}
+
+impl TryFrom<&str> for ParquetWriterVersion {
+    type Error = String;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s.to_lowercase().as_str() {
+            "v1" | "1" | "1.0" => Ok(ParquetWriterVersion::V1),
+            "v2" | "2" | "2.0" => Ok(ParquetWriterVersion::V2),
+            _ => Err(format!("unknown writer version '{s}', expected v1 or v2")),
+        }
+    }
+}
+
+impl std::fmt::Display for ParquetWriterVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParquetWriterVersion::V1 => write!(f, "v1"),
+            ParquetWriterVersion::V2 => write!(f, "v2"),
+        }
+    }
+}
+
+impl FromStr for ParquetWriterVersion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
+    }
+}
+
+/// Level of the column statistics written to Parquet output.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ParquetStatistics {
+    /// No statistics.
+    None,
+    /// Statistics for each column chunk.
+    Chunk,
+    /// Statistics for each column chunk and each data page.
+    #[default]
+    Page,
+}
+
+impl TryFrom<&str> for ParquetStatistics {
+    type Error = String;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s.to_lowercase().as_str() {
+            "none" => Ok(ParquetStatistics::None),
+            "chunk" => Ok(ParquetStatistics::Chunk),
+            "page" => Ok(ParquetStatistics::Page),
+            _ => Err(format!(
+                "unknown statistics level '{s}', expected none, chunk, or page"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for ParquetStatistics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParquetStatistics::None => write!(f, "none"),
+            ParquetStatistics::Chunk => write!(f, "chunk"),
+            ParquetStatistics::Page => write!(f, "page"),
+        }
+    }
+}
+
+impl FromStr for ParquetStatistics {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
+    }
+}
+
+/// Options for writing Parquet files, shared by the commands that write files.
+///
+/// Columns are named by their path in the Parquet schema, with nested fields joined by dots
+/// (e.g. `address.city`), as listed by `datu schema`.
+#[derive(Args, Clone, Debug, Default)]
+#[command(next_help_heading = "Parquet output")]
+pub struct ParquetWriteOptions {
+    #[arg(
+        long,
+        default_value_t = ParquetCompression::Uncompressed,
+        value_parser = clap::value_parser!(ParquetCompression),
+        help = "Compression codec: uncompressed, snappy, gzip, lz4, zstd, or brotli"
+    )]
+    pub compression: ParquetCompression,
+    #[arg(
+        long,
+        help = "Compression level for gzip (0-10), zstd (1-22), or brotli (0-11). Default: the codec's default."
+    )]
+    pub compression_level: Option<u32>,
+    #[arg(long, help = "Maximum number of rows in each row group.")]
+    pub max_row_group_size: Option<usize>,
+    #[arg(
+        long,
+        value_parser = crate::utils::parse_byte_size,
+        help = "Target size of each data page, e.g. 1MB."
+    )]
+    pub data_page_size: Option<usize>,
+    #[arg(
+        long,
+        default_value_t = ParquetWriterVersion::V1,
+        value_parser = clap::value_parser!(ParquetWriterVersion),
+        help = "Parquet format version: v1 or v2"
+    )]
+    pub writer_version: ParquetWriterVersion,
+    #[arg(
+        long,
+        help = "Disable dictionary encoding for all columns, except those given with --dictionary-columns."
+    )]
+    pub no_dictionary: bool,
+    #[arg(
+        long,
+        help = "Columns to dictionary encode, overriding --no-dictionary."
+    )]
+    pub dictionary_columns: Option<Vec<String>>,
+    #[arg(long, help = "Columns not to dictionary encode.")]
+    pub no_dictionary_columns: Option<Vec<String>>,
+    #[arg(
+        long,
+        default_value_t = ParquetStatistics::Page,
+        value_parser = clap::value_parser!(ParquetStatistics),
+        help = "Column statistics to write: none, chunk, or page"
+    )]
+    pub statistics: ParquetStatistics,
+    #[arg(long, help = "Columns to write bloom filters for.")]
+    pub bloom_filter_columns: Option<Vec<String>>,
+    #[arg(
+        long,
+        requires = "bloom_filter_columns",
+        help = "False positive probability of the bloom filters, between 0 and 1. Default: 0.05."
+    )]
+    pub bloom_filter_fpp: Option<f64>,
+    #[arg(
+        long,
+        requires = "bloom_filter_columns",
+        help = "Expected number of distinct values in each bloom filter. Default: 1000000."
+    )]
+    pub bloom_filter_ndv: Option<u64>,
+    #[arg(
+        long,
+        help = "Columns the rows are sorted by, recorded in the file metadata, e.g. date,id:desc. Add :nulls_last when nulls sort last. This does not sort the rows."
+    )]
+    pub sorting_columns: Option<Vec<String>>,
+}
+
+/// Block compression codec for Avro output.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum AvroCodec {
+    #[default]
+    Null,
+    Deflate,
+    Snappy,
+    Zstd,
+    Bzip2,
+    Xz,
+}
+
+impl TryFrom<&str> for AvroCodec {
+    type Error = String;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s.to_lowercase().as_str() {
+            "null" | "none" => Ok(AvroCodec::Null),
+            "deflate" => Ok(AvroCodec::Deflate),
+            "snappy" => Ok(AvroCodec::Snappy),
+            "zstd" | "zstandard" => Ok(AvroCodec::Zstd),
+            "bzip2" => Ok(AvroCodec::Bzip2),
+            "xz" => Ok(AvroCodec::Xz),
+            _ => Err(format!(
+                "unknown Avro codec '{s}', expected null, deflate, snappy, zstd, bzip2, or xz"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for AvroCodec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AvroCodec::Null => write!(f, "null"),
+            AvroCodec::Deflate => write!(f, "deflate"),
+            AvroCodec::Snappy => write!(f, "snappy"),
+            AvroCodec::Zstd => write!(f, "zstd"),
+            AvroCodec::Bzip2 => write!(f, "bzip2"),
+            AvroCodec::Xz => write!(f, "xz"),
+        }
+    }
+}
+
+impl FromStr for AvroCodec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
+    }
+}
+
+/// Options for reading and writing Avro files, shared by the commands that write files.
+#[derive(Args, Clone, Debug, Default)]
+#[command(next_help_heading = "Avro options")]
+pub struct AvroOptions {
+    #[arg(
+        long,
+        default_value_t = AvroCodec::Null,
+        value_parser = clap::value_parser!(AvroCodec),
+        help = "Block compression codec for Avro output: null, deflate, snappy, zstd, bzip2, or xz"
+    )]
+    pub avro_codec: AvroCodec,
+    #[arg(
+        long,
+        value_name = "SCHEMA.avsc",
+        help = "Avro schema file. Avro output is written with this schema, including its record name, namespace, docs, and logical types; columns are matched by name and cast to its types. Avro input is read with it as the reader schema, resolving older files against it."
+    )]
+    pub avro_schema: Option<String>,
+}
+
+/// Compression codec for ORC output.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum OrcCompression {
+    #[default]
+    None,
+    Zlib,
+    Snappy,
+    Zstd,
+    Lz4,
+}
+
+impl TryFrom<&str> for OrcCompression {
+    type Error = String;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s.to_lowercase().as_str() {
+            "none" | "uncompressed" => Ok(OrcCompression::None),
+            "zlib" => Ok(OrcCompression::Zlib),
+            "snappy" => Ok(OrcCompression::Snappy),
+            "zstd" => Ok(OrcCompression::Zstd),
+            "lz4" => Ok(OrcCompression::Lz4),
+            _ => Err(format!(
+                "unknown ORC compression '{s}', expected none, zlib, snappy, zstd, or lz4"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for OrcCompression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OrcCompression::None => write!(f, "none"),
+            OrcCompression::Zlib => write!(f, "zlib"),
+            OrcCompression::Snappy => write!(f, "snappy"),
+            OrcCompression::Zstd => write!(f, "zstd"),
+            OrcCompression::Lz4 => write!(f, "lz4"),
+        }
+    }
+}
+
+impl FromStr for OrcCompression {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
+    }
+}
+
+/// Options for writing ORC files, shared by the commands that write files.
+#[derive(Args, Clone, Debug, Default)]
+#[command(next_help_heading = "ORC output")]
+pub struct OrcWriteOptions {
+    #[arg(
+        long,
+        default_value_t = OrcCompression::None,
+        value_parser = clap::value_parser!(OrcCompression),
+        help = "Compression codec for ORC output: none, zlib, snappy, zstd, or lz4"
+    )]
+    pub orc_compression: OrcCompression,
+    #[arg(
+        long,
+        value_parser = crate::utils::parse_byte_size,
+        help = "Size of the chunks ORC streams are compressed in, e.g. 256KB, up to 8MiB - 1. Default: 256KiB."
+    )]
+    pub orc_compression_block_size: Option<usize>,
+    #[arg(
+        long,
+        value_parser = crate::utils::parse_byte_size,
+        help = "Approximate size of each ORC stripe, e.g. 64MB, measured as the in-memory size of its encoded rows before compression. Default: 64MiB."
+    )]
+    pub orc_stripe_size: Option<usize>,
+    #[arg(
+        long,
+        help = "Number of rows encoded at a time when writing ORC. Default: 1024."
+    )]
+    pub orc_batch_size: Option<usize>,
+}
+
+/// When to quote fields in CSV output.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum CsvQuoteStyle {
+    /// Only fields that contain the delimiter, a quote, or a line break.
+    #[default]
+    Necessary,
+    /// Every field, including the header.
+    Always,
+    /// Every field that is not a number.
+    NonNumeric,
+    /// No field; the output may not be parseable.
+    Never,
+}
+
+impl TryFrom<&str> for CsvQuoteStyle {
+    type Error = String;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s.to_lowercase().as_str() {
+            "necessary" => Ok(CsvQuoteStyle::Necessary),
+            "always" => Ok(CsvQuoteStyle::Always),
+            "non-numeric" | "nonnumeric" => Ok(CsvQuoteStyle::NonNumeric),
+            "never" => Ok(CsvQuoteStyle::Never),
+            _ => Err(format!(
+                "unknown quote style '{s}', expected necessary, always, non-numeric, or never"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for CsvQuoteStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CsvQuoteStyle::Necessary => write!(f, "necessary"),
+            CsvQuoteStyle::Always => write!(f, "always"),
+            CsvQuoteStyle::NonNumeric => write!(f, "non-numeric"),
+            CsvQuoteStyle::Never => write!(f, "never"),
+        }
+    }
+}
+
+impl FromStr for CsvQuoteStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
+    }
+}
+
+/// Line terminator for CSV output.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum LineTerminator {
+    #[default]
+    Lf,
+    Crlf,
+}
+
+impl TryFrom<&str> for LineTerminator {
+    type Error = String;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s.to_lowercase().as_str() {
+            "lf" | "unix" => Ok(LineTerminator::Lf),
+            "crlf" | "windows" => Ok(LineTerminator::Crlf),
+            _ => Err(format!(
+                "unknown line terminator '{s}', expected lf or crlf"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for LineTerminator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LineTerminator::Lf => write!(f, "lf"),
+            LineTerminator::Crlf => write!(f, "crlf"),
+        }
+    }
+}
+
+impl FromStr for LineTerminator {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
+    }
+}
+
+/// Options for writing CSV and TSV, shared by the commands that write files and by the
+/// `csv` display format.
+///
+/// Date and time formats are chrono `strftime` strings, e.g. `%d/%m/%Y`.
+#[derive(Args, Clone, Debug, Default)]
+#[command(next_help_heading = "CSV output")]
+pub struct CsvOptions {
+    #[arg(
+        long,
+        value_parser = crate::utils::parse_delimiter,
+        help = "Field delimiter: a single character such as '|' or ';', or tab. Default: comma, or tab for .tsv files."
+    )]
+    pub delimiter: Option<u8>,
+    #[arg(
+        long,
+        default_value_t = CsvQuoteStyle::Necessary,
+        value_parser = clap::value_parser!(CsvQuoteStyle),
+        help = "When to quote fields: necessary, always, non-numeric, or never"
+    )]
+    pub quote_style: CsvQuoteStyle,
+    #[arg(long, help = "Don't write the header row.")]
+    pub no_header: bool,
+    #[arg(long, help = "Text written for null values. Default: an empty field.")]
+    pub null_value: Option<String>,
+    #[arg(
+        long,
+        default_value_t = LineTerminator::Lf,
+        value_parser = clap::value_parser!(LineTerminator),
+        help = "Line terminator: lf or crlf"
+    )]
+    pub line_terminator: LineTerminator,
+    #[arg(
+        long,
+        help = "Start the output with a UTF-8 byte order mark, so that Excel detects the encoding."
+    )]
+    pub bom: bool,
+    #[arg(
+        long,
+        help = "Format of date values, e.g. %d/%m/%Y. Default: %Y-%m-%d."
+    )]
+    pub date_format: Option<String>,
+    #[arg(
+        long,
+        help = "Format of time values, e.g. %H:%M. Default: %H:%M:%S%.f."
+    )]
+    pub time_format: Option<String>,
+    #[arg(
+        long,
+        help = "Format of timestamp values, e.g. '%Y-%m-%d %H:%M:%S'. Default: RFC 3339."
+    )]
+    pub timestamp_format: Option<String>,
+}
+
+/// Options for writing Excel workbooks.
+#[derive(Args, Clone, Debug, Default)]
+#[command(next_help_heading = "XLSX output")]
+pub struct XlsxOptions {
+    #[arg(
+        long = "sheet-name",
+        value_name = "NAME",
+        help = "Name of the worksheet. Default: Sheet1. With cat --sheet-per-input, give it once per input, in order; inputs without one are named after their file."
+    )]
+    pub sheet_names: Vec<String>,
+}
+
+/// Options for when an output file already exists.
+#[derive(Args, Clone, Debug, Default)]
+pub struct OverwriteOptions {
+    #[arg(
+        long,
+        conflicts_with = "force",
+        help = "Fail if an output file already exists, rather than replacing it."
+    )]
+    pub no_clobber: bool,
+    #[arg(
+        long,
+        help = "Replace an output file even if it is also an input file. Without this, writing over an input is an error."
+    )]
+    pub force: bool,
 }
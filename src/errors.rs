@@ -11,6 +11,10 @@ pub enum Error {
     UnknownFileType(String),
     #[error("Pipeline planning error: {0}")]
     PipelinePlanningError(String),
+    #[error("Invalid Avro container file: {0}")]
+    AvroContainerError(String),
+    #[error("Invalid Avro schema: {0}")]
+    AvroSchemaError(String),
     #[error(transparent)]
     IoError(#[from] std::io::Error),
     #[error(transparent)]
@@ -6,6 +6,7 @@ pub const VERSION: &str = clap::crate_version!();
 pub mod cli;
 pub mod errors;
 pub mod pipeline;
+pub mod schema;
 pub mod utils;
 
 pub use errors::Error;
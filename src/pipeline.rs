@@ -1,17 +1,27 @@
 //! The `pipeline` module is the core of the datu crate.
 
 pub mod avro;
+pub mod concat;
 pub mod csv;
 pub mod display;
+pub mod frequency;
+pub mod grep;
+pub mod group_count;
+pub mod html;
 pub mod json;
+pub mod markdown;
 pub mod orc;
+pub mod output;
 pub mod parquet;
 pub mod record_batch_filter;
+pub mod split;
 pub mod xlsx;
 pub mod yaml;
 
 use arrow::array::RecordBatchReader;
 
+use crate::Error;
+use crate::FileType;
 use crate::Result;
 
 /// Arguments for reading a file (Avro, Parquet, ORC).
@@ -21,9 +31,11 @@ pub struct ReadArgs {
     pub offset: Option<usize>,
 }
 
-/// Arguments for writing a file (CSV, Avro, Parquet, ORC, XLSX).
+/// Arguments for writing a file (CSV, Avro, Parquet, ORC, XLSX, Markdown, HTML).
 pub struct WriteArgs {
     pub path: String,
+    /// When true, fail rather than replace an existing file.
+    pub no_clobber: bool,
 }
 
 /// Arguments for writing a JSON file.
@@ -33,6 +45,8 @@ pub struct WriteJsonArgs {
     pub sparse: bool,
     /// When true, format output with indentation and newlines.
     pub pretty: bool,
+    /// When true, fail rather than replace an existing file.
+    pub no_clobber: bool,
 }
 
 /// Arguments for writing a YAML file.
@@ -40,6 +54,29 @@ pub struct WriteYamlArgs {
     pub path: String,
     /// When true, omit keys with null/missing values. When false, output default values.
     pub sparse: bool,
+    /// When true, fail rather than replace an existing file.
+    pub no_clobber: bool,
+}
+
+/// Builds a record batch reader source for the given input file type (Parquet, Avro, or ORC).
+pub fn get_reader_step(
+    input_file_type: FileType,
+    args: ReadArgs,
+) -> Result<RecordBatchReaderSource> {
+    let reader: RecordBatchReaderSource = match input_file_type {
+        FileType::Parquet => Box::new(parquet::ReadParquetStep { args }),
+        FileType::Avro => Box::new(avro::ReadAvroStep {
+            args,
+            reader_schema: None,
+        }),
+        FileType::Orc => Box::new(orc::ReadOrcStep { args }),
+        _ => {
+            return Err(Error::PipelinePlanningError(
+                "Only Parquet, Avro, and ORC are supported as input file types".to_string(),
+            ));
+        }
+    };
+    Ok(reader)
 }
 
 /// A `Step` defines a step in the pipeline that can be executed
@@ -99,6 +136,40 @@ impl<Inner: RecordBatchReader + 'static> RecordBatchReader for LimitingRecordBat
     }
 }
 
+/// A RecordBatchReader that skips the first `offset` rows.
+pub struct OffsetRecordBatchReader<Inner: RecordBatchReader + 'static> {
+    inner: Inner,
+    offset: usize,
+    records_skipped: usize,
+}
+
+impl<Inner: RecordBatchReader + 'static> Iterator for OffsetRecordBatchReader<Inner> {
+    type Item = arrow::error::Result<arrow::record_batch::RecordBatch>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.inner.next() {
+                Some(Ok(batch)) if self.records_skipped < self.offset => {
+                    let remaining = self.offset - self.records_skipped;
+                    if batch.num_rows() <= remaining {
+                        self.records_skipped += batch.num_rows();
+                        continue;
+                    }
+                    self.records_skipped = self.offset;
+                    return Some(Ok(batch.slice(remaining, batch.num_rows() - remaining)));
+                }
+                res => return res,
+            }
+        }
+    }
+}
+
+impl<Inner: RecordBatchReader + 'static> RecordBatchReader for OffsetRecordBatchReader<Inner> {
+    fn schema(&self) -> std::sync::Arc<arrow::datatypes::Schema> {
+        self.inner.schema()
+    }
+}
+
 /// A RecordBatchReader that yields batches from a Vec.
 pub struct VecRecordBatchReader {
     batches: Vec<arrow::record_batch::RecordBatch>,
@@ -1,35 +1,93 @@
+pub mod compatibility;
+pub mod container;
+
+use std::cell::RefCell;
+use std::io::BufRead;
 use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::Cursor;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::rc::Rc;
 
+use arrow::array::ArrayRef;
 use arrow::array::RecordBatchReader;
+use arrow::compute::cast;
+use arrow::datatypes::Schema;
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+use arrow_avro::compression::CompressionCodec;
 use arrow_avro::reader::ReaderBuilder;
+use arrow_avro::schema::AvroSchema;
+use arrow_avro::schema::SCHEMA_METADATA_KEY;
+use arrow_avro::writer::AvroWriter;
+use arrow_avro::writer::WriterBuilder;
 
 use crate::Error;
 use crate::Result;
+use crate::cli::AvroCodec;
+use crate::cli::AvroOptions;
 use crate::pipeline::LimitingRecordBatchReader;
+use crate::pipeline::OffsetRecordBatchReader;
 use crate::pipeline::ReadArgs;
 use crate::pipeline::RecordBatchReaderSource;
 use crate::pipeline::Source;
 use crate::pipeline::Step;
 use crate::pipeline::WriteArgs;
+use crate::pipeline::avro::container::AVRO_SCHEMA_KEY;
+use crate::pipeline::avro::container::AvroBlockReader;
+use crate::pipeline::avro::container::AvroHeader;
+use crate::pipeline::avro::container::read_avro_header;
+use crate::pipeline::output::OutputFile;
 
 /// Pipeline step that reads an Avro file and produces a record batch reader.
 pub struct ReadAvroStep {
     pub args: ReadArgs,
+    /// Reader schema (JSON) that the file's writer schema is resolved against. When `None`, the
+    /// file is read with its own schema.
+    pub reader_schema: Option<String>,
 }
 
 impl Source<dyn RecordBatchReader + 'static> for ReadAvroStep {
     fn get(&mut self) -> Result<Box<dyn RecordBatchReader + 'static>> {
-        read_avro(&self.args).map(|reader| Box::new(reader) as Box<dyn RecordBatchReader + 'static>)
+        read_avro(&self.args, self.reader_schema.as_deref())
+            .map(|reader| Box::new(reader) as Box<dyn RecordBatchReader + 'static>)
     }
 }
 
 /// Read an Avro file and return a RecordBatchReader.
-pub fn read_avro(args: &ReadArgs) -> Result<impl RecordBatchReader + 'static> {
-    let file = std::fs::File::open(&args.path).map_err(Error::IoError)?;
-    let reader = BufReader::new(file);
-    let arrow_reader = ReaderBuilder::new()
-        .build(reader)
-        .map_err(Error::ArrowError)?;
+///
+/// When an offset is specified, whole blocks before the offset are skipped using their record
+/// counts, without being decoded. When a reader schema is given, records are resolved against
+/// it using the Avro schema resolution rules: fields missing from the file take their
+/// defaults, fields the reader schema lacks are dropped, and types are promoted.
+pub fn read_avro(
+    args: &ReadArgs,
+    reader_schema: Option<&str>,
+) -> Result<impl RecordBatchReader + 'static> {
+    let (reader, skip) = match args.offset {
+        Some(offset) if offset > 0 => seek_avro_block(&args.path, offset)?,
+        _ => {
+            let file = std::fs::File::open(&args.path).map_err(Error::IoError)?;
+            (Box::new(BufReader::new(file)) as Box<dyn BufRead>, 0)
+        }
+    };
+    let mut builder = ReaderBuilder::new();
+    if let Some(json) = reader_schema {
+        builder = builder.with_reader_schema(AvroSchema::new(json.to_string()));
+    }
+    let arrow_reader = builder.build(reader).map_err(Error::ArrowError)?;
+    let arrow_reader: Box<dyn RecordBatchReader + 'static> = if skip > 0 {
+        Box::new(OffsetRecordBatchReader {
+            inner: arrow_reader,
+            offset: skip,
+            records_skipped: 0,
+        })
+    } else {
+        Box::new(arrow_reader)
+    };
 
     if let Some(limit) = args.limit {
         Ok(Box::new(LimitingRecordBatchReader {
@@ -38,13 +96,45 @@ pub fn read_avro(args: &ReadArgs) -> Result<impl RecordBatchReader + 'static> {
             records_read: 0,
         }) as Box<dyn RecordBatchReader + 'static>)
     } else {
-        Ok(Box::new(arrow_reader) as Box<dyn RecordBatchReader + 'static>)
+        Ok(arrow_reader)
+    }
+}
+
+/// Opens an Avro file positioned at the block that contains row `offset`, returning a reader
+/// over the file header followed by that block and the rest of the file, and the number of
+/// rows in that block that come before `offset`.
+fn seek_avro_block(path: &str, offset: usize) -> Result<(Box<dyn BufRead>, usize)> {
+    let mut file = BufReader::new(std::fs::File::open(path).map_err(Error::IoError)?);
+    let header = read_avro_header(&mut file)?;
+    let mut skipped = 0usize;
+    let mut start = None;
+    for block in AvroBlockReader::new(&mut file, &header) {
+        let block = block?;
+        let record_count = block.record_count as usize;
+        if skipped + record_count > offset {
+            start = Some(block.offset);
+            break;
+        }
+        skipped += record_count;
     }
+
+    let mut file = file.into_inner();
+    let mut header_bytes = vec![0u8; header.length as usize];
+    file.seek(SeekFrom::Start(0)).map_err(Error::IoError)?;
+    file.read_exact(&mut header_bytes).map_err(Error::IoError)?;
+    let start = match start {
+        Some(start) => SeekFrom::Start(start),
+        None => SeekFrom::End(0),
+    };
+    file.seek(start).map_err(Error::IoError)?;
+    let reader = Cursor::new(header_bytes).chain(BufReader::new(file));
+    Ok((Box::new(reader), offset - skipped))
 }
 
 /// Pipeline step that writes record batches to an Avro file.
 pub struct WriteAvroStep {
     pub args: WriteArgs,
+    pub options: AvroOptions,
 }
 
 /// Result of successfully writing an Avro file.
@@ -55,27 +145,151 @@ impl Step for WriteAvroStep {
     type Output = WriteAvroResult;
 
     fn execute(self, mut input: Self::Input) -> Result<Self::Output> {
-        use arrow_avro::writer::AvroWriter;
-
-        let path = self.args.path.as_str();
-        let file = std::fs::File::create(path).map_err(Error::IoError)?;
-
         let reader = input.get()?;
-        let schema = reader.schema();
+        // With --avro-schema, batches are conformed to the Arrow form of that schema, whose
+        // metadata carries the schema JSON for the encoder to use verbatim.
+        let avsc = match &self.options.avro_schema {
+            Some(path) => {
+                let json = std::fs::read_to_string(path).map_err(Error::IoError)?;
+                let target = arrow_schema_from_avsc(&json)?;
+                let input_schema = reader.schema();
+                if let Some(field) = target
+                    .fields()
+                    .iter()
+                    .find(|f| input_schema.index_of(f.name()).is_err())
+                {
+                    return Err(Error::AvroSchemaError(format!(
+                        "Field '{}' of {path} is not a column of the input",
+                        field.name()
+                    )));
+                }
+                Some((json, SchemaRef::new(target)))
+            }
+            None => None,
+        };
+        let schema = match &avsc {
+            Some((_, target)) => (**target).clone(),
+            None => (*reader.schema()).clone(),
+        };
 
-        let mut writer = AvroWriter::new(file, (*schema).clone()).map_err(Error::ArrowError)?;
+        // The writer encodes records with the verbatim schema but advertises one regenerated
+        // from the Arrow schema in the file header, so its output is captured and the header
+        // is rewritten to carry the given schema.
+        let buffer = SharedBuffer::default();
+        let mut writer: AvroWriter<_> = WriterBuilder::new(schema)
+            .with_compression(compression_codec(self.options.avro_codec))
+            .build(buffer.clone())
+            .map_err(|e| Error::ArrowError(e.into()))?;
+        let mut header = read_avro_header(&mut buffer.take().as_slice())?;
+        if let Some((json, _)) = &avsc {
+            for (key, value) in header.metadata.iter_mut() {
+                if key == AVRO_SCHEMA_KEY {
+                    *value = json.as_bytes().to_vec();
+                }
+            }
+        }
 
+        let path = self.args.path.as_str();
+        let mut output = OutputFile::create(path, self.args.no_clobber)?;
+        let mut file = BufWriter::new(&mut output);
+        file.write_all(&header.to_bytes()).map_err(Error::IoError)?;
         for batch in reader {
             let batch = batch.map_err(Error::ArrowError)?;
-            writer.write(&batch).map_err(Error::ArrowError)?;
+            match &avsc {
+                Some((_, target)) => writer.write(&conform_batch(&batch, target)?),
+                None => writer.write(&batch),
+            }
+            .map_err(|e| Error::ArrowError(e.into()))?;
+            file.write_all(&buffer.take()).map_err(Error::IoError)?;
         }
 
-        writer.finish().map_err(Error::ArrowError)?;
+        writer.finish().map_err(|e| Error::ArrowError(e.into()))?;
+        file.write_all(&buffer.take()).map_err(Error::IoError)?;
+        file.flush().map_err(Error::IoError)?;
+        drop(file);
+        output.commit()?;
 
         Ok(WriteAvroResult {})
     }
 }
 
+/// An in-memory sink shared with a writer that owns it, so that its output can be taken as it
+/// is written.
+#[derive(Clone, Default)]
+struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+impl SharedBuffer {
+    fn take(&self) -> Vec<u8> {
+        std::mem::take(&mut self.0.borrow_mut())
+    }
+}
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn compression_codec(codec: AvroCodec) -> Option<CompressionCodec> {
+    match codec {
+        AvroCodec::Null => None,
+        AvroCodec::Deflate => Some(CompressionCodec::Deflate),
+        AvroCodec::Snappy => Some(CompressionCodec::Snappy),
+        AvroCodec::Zstd => Some(CompressionCodec::ZStandard),
+        AvroCodec::Bzip2 => Some(CompressionCodec::Bzip2),
+        AvroCodec::Xz => Some(CompressionCodec::Xz),
+    }
+}
+
+/// Returns the Arrow schema that data written with the Avro schema `json` is read back as,
+/// with `json` kept in the schema metadata under the `avro.schema` key.
+///
+/// `arrow_avro` only maps Avro schemas to Arrow when reading, so this decodes the header of an
+/// empty container file that carries `json` as its writer schema.
+pub fn arrow_schema_from_avsc(json: &str) -> Result<Schema> {
+    let header = AvroHeader {
+        metadata: vec![(AVRO_SCHEMA_KEY.to_string(), json.as_bytes().to_vec())],
+        sync: [0; 16],
+        length: 0,
+    };
+    let reader = ReaderBuilder::new()
+        .build(Cursor::new(header.to_bytes()))
+        .map_err(|e| Error::AvroSchemaError(e.to_string()))?;
+    let schema = reader.schema();
+    let mut metadata = schema.metadata().clone();
+    metadata.insert(SCHEMA_METADATA_KEY.to_string(), json.to_string());
+    Ok(Schema::new_with_metadata(schema.fields().clone(), metadata))
+}
+
+/// Selects the columns of `target` from `batch` by name and casts them to its types.
+fn conform_batch(batch: &RecordBatch, target: &SchemaRef) -> Result<RecordBatch> {
+    let columns = target
+        .fields()
+        .iter()
+        .map(|field| {
+            let column = batch.column_by_name(field.name()).ok_or_else(|| {
+                Error::AvroSchemaError(format!(
+                    "Field '{}' is not a column of the input",
+                    field.name()
+                ))
+            })?;
+            cast(column, field.data_type()).map_err(|e| {
+                Error::AvroSchemaError(format!(
+                    "Column '{}' cannot be written as {}: {e}",
+                    field.name(),
+                    field.data_type()
+                ))
+            })
+        })
+        .collect::<Result<Vec<ArrayRef>>>()?;
+    RecordBatch::try_new(target.clone(), columns).map_err(Error::ArrowError)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -89,7 +303,7 @@ mod tests {
             limit: None,
             offset: None,
         };
-        let mut reader = read_avro(&args).expect("read_avro failed");
+        let mut reader = read_avro(&args, None).expect("read_avro failed");
         let schema = reader.schema();
         assert!(!schema.fields().is_empty(), "Schema should have columns");
         let batch = reader
@@ -99,4 +313,143 @@ mod tests {
             .expect("Failed to read batch");
         assert!(batch.num_rows() > 0, "Expected at least one row");
     }
+
+    #[test]
+    fn test_read_avro_with_offset() {
+        // userdata5.avro has 1000 rows in several blocks; ids run from 1.
+        let args = ReadArgs {
+            path: "fixtures/userdata5.avro".to_string(),
+            limit: Some(3),
+            offset: Some(998),
+        };
+        let batches: Vec<_> = read_avro(&args, None)
+            .expect("read_avro failed")
+            .collect::<arrow::error::Result<_>>()
+            .expect("Failed to read batches");
+        let ids: Vec<i64> = batches
+            .iter()
+            .flat_map(|b| {
+                b.column_by_name("id")
+                    .unwrap()
+                    .as_any()
+                    .downcast_ref::<arrow::array::Int64Array>()
+                    .unwrap()
+                    .values()
+                    .to_vec()
+            })
+            .collect();
+        assert_eq!(ids, vec![999, 1000]);
+    }
+
+    fn userdata_source() -> RecordBatchReaderSource {
+        Box::new(ReadAvroStep {
+            args: ReadArgs {
+                path: "fixtures/userdata5.avro".to_string(),
+                limit: None,
+                offset: None,
+            },
+            reader_schema: None,
+        })
+    }
+
+    #[test]
+    fn test_write_avro_with_schema_and_codec() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let path = temp_dir
+            .path()
+            .join("out.avro")
+            .to_str()
+            .unwrap()
+            .to_string();
+        let step = WriteAvroStep {
+            args: WriteArgs {
+                path: path.clone(),
+                no_clobber: false,
+            },
+            options: AvroOptions {
+                avro_codec: AvroCodec::Deflate,
+                avro_schema: Some("fixtures/userdata_export.avsc".to_string()),
+            },
+        };
+        step.execute(userdata_source()).expect("write failed");
+
+        let mut file = BufReader::new(std::fs::File::open(&path).unwrap());
+        let header = read_avro_header(&mut file).unwrap();
+        assert_eq!(header.codec(), "deflate");
+        let schema: serde_json::Value = serde_json::from_str(header.schema().unwrap()).unwrap();
+        assert_eq!(schema["name"], "Registration");
+        assert_eq!(schema["namespace"], "com.example.users");
+        assert_eq!(schema["doc"], "A user registration");
+
+        let args = ReadArgs {
+            path,
+            limit: Some(1),
+            offset: None,
+        };
+        let batch = read_avro(&args, None).unwrap().next().unwrap().unwrap();
+        let names: Vec<String> = batch
+            .schema()
+            .fields()
+            .iter()
+            .map(|f| f.name().clone())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["id", "first_name", "registration_dttm", "salary"]
+        );
+        let registered = batch
+            .column(2)
+            .as_any()
+            .downcast_ref::<arrow::array::TimestampMillisecondArray>()
+            .expect("registration_dttm should be a timestamp");
+        assert_eq!(registered.value(0), 1_454_582_047_000);
+    }
+
+    #[test]
+    fn test_write_avro_schema_missing_column() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let step = WriteAvroStep {
+            args: WriteArgs {
+                path: temp_dir
+                    .path()
+                    .join("out.avro")
+                    .to_str()
+                    .unwrap()
+                    .to_string(),
+                no_clobber: false,
+            },
+            options: AvroOptions {
+                avro_codec: AvroCodec::Null,
+                avro_schema: Some("fixtures/user_v1.avsc".to_string()),
+            },
+        };
+        let result = step.execute(userdata_source());
+        assert!(matches!(result, Err(Error::AvroSchemaError(_))));
+    }
+
+    #[test]
+    fn test_read_avro_with_reader_schema() {
+        let reader_schema = std::fs::read_to_string("fixtures/userdata_v2.avsc").unwrap();
+        let args = ReadArgs {
+            path: "fixtures/userdata5.avro".to_string(),
+            limit: Some(2),
+            offset: None,
+        };
+        let batch = read_avro(&args, Some(&reader_schema))
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+        let names: Vec<String> = batch
+            .schema()
+            .fields()
+            .iter()
+            .map(|f| f.name().clone())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["id", "first_name", "last_name", "salary", "nickname"]
+        );
+        assert_eq!(batch.column(4).null_count(), batch.num_rows());
+    }
 }
@@ -0,0 +1,741 @@
+//! Avro schema resolution rules, used to check whether data written with one schema can be
+//! read with another.
+//!
+//! The rules follow the "Schema Resolution" section of the Avro specification: numeric and
+//! string/bytes promotions, union branch matching (which covers nullability changes), reader
+//! field defaults for fields missing from the writer, enum symbols, and fixed sizes. Record
+//! names are not compared, since schemas derived from Parquet or ORC files carry generated
+//! names. Unlike the specification, a change of logical type (e.g. `timestamp-millis` to
+//! `timestamp-micros`) is reported, because it changes the meaning of the stored values.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use serde::Serialize;
+use serde_json::Map;
+use serde_json::Value;
+
+use crate::Error;
+use crate::Result;
+use crate::cli::CompatibilityMode;
+
+/// A parsed Avro schema.
+#[derive(Debug, Clone)]
+pub struct AvroSchemaDef {
+    root: AvroType,
+    /// Named types (records, enums, fixed) by full name.
+    named: HashMap<String, AvroType>,
+}
+
+/// An Avro type, with its logical type annotation (if any).
+#[derive(Debug, Clone)]
+struct AvroType {
+    kind: Kind,
+    logical: Option<LogicalType>,
+}
+
+#[derive(Debug, Clone)]
+enum Kind {
+    Null,
+    Boolean,
+    Int,
+    Long,
+    Float,
+    Double,
+    Bytes,
+    String,
+    Record(Vec<AvroField>),
+    Enum {
+        symbols: Vec<String>,
+        default: Option<String>,
+    },
+    Array(Box<AvroType>),
+    Map(Box<AvroType>),
+    Fixed(u64),
+    Union(Vec<AvroType>),
+    /// A reference to a named type by full name.
+    Ref(String),
+}
+
+#[derive(Debug, Clone)]
+struct AvroField {
+    name: String,
+    aliases: Vec<String>,
+    ty: AvroType,
+    has_default: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LogicalType {
+    name: String,
+    precision: Option<u64>,
+    scale: Option<u64>,
+}
+
+impl std::fmt::Display for LogicalType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.precision, self.scale) {
+            (Some(p), Some(s)) => write!(f, "{}({p}, {s})", self.name),
+            (Some(p), None) => write!(f, "{}({p})", self.name),
+            _ => write!(f, "{}", self.name),
+        }
+    }
+}
+
+/// A change that prevents one schema from reading data written with another.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BreakingChange {
+    /// The direction in which the change breaks: `backward` or `forward`.
+    pub direction: String,
+    /// Dotted path to the affected field; `[]` marks array items and `{}` map values.
+    pub path: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for BreakingChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}] {}: {}", self.direction, self.path, self.message)
+    }
+}
+
+impl AvroSchemaDef {
+    /// Parses an Avro schema from its JSON representation.
+    pub fn parse(json: &str) -> Result<Self> {
+        let value: Value = serde_json::from_str(json)
+            .map_err(|e| Error::AvroSchemaError(format!("not valid JSON: {e}")))?;
+        let mut parser = Parser::default();
+        let root = parser.parse(&value, None)?;
+        let schema = Self {
+            root,
+            named: parser.named,
+        };
+        if let Some(name) = parser.refs.iter().find(|r| schema.lookup(r).is_none()) {
+            return Err(Error::AvroSchemaError(format!("unknown type '{name}'")));
+        }
+        Ok(schema)
+    }
+
+    /// Looks up a named type by full name, falling back to a type defined in the null
+    /// namespace with the same simple name.
+    fn lookup(&self, full_name: &str) -> Option<&AvroType> {
+        self.named.get(full_name).or_else(|| {
+            let (_, simple) = full_name.rsplit_once('.')?;
+            self.named.get(simple)
+        })
+    }
+
+    /// Follows named type references.
+    fn resolve<'a>(&'a self, ty: &'a AvroType) -> (&'a AvroType, Option<&'a str>) {
+        match &ty.kind {
+            Kind::Ref(name) => match self.lookup(name) {
+                Some(named) => (named, Some(name.as_str())),
+                None => (ty, None),
+            },
+            _ => (ty, None),
+        }
+    }
+
+    /// Describes a type for messages, e.g. `long (timestamp-millis)` or `array<string>`.
+    fn describe(&self, ty: &AvroType) -> String {
+        let (ty, name) = self.resolve(ty);
+        let base = match &ty.kind {
+            Kind::Null => "null".to_string(),
+            Kind::Boolean => "boolean".to_string(),
+            Kind::Int => "int".to_string(),
+            Kind::Long => "long".to_string(),
+            Kind::Float => "float".to_string(),
+            Kind::Double => "double".to_string(),
+            Kind::Bytes => "bytes".to_string(),
+            Kind::String => "string".to_string(),
+            Kind::Record(_) => format!("record {}", name.unwrap_or_default()),
+            Kind::Enum { .. } => format!("enum {}", name.unwrap_or_default()),
+            Kind::Array(items) => format!("array<{}>", self.describe(items)),
+            Kind::Map(values) => format!("map<{}>", self.describe(values)),
+            Kind::Fixed(size) => format!("fixed({size})"),
+            Kind::Union(branches) => format!(
+                "union [{}]",
+                branches
+                    .iter()
+                    .map(|b| self.describe(b))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Kind::Ref(name) => name.clone(),
+        };
+        match &ty.logical {
+            Some(logical) => format!("{base} ({logical})"),
+            None => base,
+        }
+    }
+}
+
+/// Builds an [`AvroType`] tree from schema JSON, collecting named type definitions.
+#[derive(Default)]
+struct Parser {
+    named: HashMap<String, AvroType>,
+    refs: Vec<String>,
+}
+
+impl Parser {
+    fn parse(&mut self, value: &Value, namespace: Option<&str>) -> Result<AvroType> {
+        match value {
+            Value::String(name) => Ok(self.parse_name(name, namespace)),
+            Value::Array(branches) => {
+                let branches = branches
+                    .iter()
+                    .map(|b| self.parse(b, namespace))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(plain(Kind::Union(branches)))
+            }
+            Value::Object(obj) => self.parse_object(obj, namespace),
+            other => Err(Error::AvroSchemaError(format!(
+                "expected a type name, union, or object, got {other}"
+            ))),
+        }
+    }
+
+    fn parse_name(&mut self, name: &str, namespace: Option<&str>) -> AvroType {
+        let kind = match name {
+            "null" => Kind::Null,
+            "boolean" => Kind::Boolean,
+            "int" => Kind::Int,
+            "long" => Kind::Long,
+            "float" => Kind::Float,
+            "double" => Kind::Double,
+            "bytes" => Kind::Bytes,
+            "string" => Kind::String,
+            _ => {
+                let full_name = full_name(name, namespace);
+                self.refs.push(full_name.clone());
+                Kind::Ref(full_name)
+            }
+        };
+        plain(kind)
+    }
+
+    fn parse_object(
+        &mut self,
+        obj: &Map<String, Value>,
+        namespace: Option<&str>,
+    ) -> Result<AvroType> {
+        let type_name = match obj.get("type") {
+            Some(Value::String(t)) => t.as_str(),
+            Some(inner) => return self.parse(inner, namespace),
+            None => {
+                return Err(Error::AvroSchemaError(
+                    "schema object has no 'type'".to_string(),
+                ));
+            }
+        };
+        let kind = match type_name {
+            "record" | "error" => {
+                let (name, ns) = self.named_type_name(obj, namespace)?;
+                let fields = obj
+                    .get("fields")
+                    .and_then(Value::as_array)
+                    .ok_or_else(|| {
+                        Error::AvroSchemaError(format!("record '{name}' has no 'fields' array"))
+                    })?
+                    .iter()
+                    .map(|field| self.parse_field(field, ns.as_deref()))
+                    .collect::<Result<Vec<_>>>()?;
+                return Ok(self.define(name, plain(Kind::Record(fields))));
+            }
+            "enum" => {
+                let (name, _) = self.named_type_name(obj, namespace)?;
+                let symbols = obj
+                    .get("symbols")
+                    .and_then(Value::as_array)
+                    .ok_or_else(|| {
+                        Error::AvroSchemaError(format!("enum '{name}' has no 'symbols' array"))
+                    })?
+                    .iter()
+                    .filter_map(|s| s.as_str().map(str::to_string))
+                    .collect();
+                let default = obj
+                    .get("default")
+                    .and_then(Value::as_str)
+                    .map(str::to_string);
+                return Ok(self.define(name, plain(Kind::Enum { symbols, default })));
+            }
+            "fixed" => {
+                let (name, _) = self.named_type_name(obj, namespace)?;
+                let size = obj.get("size").and_then(Value::as_u64).ok_or_else(|| {
+                    Error::AvroSchemaError(format!("fixed '{name}' has no 'size'"))
+                })?;
+                // The logical type lives on the definition; references resolve to it.
+                let fixed = AvroType {
+                    kind: Kind::Fixed(size),
+                    logical: logical_type(obj),
+                };
+                return Ok(self.define(name, fixed));
+            }
+            "array" => {
+                let items = obj
+                    .get("items")
+                    .ok_or_else(|| Error::AvroSchemaError("array has no 'items'".to_string()))?;
+                Kind::Array(Box::new(self.parse(items, namespace)?))
+            }
+            "map" => {
+                let values = obj
+                    .get("values")
+                    .ok_or_else(|| Error::AvroSchemaError("map has no 'values'".to_string()))?;
+                Kind::Map(Box::new(self.parse(values, namespace)?))
+            }
+            other => {
+                let mut ty = self.parse_name(other, namespace);
+                if matches!(ty.kind, Kind::Ref(_)) {
+                    return Ok(ty);
+                }
+                ty.logical = logical_type(obj);
+                return Ok(ty);
+            }
+        };
+        Ok(AvroType {
+            kind,
+            logical: logical_type(obj),
+        })
+    }
+
+    fn parse_field(&mut self, field: &Value, namespace: Option<&str>) -> Result<AvroField> {
+        let obj = field
+            .as_object()
+            .ok_or_else(|| Error::AvroSchemaError(format!("invalid record field {field}")))?;
+        let name = obj
+            .get("name")
+            .and_then(Value::as_str)
+            .ok_or_else(|| Error::AvroSchemaError(format!("record field has no 'name': {field}")))?
+            .to_string();
+        let ty = obj
+            .get("type")
+            .ok_or_else(|| Error::AvroSchemaError(format!("field '{name}' has no 'type'")))?;
+        let aliases = obj
+            .get("aliases")
+            .and_then(Value::as_array)
+            .map(|a| {
+                a.iter()
+                    .filter_map(|s| s.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(AvroField {
+            ty: self.parse(ty, namespace)?,
+            name,
+            aliases,
+            has_default: obj.contains_key("default"),
+        })
+    }
+
+    /// Returns the full name and namespace of a named type definition.
+    fn named_type_name(
+        &self,
+        obj: &Map<String, Value>,
+        namespace: Option<&str>,
+    ) -> Result<(String, Option<String>)> {
+        let name = obj
+            .get("name")
+            .and_then(Value::as_str)
+            .ok_or_else(|| Error::AvroSchemaError("named type has no 'name'".to_string()))?;
+        let namespace = obj
+            .get("namespace")
+            .and_then(Value::as_str)
+            .filter(|ns| !ns.is_empty())
+            .or(namespace);
+        let full_name = full_name(name, namespace);
+        let ns = full_name.rsplit_once('.').map(|(ns, _)| ns.to_string());
+        Ok((full_name, ns))
+    }
+
+    /// Registers a named type and returns a reference to it.
+    fn define(&mut self, name: String, ty: AvroType) -> AvroType {
+        self.named.insert(name.clone(), ty);
+        plain(Kind::Ref(name))
+    }
+}
+
+fn plain(kind: Kind) -> AvroType {
+    AvroType {
+        kind,
+        logical: None,
+    }
+}
+
+fn full_name(name: &str, namespace: Option<&str>) -> String {
+    match namespace {
+        Some(ns) if !name.contains('.') => format!("{ns}.{name}"),
+        _ => name.to_string(),
+    }
+}
+
+fn logical_type(obj: &Map<String, Value>) -> Option<LogicalType> {
+    obj.get("logicalType")
+        .and_then(Value::as_str)
+        .map(|name| LogicalType {
+            name: name.to_string(),
+            precision: obj.get("precision").and_then(Value::as_u64),
+            scale: obj.get("scale").and_then(Value::as_u64),
+        })
+}
+
+/// A problem found while resolving a writer schema against a reader schema.
+struct Issue {
+    path: String,
+    message: String,
+}
+
+/// Resolves a writer schema against a reader schema, collecting the issues that would stop
+/// the reader from decoding the writer's data. `reader_label` and `writer_label` name the
+/// schemas ("old" or "new") in messages.
+struct Resolver<'a> {
+    reader: &'a AvroSchemaDef,
+    writer: &'a AvroSchemaDef,
+    reader_label: &'static str,
+    writer_label: &'static str,
+    /// Pairs of named types already being compared, to stop at recursive types.
+    visited: HashSet<(String, String)>,
+}
+
+impl Resolver<'_> {
+    fn resolve(&mut self, path: &str, reader: &AvroType, writer: &AvroType) -> Vec<Issue> {
+        let (r, r_name) = self.reader.resolve(reader);
+        let (w, w_name) = self.writer.resolve(writer);
+        if let (Some(r_name), Some(w_name)) = (r_name, w_name)
+            && !self
+                .visited
+                .insert((r_name.to_string(), w_name.to_string()))
+        {
+            return Vec::new();
+        }
+
+        match (&r.kind, &w.kind) {
+            (_, Kind::Union(w_branches)) => {
+                let mut issues = Vec::new();
+                for branch in w_branches {
+                    let (b, _) = self.writer.resolve(branch);
+                    let reader_is_union = matches!(r.kind, Kind::Union(_));
+                    if matches!(b.kind, Kind::Null)
+                        && !reader_is_union
+                        && !matches!(r.kind, Kind::Null)
+                    {
+                        issues.push(self.issue(
+                            path,
+                            format!(
+                                "is nullable in the {} schema but not in the {} schema",
+                                self.writer_label, self.reader_label
+                            ),
+                        ));
+                        continue;
+                    }
+                    issues.extend(self.resolve(path, reader, branch));
+                }
+                issues
+            }
+            (Kind::Union(r_branches), _) => {
+                // The first reader branch that resolves without issues is used.
+                for branch in r_branches {
+                    if self.try_resolve(path, branch, writer) {
+                        return Vec::new();
+                    }
+                }
+                // Report the issues of a branch of the same kind, if any, for a useful message.
+                let same_kind = r_branches
+                    .iter()
+                    .find(|b| same_kind(&self.reader.resolve(b).0.kind, &w.kind));
+                match same_kind {
+                    Some(branch) => self.resolve(path, branch, writer),
+                    None => vec![self.type_changed(path, r, w)],
+                }
+            }
+            (Kind::Record(r_fields), Kind::Record(w_fields)) => {
+                let mut issues = Vec::new();
+                for field in r_fields {
+                    let field_path = join(path, &field.name);
+                    let w_field = w_fields
+                        .iter()
+                        .find(|w| w.name == field.name || field.aliases.contains(&w.name));
+                    match w_field {
+                        Some(w_field) => {
+                            issues.extend(self.resolve(&field_path, &field.ty, &w_field.ty));
+                        }
+                        None if !field.has_default => issues.push(self.issue(
+                            &field_path,
+                            format!(
+                                "is missing from the {} schema and has no default in the {} schema",
+                                self.writer_label, self.reader_label
+                            ),
+                        )),
+                        None => {}
+                    }
+                }
+                issues
+            }
+            (
+                Kind::Enum {
+                    symbols: r_symbols,
+                    default,
+                },
+                Kind::Enum {
+                    symbols: w_symbols, ..
+                },
+            ) => {
+                if default.is_some() {
+                    return Vec::new();
+                }
+                w_symbols
+                    .iter()
+                    .filter(|s| !r_symbols.contains(s))
+                    .map(|s| {
+                        self.issue(
+                            path,
+                            format!(
+                                "enum symbol '{s}' is missing from the {} schema, which has no default",
+                                self.reader_label
+                            ),
+                        )
+                    })
+                    .collect()
+            }
+            (Kind::Array(r_items), Kind::Array(w_items)) => {
+                self.resolve(&format!("{path}[]"), r_items, w_items)
+            }
+            (Kind::Map(r_values), Kind::Map(w_values)) => {
+                self.resolve(&format!("{path}{{}}"), r_values, w_values)
+            }
+            (Kind::Fixed(r_size), Kind::Fixed(w_size)) if r_size != w_size => {
+                vec![self.issue(
+                    path,
+                    format!("fixed size changed from {w_size} to {r_size}"),
+                )]
+            }
+            (r_kind, w_kind) if same_kind(r_kind, w_kind) || promotable(w_kind, r_kind) => {
+                self.check_logical(path, r, w).into_iter().collect()
+            }
+            _ => vec![self.type_changed(path, r, w)],
+        }
+    }
+
+    /// Returns true when the writer type resolves against the reader type without issues.
+    fn try_resolve(&mut self, path: &str, reader: &AvroType, writer: &AvroType) -> bool {
+        let visited = self.visited.clone();
+        let ok = self.resolve(path, reader, writer).is_empty();
+        self.visited = visited;
+        ok
+    }
+
+    fn check_logical(&self, path: &str, r: &AvroType, w: &AvroType) -> Option<Issue> {
+        (r.logical != w.logical).then(|| {
+            let describe = |l: &Option<LogicalType>| {
+                l.as_ref()
+                    .map_or_else(|| "none".to_string(), LogicalType::to_string)
+            };
+            self.issue(
+                path,
+                format!(
+                    "logical type changed from {} ({} schema) to {} ({} schema)",
+                    describe(&w.logical),
+                    self.writer_label,
+                    describe(&r.logical),
+                    self.reader_label
+                ),
+            )
+        })
+    }
+
+    fn type_changed(&self, path: &str, r: &AvroType, w: &AvroType) -> Issue {
+        self.issue(
+            path,
+            format!(
+                "{} type {} cannot be read as {} type {}",
+                self.writer_label,
+                self.writer.describe(w),
+                self.reader_label,
+                self.reader.describe(r)
+            ),
+        )
+    }
+
+    fn issue(&self, path: &str, message: String) -> Issue {
+        Issue {
+            path: if path.is_empty() {
+                "(root)".to_string()
+            } else {
+                path.to_string()
+            },
+            message,
+        }
+    }
+}
+
+fn join(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{path}.{name}")
+    }
+}
+
+fn same_kind(a: &Kind, b: &Kind) -> bool {
+    std::mem::discriminant(a) == std::mem::discriminant(b)
+}
+
+/// Returns true when the Avro specification allows promoting writer values to the reader type.
+fn promotable(writer: &Kind, reader: &Kind) -> bool {
+    matches!(
+        (writer, reader),
+        (Kind::Int, Kind::Long | Kind::Float | Kind::Double)
+            | (Kind::Long, Kind::Float | Kind::Double)
+            | (Kind::Float, Kind::Double)
+            | (Kind::String, Kind::Bytes)
+            | (Kind::Bytes, Kind::String)
+    )
+}
+
+/// Checks the compatibility of a new schema with an old one, returning the breaking changes.
+///
+/// - [`CompatibilityMode::Backward`]: the new schema can read data written with the old one.
+/// - [`CompatibilityMode::Forward`]: the old schema can read data written with the new one.
+/// - [`CompatibilityMode::Full`]: both of the above.
+pub fn check_compatibility(
+    old: &AvroSchemaDef,
+    new: &AvroSchemaDef,
+    mode: CompatibilityMode,
+) -> Vec<BreakingChange> {
+    let mut changes = Vec::new();
+    let mut check = |direction: &str, reader, writer, reader_label, writer_label| {
+        let mut resolver = Resolver {
+            reader,
+            writer,
+            reader_label,
+            writer_label,
+            visited: HashSet::new(),
+        };
+        let issues = resolver.resolve("", &reader.root, &writer.root);
+        changes.extend(issues.into_iter().map(|issue| BreakingChange {
+            direction: direction.to_string(),
+            path: issue.path,
+            message: issue.message,
+        }));
+    };
+    if matches!(mode, CompatibilityMode::Backward | CompatibilityMode::Full) {
+        check("backward", new, old, "new", "old");
+    }
+    if matches!(mode, CompatibilityMode::Forward | CompatibilityMode::Full) {
+        check("forward", old, new, "old", "new");
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema(fields: &str) -> AvroSchemaDef {
+        AvroSchemaDef::parse(&format!(
+            r#"{{"type": "record", "name": "r", "namespace": "ns", "fields": [{fields}]}}"#
+        ))
+        .unwrap()
+    }
+
+    fn check(old: &str, new: &str, mode: CompatibilityMode) -> Vec<String> {
+        check_compatibility(&schema(old), &schema(new), mode)
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn test_identical_schemas() {
+        let fields = r#"{"name": "id", "type": "long"}, {"name": "tags", "type": {"type": "array", "items": "string"}}"#;
+        assert!(check(fields, fields, CompatibilityMode::Full).is_empty());
+    }
+
+    #[test]
+    fn test_type_promotion() {
+        let old = r#"{"name": "id", "type": "int"}"#;
+        let new = r#"{"name": "id", "type": "long"}"#;
+        assert!(check(old, new, CompatibilityMode::Backward).is_empty());
+        assert_eq!(
+            check(old, new, CompatibilityMode::Forward),
+            vec!["[forward] id: new type long cannot be read as old type int"]
+        );
+    }
+
+    #[test]
+    fn test_added_field_needs_default() {
+        let old = r#"{"name": "id", "type": "long"}"#;
+        let new_without_default =
+            r#"{"name": "id", "type": "long"}, {"name": "email", "type": "string"}"#;
+        let new_with_default = r#"{"name": "id", "type": "long"}, {"name": "email", "type": ["null", "string"], "default": null}"#;
+        assert_eq!(
+            check(old, new_without_default, CompatibilityMode::Backward),
+            vec![
+                "[backward] email: is missing from the old schema and has no default in the new schema"
+            ]
+        );
+        assert!(check(old, new_with_default, CompatibilityMode::Full).is_empty());
+    }
+
+    #[test]
+    fn test_removed_required_field() {
+        let old = r#"{"name": "id", "type": "long"}, {"name": "email", "type": "string"}"#;
+        let new = r#"{"name": "id", "type": "long"}"#;
+        assert!(check(old, new, CompatibilityMode::Backward).is_empty());
+        assert_eq!(
+            check(old, new, CompatibilityMode::Forward),
+            vec![
+                "[forward] email: is missing from the new schema and has no default in the old schema"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nullable_changes() {
+        let required = r#"{"name": "name", "type": "string"}"#;
+        let nullable = r#"{"name": "name", "type": ["null", "string"]}"#;
+        assert!(check(required, nullable, CompatibilityMode::Backward).is_empty());
+        assert_eq!(
+            check(nullable, required, CompatibilityMode::Backward),
+            vec!["[backward] name: is nullable in the old schema but not in the new schema"]
+        );
+    }
+
+    #[test]
+    fn test_logical_type_change() {
+        let old = r#"{"name": "ts", "type": {"type": "long", "logicalType": "timestamp-millis"}}"#;
+        let new = r#"{"name": "ts", "type": {"type": "long", "logicalType": "timestamp-micros"}}"#;
+        assert_eq!(
+            check(old, new, CompatibilityMode::Backward),
+            vec![
+                "[backward] ts: logical type changed from timestamp-millis (old schema) to timestamp-micros (new schema)"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_enum_and_nested_record() {
+        let old = r#"{"name": "address", "type": {"type": "record", "name": "Address", "fields": [
+            {"name": "kind", "type": {"type": "enum", "name": "Kind", "symbols": ["HOME", "WORK"]}}
+        ]}}"#;
+        let new = r#"{"name": "address", "type": {"type": "record", "name": "Address", "fields": [
+            {"name": "kind", "type": {"type": "enum", "name": "Kind", "symbols": ["HOME"]}}
+        ]}}"#;
+        assert_eq!(
+            check(old, new, CompatibilityMode::Backward),
+            vec![
+                "[backward] address.kind: enum symbol 'WORK' is missing from the new schema, which has no default"
+            ]
+        );
+        assert!(check(old, new, CompatibilityMode::Forward).is_empty());
+    }
+
+    #[test]
+    fn test_recursive_type() {
+        let fields = r#"{"name": "next", "type": ["null", "ns.r"]}"#;
+        assert!(check(fields, fields, CompatibilityMode::Full).is_empty());
+    }
+
+    #[test]
+    fn test_parse_unknown_type() {
+        assert!(AvroSchemaDef::parse(r#"{"type": "array", "items": "Nope"}"#).is_err());
+    }
+}
@@ -0,0 +1,395 @@
+//! Low-level reading of the Avro object container file format: the file header (magic,
+//! metadata, sync marker) and the sequence of data blocks that follow it.
+//!
+//! `arrow_avro` decodes container files into record batches but does not expose the raw
+//! header or block layout, which `datu` needs for inspection.
+
+use std::io::Read;
+
+use crate::Error;
+use crate::Result;
+
+/// The four magic bytes at the start of every Avro object container file.
+pub const AVRO_MAGIC: [u8; 4] = [b'O', b'b', b'j', 1];
+
+/// Metadata key holding the writer schema (JSON).
+pub const AVRO_SCHEMA_KEY: &str = "avro.schema";
+
+/// Metadata key holding the block compression codec.
+pub const AVRO_CODEC_KEY: &str = "avro.codec";
+
+/// The header of an Avro object container file.
+#[derive(Debug, Clone)]
+pub struct AvroHeader {
+    /// Metadata entries in file order, including the reserved `avro.*` keys.
+    pub metadata: Vec<(String, Vec<u8>)>,
+    /// The 16-byte sync marker that terminates the header and every block.
+    pub sync: [u8; 16],
+    /// Length of the header in bytes; the first block starts at this offset.
+    pub length: u64,
+}
+
+impl AvroHeader {
+    /// Returns the value of the metadata entry with the given key.
+    pub fn get(&self, key: &str) -> Option<&[u8]> {
+        self.metadata
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_slice())
+    }
+
+    /// Returns the writer schema JSON.
+    pub fn schema(&self) -> Result<&str> {
+        let schema = self.get(AVRO_SCHEMA_KEY).ok_or_else(|| {
+            Error::AvroContainerError(format!("header has no '{AVRO_SCHEMA_KEY}' entry"))
+        })?;
+        std::str::from_utf8(schema)
+            .map_err(|e| Error::AvroContainerError(format!("writer schema is not UTF-8: {e}")))
+    }
+
+    /// Returns the block compression codec; `null` (no compression) when absent.
+    pub fn codec(&self) -> String {
+        self.get(AVRO_CODEC_KEY)
+            .map(|v| String::from_utf8_lossy(v).into_owned())
+            .unwrap_or_else(|| "null".to_string())
+    }
+
+    /// Returns the metadata entries that are not reserved `avro.*` keys.
+    pub fn user_metadata(&self) -> impl Iterator<Item = (&str, &[u8])> {
+        self.metadata
+            .iter()
+            .filter(|(k, _)| !k.starts_with("avro."))
+            .map(|(k, v)| (k.as_str(), v.as_slice()))
+    }
+
+    /// Serializes the header as it appears in a container file: magic, metadata map, and sync
+    /// marker.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = AVRO_MAGIC.to_vec();
+        if !self.metadata.is_empty() {
+            write_long(&mut buf, self.metadata.len() as i64);
+            for (key, value) in &self.metadata {
+                write_long(&mut buf, key.len() as i64);
+                buf.extend_from_slice(key.as_bytes());
+                write_long(&mut buf, value.len() as i64);
+                buf.extend_from_slice(value);
+            }
+        }
+        write_long(&mut buf, 0);
+        buf.extend_from_slice(&self.sync);
+        buf
+    }
+}
+
+/// A single data block of an Avro object container file.
+#[derive(Debug, Clone)]
+pub struct AvroBlock {
+    /// Zero-based index of the block in the file.
+    pub index: usize,
+    /// Byte offset of the start of the block in the file.
+    pub offset: u64,
+    /// Number of records in the block.
+    pub record_count: u64,
+    /// The (possibly compressed) serialized records.
+    pub data: Vec<u8>,
+    /// The sync marker that followed the block.
+    pub sync: [u8; 16],
+}
+
+impl AvroBlock {
+    /// Serializes the block as it appears in a container file: record count, size, data, and
+    /// sync marker.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(self.data.len() + 36);
+        write_long(&mut buf, self.record_count as i64);
+        write_long(&mut buf, self.data.len() as i64);
+        buf.extend_from_slice(&self.data);
+        buf.extend_from_slice(&self.sync);
+        buf
+    }
+}
+
+/// Reads the header of an Avro object container file.
+pub fn read_avro_header<R: Read>(reader: &mut R) -> Result<AvroHeader> {
+    let mut reader = CountingReader {
+        inner: reader,
+        position: 0,
+    };
+    let mut magic = [0u8; 4];
+    reader
+        .read_exact(&mut magic)
+        .map_err(|e| Error::AvroContainerError(format!("failed to read magic: {e}")))?;
+    if magic != AVRO_MAGIC {
+        return Err(Error::AvroContainerError(
+            "not an Avro object container file (bad magic)".to_string(),
+        ));
+    }
+
+    let mut metadata = Vec::new();
+    loop {
+        let count = read_long(&mut reader)?;
+        if count == 0 {
+            break;
+        }
+        if count < 0 {
+            // A negative count is followed by the block size in bytes, which we don't need.
+            read_long(&mut reader)?;
+        }
+        for _ in 0..count.unsigned_abs() {
+            let key = read_bytes(&mut reader)?;
+            let key = String::from_utf8(key).map_err(|e| {
+                Error::AvroContainerError(format!("metadata key is not UTF-8: {e}"))
+            })?;
+            let value = read_bytes(&mut reader)?;
+            metadata.push((key, value));
+        }
+    }
+
+    let mut sync = [0u8; 16];
+    reader
+        .read_exact(&mut sync)
+        .map_err(|e| Error::AvroContainerError(format!("failed to read sync marker: {e}")))?;
+
+    Ok(AvroHeader {
+        metadata,
+        sync,
+        length: reader.position,
+    })
+}
+
+/// Iterator over the data blocks of an Avro object container file, positioned just after
+/// the header.
+pub struct AvroBlockReader<R: Read> {
+    reader: R,
+    position: u64,
+    index: usize,
+    done: bool,
+}
+
+impl<R: Read> AvroBlockReader<R> {
+    /// Creates a block reader for a reader that has just consumed the given header.
+    pub fn new(reader: R, header: &AvroHeader) -> Self {
+        Self {
+            reader,
+            position: header.length,
+            index: 0,
+            done: false,
+        }
+    }
+
+    /// Reads the next block, or returns `None` at a clean end of file.
+    fn read_block(&mut self) -> Result<Option<AvroBlock>> {
+        let offset = self.position;
+        let mut reader = CountingReader {
+            inner: &mut self.reader,
+            position: offset,
+        };
+
+        // A clean end of file can only occur before the block's record count.
+        let mut first = [0u8; 1];
+        if reader.read(&mut first)? == 0 {
+            return Ok(None);
+        }
+        let record_count = read_long_with_first_byte(&mut reader, first[0])?;
+        let size = read_long(&mut reader)?;
+        if record_count < 0 || size < 0 {
+            return Err(Error::AvroContainerError(format!(
+                "block {} at offset {offset} has a negative record count or size",
+                self.index
+            )));
+        }
+        let mut data = Vec::new();
+        (&mut reader)
+            .take(size as u64)
+            .read_to_end(&mut data)
+            .map_err(Error::IoError)?;
+        if data.len() as u64 != size as u64 {
+            return Err(Error::AvroContainerError(format!(
+                "block {} at offset {offset} is truncated: expected {size} bytes, found {}",
+                self.index,
+                data.len()
+            )));
+        }
+        let mut sync = [0u8; 16];
+        reader.read_exact(&mut sync).map_err(|e| {
+            Error::AvroContainerError(format!(
+                "block {} at offset {offset} is missing its sync marker: {e}",
+                self.index
+            ))
+        })?;
+
+        self.position = reader.position;
+        let block = AvroBlock {
+            index: self.index,
+            offset,
+            record_count: record_count as u64,
+            data,
+            sync,
+        };
+        self.index += 1;
+        Ok(Some(block))
+    }
+}
+
+impl<R: Read> Iterator for AvroBlockReader<R> {
+    type Item = Result<AvroBlock>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.read_block() {
+            Ok(Some(block)) => Some(Ok(block)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+/// A reader that tracks how many bytes have been read through it.
+struct CountingReader<R: Read> {
+    inner: R,
+    position: u64,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.position += n as u64;
+        Ok(n)
+    }
+}
+
+/// Reads a zig-zag encoded variable-length Avro `long`.
+fn read_long<R: Read>(reader: &mut R) -> Result<i64> {
+    let mut first = [0u8; 1];
+    reader
+        .read_exact(&mut first)
+        .map_err(|e| Error::AvroContainerError(format!("unexpected end of file: {e}")))?;
+    read_long_with_first_byte(reader, first[0])
+}
+
+/// Appends a zig-zag encoded variable-length Avro `long`.
+fn write_long(buf: &mut Vec<u8>, value: i64) {
+    let mut n = ((value << 1) ^ (value >> 63)) as u64;
+    while n >= 0x80 {
+        buf.push((n as u8 & 0x7f) | 0x80);
+        n >>= 7;
+    }
+    buf.push(n as u8);
+}
+
+/// Reads the rest of a zig-zag encoded `long` whose first byte has already been read.
+fn read_long_with_first_byte<R: Read>(reader: &mut R, first: u8) -> Result<i64> {
+    let mut value = u64::from(first & 0x7f);
+    let mut shift = 7;
+    let mut byte = first;
+    while byte & 0x80 != 0 {
+        if shift >= 64 {
+            return Err(Error::AvroContainerError(
+                "variable-length integer is too long".to_string(),
+            ));
+        }
+        let mut next = [0u8; 1];
+        reader
+            .read_exact(&mut next)
+            .map_err(|e| Error::AvroContainerError(format!("unexpected end of file: {e}")))?;
+        byte = next[0];
+        value |= u64::from(byte & 0x7f) << shift;
+        shift += 7;
+    }
+    Ok((value >> 1) as i64 ^ -((value & 1) as i64))
+}
+
+/// Reads a length-prefixed Avro `bytes` (or `string`) value.
+fn read_bytes<R: Read>(reader: &mut R) -> Result<Vec<u8>> {
+    let len = read_long(reader)?;
+    if len < 0 {
+        return Err(Error::AvroContainerError(format!(
+            "negative length {len} for bytes value"
+        )));
+    }
+    // The length comes from the file, so the buffer only grows as bytes actually arrive.
+    let mut buf = Vec::new();
+    reader
+        .take(len as u64)
+        .read_to_end(&mut buf)
+        .map_err(Error::IoError)?;
+    if buf.len() as u64 != len as u64 {
+        return Err(Error::AvroContainerError(format!(
+            "unexpected end of file: expected a {len}-byte value, found {}",
+            buf.len()
+        )));
+    }
+    Ok(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufReader;
+
+    use super::*;
+
+    #[test]
+    fn test_read_long() {
+        for (bytes, expected) in [
+            (vec![0x00], 0),
+            (vec![0x01], -1),
+            (vec![0x02], 1),
+            (vec![0x7f], -64),
+            (vec![0x80, 0x01], 64),
+            (vec![0xd0, 0x0f], 1000),
+        ] {
+            assert_eq!(read_long(&mut bytes.as_slice()).unwrap(), expected);
+            let mut buf = Vec::new();
+            write_long(&mut buf, expected);
+            assert_eq!(buf, bytes);
+        }
+    }
+
+    #[test]
+    fn test_read_avro_header_and_blocks() {
+        let file = std::fs::File::open("fixtures/userdata5.avro").expect("Failed to open file");
+        let mut reader = BufReader::new(file);
+        let header = read_avro_header(&mut reader).expect("Failed to read header");
+        assert!(header.schema().unwrap().contains("kylosample"));
+        let blocks = AvroBlockReader::new(reader, &header)
+            .collect::<Result<Vec<_>>>()
+            .expect("Failed to read blocks");
+        assert!(!blocks.is_empty());
+        assert!(blocks.iter().all(|b| b.sync == header.sync));
+        assert_eq!(blocks.iter().map(|b| b.record_count).sum::<u64>(), 1000);
+        assert_eq!(blocks[0].offset, header.length);
+
+        let file = std::fs::read("fixtures/userdata5.avro").expect("Failed to read file");
+        let first = &blocks[0];
+        let bytes = first.to_bytes();
+        let start = first.offset as usize;
+        assert_eq!(bytes, file[start..start + bytes.len()]);
+        assert_eq!(header.to_bytes(), file[..header.length as usize]);
+    }
+
+    #[test]
+    fn test_read_bytes_truncated() {
+        let mut bytes = Vec::new();
+        write_long(&mut bytes, i64::MAX);
+        bytes.extend_from_slice(b"abc");
+        let result = read_bytes(&mut bytes.as_slice());
+        assert!(
+            matches!(&result, Err(Error::AvroContainerError(e)) if e.contains("found 3")),
+            "{result:?}"
+        );
+    }
+
+    #[test]
+    fn test_read_avro_header_bad_magic() {
+        let result = read_avro_header(&mut b"PAR1".as_slice());
+        assert!(matches!(result, Err(Error::AvroContainerError(_))));
+    }
+}
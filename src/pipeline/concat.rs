@@ -0,0 +1,352 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use arrow::array::ArrayRef;
+use arrow::array::RecordBatchReader;
+use arrow::array::StringArray;
+use arrow::array::new_null_array;
+use arrow::datatypes::DataType;
+use arrow::datatypes::Field;
+use arrow::datatypes::Schema;
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+
+use crate::Error;
+use crate::Result;
+use crate::pipeline::RecordBatchReaderSource;
+use crate::pipeline::Step;
+use crate::pipeline::record_batch_filter::RecordBatchReaderHolder;
+
+/// Name of the column added by [`ConcatStep`] when `source_file_column` is set.
+pub const SOURCE_FILE_COLUMN: &str = "_source_file";
+
+/// An input to [`ConcatStep`]: the file path and a source for its record batches.
+pub struct ConcatInput {
+    pub path: String,
+    pub source: RecordBatchReaderSource,
+}
+
+/// Pipeline step that appends the record batches of several inputs into a single stream.
+///
+/// In strict mode every input must have the same column names and types, in the same order.
+/// With `union_by_name`, the output has every column that appears in any input (in order of
+/// first appearance), columns are matched by name, and columns missing from an input are
+/// filled with nulls; a column must have the same type in every input that has it.
+pub struct ConcatStep {
+    pub union_by_name: bool,
+    /// When true, adds a [`SOURCE_FILE_COLUMN`] column holding each row's input path.
+    pub source_file_column: bool,
+}
+
+impl Step for ConcatStep {
+    type Input = Vec<ConcatInput>;
+    type Output = RecordBatchReaderSource;
+
+    fn execute(self, inputs: Self::Input) -> Result<Self::Output> {
+        if inputs.is_empty() {
+            return Err(Error::PipelinePlanningError(
+                "At least one input is required".to_string(),
+            ));
+        }
+        // The output schema depends on every input, so all readers are opened up front; their
+        // batches are still read one input at a time.
+        let mut readers = VecDeque::with_capacity(inputs.len());
+        for mut input in inputs {
+            let reader = input.source.get()?;
+            readers.push_back((input.path, reader));
+        }
+        let schemas: Vec<(&str, SchemaRef)> = readers
+            .iter()
+            .map(|(path, reader)| (path.as_str(), reader.schema()))
+            .collect();
+        let mut schema = if self.union_by_name {
+            union_schemas(&schemas)?
+        } else {
+            strict_schema(&schemas)?
+        };
+        if self.source_file_column {
+            if schema.field_with_name(SOURCE_FILE_COLUMN).is_ok() {
+                return Err(Error::PipelinePlanningError(format!(
+                    "Input already has a '{SOURCE_FILE_COLUMN}' column"
+                )));
+            }
+            let mut fields = schema.fields().to_vec();
+            fields.push(Arc::new(Field::new(
+                SOURCE_FILE_COLUMN,
+                DataType::Utf8,
+                false,
+            )));
+            schema = Schema::new(fields);
+        }
+        let reader = ConcatRecordBatchReader {
+            readers,
+            schema: Arc::new(schema),
+            source_file_column: self.source_file_column,
+        };
+        Ok(Box::new(RecordBatchReaderHolder {
+            reader: Some(Box::new(reader)),
+        }))
+    }
+}
+
+/// Returns the common schema of inputs that must have the same columns and types.
+fn strict_schema(schemas: &[(&str, SchemaRef)]) -> Result<Schema> {
+    let (first_path, first) = &schemas[0];
+    let mut fields: Vec<Field> = first.fields().iter().map(|f| f.as_ref().clone()).collect();
+    for (path, schema) in &schemas[1..] {
+        let same = schema.fields().len() == fields.len()
+            && schema
+                .fields()
+                .iter()
+                .zip(&fields)
+                .all(|(a, b)| a.name() == b.name() && a.data_type() == b.data_type());
+        if !same {
+            return Err(Error::PipelinePlanningError(format!(
+                "Schema of '{path}' does not match '{first_path}' (use --union-by-name to align columns by name):\n  {}\n  {}",
+                describe_schema(first),
+                describe_schema(schema)
+            )));
+        }
+        for (field, other) in fields.iter_mut().zip(schema.fields()) {
+            if other.is_nullable() {
+                field.set_nullable(true);
+            }
+        }
+    }
+    Ok(Schema::new(fields))
+}
+
+/// Returns the union of the input schemas, matching columns by name.
+fn union_schemas(schemas: &[(&str, SchemaRef)]) -> Result<Schema> {
+    let mut fields: Vec<(Field, &str)> = Vec::new();
+    for (path, schema) in schemas {
+        for field in schema.fields() {
+            match fields.iter_mut().find(|(f, _)| f.name() == field.name()) {
+                Some((existing, first_path)) => {
+                    if existing.data_type() != field.data_type() {
+                        return Err(Error::PipelinePlanningError(format!(
+                            "Column '{}' is {} in '{first_path}' but {} in '{path}'",
+                            field.name(),
+                            existing.data_type(),
+                            field.data_type()
+                        )));
+                    }
+                    if field.is_nullable() {
+                        existing.set_nullable(true);
+                    }
+                }
+                None => fields.push((field.as_ref().clone(), path)),
+            }
+        }
+    }
+    // A column missing from any input is filled with nulls there.
+    let fields = fields
+        .into_iter()
+        .map(|(field, _)| {
+            let everywhere = schemas
+                .iter()
+                .all(|(_, s)| s.field_with_name(field.name()).is_ok());
+            if everywhere {
+                field
+            } else {
+                field.with_nullable(true)
+            }
+        })
+        .collect::<Vec<_>>();
+    Ok(Schema::new(fields))
+}
+
+fn describe_schema(schema: &Schema) -> String {
+    schema
+        .fields()
+        .iter()
+        .map(|f| format!("{}: {}", f.name(), f.data_type()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Record batch reader that yields the batches of each input in turn, aligned to a common
+/// schema.
+struct ConcatRecordBatchReader {
+    readers: VecDeque<(String, Box<dyn RecordBatchReader + 'static>)>,
+    schema: SchemaRef,
+    source_file_column: bool,
+}
+
+impl ConcatRecordBatchReader {
+    /// Aligns a batch from the input at `path` to the output schema.
+    fn align(&self, batch: RecordBatch, path: &str) -> arrow::error::Result<RecordBatch> {
+        let num_rows = batch.num_rows();
+        let input_schema = batch.schema();
+        let columns = self
+            .schema
+            .fields()
+            .iter()
+            .map(|field| {
+                if self.source_file_column && field.name() == SOURCE_FILE_COLUMN {
+                    return Arc::new(StringArray::from(vec![path; num_rows])) as ArrayRef;
+                }
+                match input_schema.index_of(field.name()) {
+                    Ok(i) => batch.column(i).clone(),
+                    Err(_) => new_null_array(field.data_type(), num_rows),
+                }
+            })
+            .collect();
+        RecordBatch::try_new(self.schema.clone(), columns)
+    }
+}
+
+impl RecordBatchReader for ConcatRecordBatchReader {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+impl Iterator for ConcatRecordBatchReader {
+    type Item = arrow::error::Result<RecordBatch>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (path, reader) = self.readers.front_mut()?;
+            match reader.next() {
+                Some(Ok(batch)) => {
+                    let path = path.clone();
+                    return Some(self.align(batch, &path));
+                }
+                Some(Err(e)) => return Some(Err(e)),
+                None => {
+                    self.readers.pop_front();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::Int32Array;
+    use arrow::array::StringArray;
+
+    use super::*;
+    use crate::pipeline::VecRecordBatchReaderSource;
+
+    fn input(path: &str, fields: Vec<(&str, ArrayRef)>) -> ConcatInput {
+        let schema = Schema::new(
+            fields
+                .iter()
+                .map(|(name, array)| Field::new(*name, array.data_type().clone(), false))
+                .collect::<Vec<_>>(),
+        );
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            fields.into_iter().map(|(_, array)| array).collect(),
+        )
+        .unwrap();
+        ConcatInput {
+            path: path.to_string(),
+            source: Box::new(VecRecordBatchReaderSource::new(vec![batch])),
+        }
+    }
+
+    fn ids(values: Vec<i32>) -> ArrayRef {
+        Arc::new(Int32Array::from(values))
+    }
+
+    fn names(values: Vec<&str>) -> ArrayRef {
+        Arc::new(StringArray::from(values))
+    }
+
+    fn collect(mut source: RecordBatchReaderSource) -> (SchemaRef, Vec<RecordBatch>) {
+        let reader = source.get().unwrap();
+        let schema = reader.schema();
+        (
+            schema,
+            reader.collect::<arrow::error::Result<Vec<_>>>().unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_concat_strict() {
+        let step = ConcatStep {
+            union_by_name: false,
+            source_file_column: true,
+        };
+        let source = step
+            .execute(vec![
+                input("a.avro", vec![("id", ids(vec![1, 2]))]),
+                input("b.avro", vec![("id", ids(vec![3]))]),
+            ])
+            .unwrap();
+        let (schema, batches) = collect(source);
+        assert_eq!(schema.field(1).name(), SOURCE_FILE_COLUMN);
+        assert_eq!(batches.len(), 2);
+        let files = batches[1]
+            .column(1)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(files.value(0), "b.avro");
+    }
+
+    #[test]
+    fn test_concat_strict_rejects_different_schemas() {
+        let step = ConcatStep {
+            union_by_name: false,
+            source_file_column: false,
+        };
+        let result = step.execute(vec![
+            input("a.avro", vec![("id", ids(vec![1]))]),
+            input("b.avro", vec![("name", names(vec!["x"]))]),
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_concat_union_by_name() {
+        let step = ConcatStep {
+            union_by_name: true,
+            source_file_column: false,
+        };
+        let source = step
+            .execute(vec![
+                input(
+                    "a.avro",
+                    vec![("id", ids(vec![1])), ("name", names(vec!["x"]))],
+                ),
+                input(
+                    "b.avro",
+                    vec![("email", names(vec!["e"])), ("id", ids(vec![2]))],
+                ),
+            ])
+            .unwrap();
+        let (schema, batches) = collect(source);
+        let columns: Vec<&str> = schema.fields().iter().map(|f| f.name().as_str()).collect();
+        assert_eq!(columns, vec!["id", "name", "email"]);
+        assert!(!schema.field(0).is_nullable());
+        assert!(schema.field(1).is_nullable());
+        assert!(batches[0].column(2).is_null(0));
+        assert!(batches[1].column(1).is_null(0));
+        assert_eq!(
+            batches[1]
+                .column(0)
+                .as_any()
+                .downcast_ref::<Int32Array>()
+                .unwrap()
+                .value(0),
+            2
+        );
+    }
+
+    #[test]
+    fn test_concat_union_by_name_rejects_conflicting_types() {
+        let step = ConcatStep {
+            union_by_name: true,
+            source_file_column: false,
+        };
+        let result = step.execute(vec![
+            input("a.avro", vec![("id", ids(vec![1]))]),
+            input("b.avro", vec![("id", names(vec!["1"]))]),
+        ]);
+        assert!(result.is_err());
+    }
+}
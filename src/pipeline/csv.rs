@@ -1,12 +1,141 @@
+//! Writing record batches as CSV, TSV, and other delimited text.
+
+use std::io::Write;
+
+use arrow::array::Array;
+use arrow::record_batch::RecordBatch;
+use arrow::util::display::ArrayFormatter;
+use arrow::util::display::FormatOptions;
+use chrono::format::Item;
+use chrono::format::StrftimeItems;
+
 use crate::Error;
 use crate::Result;
+use crate::cli::CsvOptions;
+use crate::cli::CsvQuoteStyle;
+use crate::cli::LineTerminator;
 use crate::pipeline::RecordBatchReaderSource;
 use crate::pipeline::Step;
 use crate::pipeline::WriteArgs;
+use crate::pipeline::output::OutputFile;
+
+/// The UTF-8 byte order mark, written first with `--bom`.
+pub const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Writes record batches as delimited text in the dialect given by [`CsvOptions`].
+///
+/// The header row is written with the first batch, so nothing is written for an input
+/// without batches.
+pub struct CsvWriter<W: Write> {
+    writer: csv::Writer<W>,
+    options: CsvOptions,
+    header_written: bool,
+}
 
-/// Pipeline step that writes record batches to a CSV file.
+impl<W: Write> CsvWriter<W> {
+    /// Creates a writer, checking the date and time formats and writing the byte order mark
+    /// if requested.
+    pub fn try_new(mut w: W, options: &CsvOptions) -> Result<Self> {
+        for format in [
+            &options.date_format,
+            &options.time_format,
+            &options.timestamp_format,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+                return Err(Error::GenericError(format!(
+                    "Invalid date or time format '{format}'"
+                )));
+            }
+        }
+        if options.bom {
+            w.write_all(UTF8_BOM)?;
+        }
+        let writer = csv::WriterBuilder::new()
+            .delimiter(options.delimiter.unwrap_or(b','))
+            .quote_style(match options.quote_style {
+                CsvQuoteStyle::Necessary => csv::QuoteStyle::Necessary,
+                CsvQuoteStyle::Always => csv::QuoteStyle::Always,
+                CsvQuoteStyle::NonNumeric => csv::QuoteStyle::NonNumeric,
+                CsvQuoteStyle::Never => csv::QuoteStyle::Never,
+            })
+            .terminator(match options.line_terminator {
+                LineTerminator::Lf => csv::Terminator::Any(b'\n'),
+                LineTerminator::Crlf => csv::Terminator::CRLF,
+            })
+            .from_writer(w);
+        Ok(CsvWriter {
+            writer,
+            options: options.clone(),
+            header_written: false,
+        })
+    }
+
+    /// Writes the rows of a batch, preceded by the header row for the first batch.
+    pub fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        if !self.header_written {
+            if !self.options.no_header {
+                let schema = batch.schema();
+                self.writer
+                    .write_record(schema.fields().iter().map(|f| f.name()))?;
+            }
+            self.header_written = true;
+        }
+
+        let timestamp_format = self.options.timestamp_format.as_deref();
+        let format_options = FormatOptions::default()
+            .with_null(self.options.null_value.as_deref().unwrap_or(""))
+            .with_date_format(self.options.date_format.as_deref())
+            .with_time_format(self.options.time_format.as_deref())
+            .with_datetime_format(timestamp_format)
+            .with_timestamp_format(timestamp_format)
+            .with_timestamp_tz_format(timestamp_format);
+        let formatters = batch
+            .columns()
+            .iter()
+            .map(|column| {
+                if column.data_type().is_nested() {
+                    Err(Error::GenericError(format!(
+                        "Nested type {} is not supported in CSV",
+                        column.data_type()
+                    )))
+                } else {
+                    ArrayFormatter::try_new(column.as_ref(), &format_options)
+                        .map_err(Error::ArrowError)
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut buffer = String::new();
+        let mut record = csv::ByteRecord::with_capacity(1024, formatters.len());
+        for row in 0..batch.num_rows() {
+            record.clear();
+            for formatter in &formatters {
+                buffer.clear();
+                formatter
+                    .value(row)
+                    .write(&mut buffer)
+                    .map_err(Error::ArrowError)?;
+                record.push_field(buffer.as_bytes());
+            }
+            self.writer.write_byte_record(&record)?;
+        }
+        Ok(())
+    }
+
+    /// Flushes the buffered rows to the underlying writer.
+    pub fn flush(&mut self) -> Result<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Pipeline step that writes record batches to a CSV or TSV file.
 pub struct WriteCsvStep {
     pub args: WriteArgs,
+    pub options: CsvOptions,
 }
 
 /// Result of successfully writing a CSV file.
@@ -18,13 +147,16 @@ impl Step for WriteCsvStep {
 
     fn execute(self, mut input: Self::Input) -> Result<Self::Output> {
         let path = self.args.path.as_str();
-        let file = std::fs::File::create(path).map_err(Error::IoError)?;
-        let mut writer = arrow::csv::Writer::new(file);
+        let mut output = OutputFile::create(path, self.args.no_clobber)?;
+        let mut writer = CsvWriter::try_new(&mut output, &self.options)?;
         let reader = input.get()?;
         for batch in reader {
             let batch = batch.map_err(Error::ArrowError)?;
-            writer.write(&batch).map_err(Error::ArrowError)?;
+            writer.write(&batch)?;
         }
+        writer.flush()?;
+        drop(writer);
+        output.commit()?;
         Ok(WriteCsvResult {})
     }
 }
@@ -70,8 +202,14 @@ mod tests {
             reader: Some(Box::new(reader)),
         });
 
-        let args = WriteArgs { path };
-        let writer = WriteCsvStep { args };
+        let args = WriteArgs {
+            path,
+            no_clobber: false,
+        };
+        let writer = WriteCsvStep {
+            args,
+            options: CsvOptions::default(),
+        };
         let result = writer.execute(prev);
         assert!(result.is_ok());
         assert!(output_path.exists());
@@ -1,17 +1,49 @@
+pub mod table;
+
+use std::borrow::Cow;
 use std::io::Write;
 
+use arrow::array::Array;
+use arrow::array::AsArray;
 use arrow::array::RecordBatchReader;
+use arrow::datatypes::DataType;
+use arrow::datatypes::Float16Type;
+use arrow::datatypes::Float32Type;
+use arrow::datatypes::Float64Type;
+use arrow::datatypes::Int8Type;
+use arrow::datatypes::Int16Type;
+use arrow::datatypes::Int32Type;
+use arrow::datatypes::Int64Type;
+use arrow::datatypes::Schema;
+use arrow::datatypes::UInt8Type;
+use arrow::datatypes::UInt16Type;
+use arrow::datatypes::UInt32Type;
+use arrow::datatypes::UInt64Type;
 use arrow::record_batch::RecordBatch;
+use arrow::util::display::ArrayFormatter;
+use arrow::util::display::FormatOptions;
 use arrow_json::writer::JsonArray;
+use arrow_json::writer::LineDelimited;
 use arrow_json::writer::WriterBuilder;
+use saphyr::LoadableYamlNode;
+use saphyr::Scalar;
+use saphyr::ScalarStyle;
 use saphyr::Yaml;
 use saphyr::YamlEmitter;
+use serde::Serialize;
 
 use crate::Error;
 use crate::Result;
+use crate::cli::CsvOptions;
 use crate::cli::DisplayOutputFormat;
 use crate::pipeline::RecordBatchReaderSource;
 use crate::pipeline::Step;
+use crate::pipeline::csv::CsvWriter;
+use crate::pipeline::display::table::Align;
+use crate::pipeline::display::table::HtmlTableWriter;
+use crate::pipeline::display::table::MarkdownTableWriter;
+use crate::pipeline::display::table::Table;
+use crate::pipeline::display::table::TableOptions;
 
 /// Normalizes string values for YAML emission. Unicode line/paragraph separators (U+2028, U+2029)
 /// are replaced with newlines so that saphyr's emitter will quote and escape them, producing
@@ -21,46 +53,174 @@ fn normalize_yaml_string_value(s: String) -> String {
 }
 
 /// Converts a record batch into YAML row objects; when `sparse` is true, omits null values.
-fn record_batch_to_yaml_rows(batch: &RecordBatch, sparse: bool) -> Vec<Yaml<'static>> {
+fn record_batch_to_yaml_rows(batch: &RecordBatch, sparse: bool) -> Result<Vec<Yaml<'static>>> {
     let schema = batch.schema();
-    let num_rows = batch.num_rows();
-    (0..num_rows)
+    // Dictionary-encoded columns are decoded once, rather than looking up every value.
+    let columns = batch
+        .columns()
+        .iter()
+        .map(|column| match column.data_type() {
+            DataType::Dictionary(_, value_type) => {
+                arrow::compute::cast(column, value_type).map_err(Error::ArrowError)
+            }
+            _ => Ok(column.clone()),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok((0..batch.num_rows())
         .map(|row_idx| {
             let mut map = hashlink::LinkedHashMap::new();
-            for (col_idx, field) in schema.fields().iter().enumerate() {
-                let array = batch.column(col_idx);
+            for (field, array) in schema.fields().iter().zip(&columns) {
                 if sparse && array.is_null(row_idx) {
                     continue;
                 }
-                let col_name = field.name().clone();
-                let value_str =
-                    arrow::util::display::array_value_to_string(array.as_ref(), row_idx)
-                        .unwrap_or_else(|_| "-".to_string());
-                let value_str = normalize_yaml_string_value(value_str);
                 map.insert(
-                    Yaml::scalar_from_string(col_name),
-                    Yaml::scalar_from_string(value_str),
+                    yaml_string(field.name().clone()),
+                    array_value_to_yaml(array.as_ref(), row_idx, sparse),
                 );
             }
             Yaml::Mapping(map)
         })
-        .collect()
+        .collect())
+}
+
+/// Converts the value at `row` of an array into a typed YAML node, the way it would be
+/// written to JSON: numbers, booleans, and null as YAML scalars; dates and timestamps as ISO
+/// 8601 timestamps; structs and maps as mappings; and lists as sequences. Other values are
+/// strings. When `sparse` is true, null struct fields are omitted.
+fn array_value_to_yaml(array: &dyn Array, row: usize, sparse: bool) -> Yaml<'static> {
+    if array.is_null(row) {
+        return yaml_plain("null".to_string());
+    }
+    match array.data_type() {
+        DataType::Boolean => Yaml::Value(Scalar::Boolean(array.as_boolean().value(row))),
+        DataType::Int8 => yaml_integer(array.as_primitive::<Int8Type>().value(row)),
+        DataType::Int16 => yaml_integer(array.as_primitive::<Int16Type>().value(row)),
+        DataType::Int32 => yaml_integer(array.as_primitive::<Int32Type>().value(row)),
+        DataType::Int64 => yaml_integer(array.as_primitive::<Int64Type>().value(row)),
+        DataType::UInt8 => yaml_integer(array.as_primitive::<UInt8Type>().value(row)),
+        DataType::UInt16 => yaml_integer(array.as_primitive::<UInt16Type>().value(row)),
+        DataType::UInt32 => yaml_integer(array.as_primitive::<UInt32Type>().value(row)),
+        // May not fit in an i64, which is all saphyr's integers hold.
+        DataType::UInt64 => yaml_plain(array.as_primitive::<UInt64Type>().value(row).to_string()),
+        DataType::Float16 => {
+            let value = array.as_primitive::<Float16Type>().value(row);
+            yaml_float(value.to_f64(), format!("{:?}", value.to_f32()))
+        }
+        DataType::Float32 => {
+            let value = array.as_primitive::<Float32Type>().value(row);
+            yaml_float(value.into(), format!("{value:?}"))
+        }
+        DataType::Float64 => {
+            let value = array.as_primitive::<Float64Type>().value(row);
+            yaml_float(value, format!("{value:?}"))
+        }
+        DataType::Decimal32(_, _)
+        | DataType::Decimal64(_, _)
+        | DataType::Decimal128(_, _)
+        | DataType::Decimal256(_, _)
+        | DataType::Date32
+        | DataType::Date64
+        | DataType::Timestamp(_, _) => yaml_plain(display_value(array, row)),
+        DataType::Struct(fields) => {
+            let array = array.as_struct();
+            let mut map = hashlink::LinkedHashMap::new();
+            for (field, column) in fields.iter().zip(array.columns()) {
+                if sparse && column.is_null(row) {
+                    continue;
+                }
+                map.insert(
+                    yaml_string(field.name().clone()),
+                    array_value_to_yaml(column.as_ref(), row, sparse),
+                );
+            }
+            Yaml::Mapping(map)
+        }
+        DataType::List(_) => yaml_sequence(array.as_list::<i32>().value(row).as_ref(), sparse),
+        DataType::LargeList(_) => yaml_sequence(array.as_list::<i64>().value(row).as_ref(), sparse),
+        DataType::FixedSizeList(_, _) => {
+            yaml_sequence(array.as_fixed_size_list().value(row).as_ref(), sparse)
+        }
+        DataType::Map(_, _) => {
+            let entries = array.as_map().value(row);
+            let mut map = hashlink::LinkedHashMap::new();
+            for i in 0..entries.len() {
+                map.insert(
+                    array_value_to_yaml(entries.column(0).as_ref(), i, sparse),
+                    array_value_to_yaml(entries.column(1).as_ref(), i, sparse),
+                );
+            }
+            Yaml::Mapping(map)
+        }
+        _ => yaml_string(display_value(array, row)),
+    }
+}
+
+/// Formats a value with Arrow's display formatting, e.g. ISO 8601 for timestamps.
+fn display_value(array: &dyn Array, row: usize) -> String {
+    arrow::util::display::array_value_to_string(array, row).unwrap_or_else(|_| "-".to_string())
+}
+
+fn yaml_sequence(array: &dyn Array, sparse: bool) -> Yaml<'static> {
+    Yaml::Sequence(
+        (0..array.len())
+            .map(|i| array_value_to_yaml(array, i, sparse))
+            .collect(),
+    )
+}
+
+fn yaml_integer(value: impl Into<i64>) -> Yaml<'static> {
+    Yaml::Value(Scalar::Integer(value.into()))
+}
+
+/// A string scalar, which the emitter quotes if it would otherwise read as another type.
+fn yaml_string(value: String) -> Yaml<'static> {
+    Yaml::Value(Scalar::String(Cow::Owned(normalize_yaml_string_value(
+        value,
+    ))))
+}
+
+/// A scalar written exactly as given, for values whose text already has the intended type.
+fn yaml_plain(repr: String) -> Yaml<'static> {
+    Yaml::Representation(Cow::Owned(repr), ScalarStyle::Plain, None)
+}
+
+/// A float scalar from its value and its Rust `Debug` text, e.g. `1.0` or `1e-7`. saphyr writes whole
+/// floats without a fraction, so that they would read back as integers; and YAML 1.1 parsers
+/// need a fraction and a signed exponent, so `1e-7` is written as `1.0e-7`.
+fn yaml_float(value: f64, repr: String) -> Yaml<'static> {
+    let repr = if value.is_nan() {
+        ".nan".to_string()
+    } else if value.is_infinite() {
+        if value < 0.0 { "-.inf" } else { ".inf" }.to_string()
+    } else if let Some((mantissa, exponent)) = repr.split_once('e') {
+        let fraction = if mantissa.contains('.') { "" } else { ".0" };
+        let sign = if exponent.starts_with('-') { "" } else { "+" };
+        format!("{mantissa}{fraction}e{sign}{exponent}")
+    } else {
+        repr
+    };
+    yaml_plain(repr)
 }
 
-/// Write record batches from a reader to the given writer as CSV.
-pub fn write_record_batches_as_csv<W>(reader: &mut dyn RecordBatchReader, w: W) -> Result<()>
+/// Write record batches from a reader to the given writer as CSV in the given dialect.
+pub fn write_record_batches_as_csv<W>(
+    reader: &mut dyn RecordBatchReader,
+    w: W,
+    options: &CsvOptions,
+) -> Result<()>
 where
     W: Write,
 {
-    let mut writer = arrow::csv::Writer::new(w);
+    let mut writer = CsvWriter::try_new(w, options)?;
     for batch in reader {
         let batch = batch.map_err(Error::ArrowError)?;
-        writer.write(&batch).map_err(Error::ArrowError)?;
+        writer.write(&batch)?;
     }
-    Ok(())
+    writer.flush()
 }
 
-/// Write record batches from a reader to the given writer as JSON.
+/// Write record batches from a reader to the given writer as a JSON array of objects. Each
+/// batch is written as soon as it is read.
 pub fn write_record_batches_as_json<W>(
     reader: &mut dyn RecordBatchReader,
     w: W,
@@ -69,21 +229,19 @@ pub fn write_record_batches_as_json<W>(
 where
     W: Write,
 {
-    let batches: Vec<RecordBatch> = reader
-        .collect::<std::result::Result<Vec<_>, arrow::error::ArrowError>>()
-        .map_err(Error::ArrowError)?;
-    let batch_refs: Vec<&RecordBatch> = batches.iter().collect();
     let builder = WriterBuilder::new().with_explicit_nulls(!sparse);
     let mut writer = builder.build::<_, JsonArray>(w);
-    writer
-        .write_batches(&batch_refs)
-        .map_err(Error::ArrowError)?;
+    for batch in reader {
+        let batch = batch.map_err(Error::ArrowError)?;
+        writer.write(&batch).map_err(Error::ArrowError)?;
+    }
     writer.finish().map_err(Error::ArrowError)?;
     Ok(())
 }
 
-/// Write record batches from a reader to the given writer as pretty-formatted JSON.
-pub fn write_record_batches_as_json_pretty<W>(
+/// Write record batches from a reader to the given writer as JSON Lines, one object per
+/// row. Each batch is written as soon as it is read.
+pub fn write_record_batches_as_json_lines<W>(
     reader: &mut dyn RecordBatchReader,
     w: W,
     sparse: bool,
@@ -91,16 +249,125 @@ pub fn write_record_batches_as_json_pretty<W>(
 where
     W: Write,
 {
-    let mut buf = Vec::new();
-    write_record_batches_as_json(reader, &mut buf, sparse)?;
-    let value: serde_json::Value = serde_json::from_slice(&buf)
-        .map_err(|e| Error::GenericError(format!("Invalid JSON: {e}")))?;
-    serde_json::to_writer_pretty(w, &value)
-        .map_err(|e| Error::GenericError(format!("Failed to write JSON: {e}")))?;
+    let builder = WriterBuilder::new().with_explicit_nulls(!sparse);
+    let mut writer = builder.build::<_, LineDelimited>(w);
+    for batch in reader {
+        let batch = batch.map_err(Error::ArrowError)?;
+        writer.write(&batch).map_err(Error::ArrowError)?;
+    }
+    writer.finish().map_err(Error::ArrowError)?;
     Ok(())
 }
 
-/// Write record batches from a reader to the given writer as YAML.
+/// Write record batches from a reader to the given writer as pretty-formatted JSON. Rows are
+/// reformatted as they are written, so keys stay in column order.
+pub fn write_record_batches_as_json_pretty<W>(
+    reader: &mut dyn RecordBatchReader,
+    w: W,
+    sparse: bool,
+) -> Result<()>
+where
+    W: Write,
+{
+    write_record_batches_as_json(reader, PrettyJsonWriter::new(w), sparse)
+}
+
+/// A writer that indents compact JSON written to it, with the same layout as
+/// `serde_json::to_writer_pretty`, without holding more than one write in memory.
+struct PrettyJsonWriter<W: Write> {
+    inner: W,
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+    /// Whether an object or array was just opened, so that its newline is only written
+    /// once it turns out not to be empty.
+    opened: bool,
+    buf: Vec<u8>,
+}
+
+impl<W: Write> PrettyJsonWriter<W> {
+    fn new(inner: W) -> Self {
+        PrettyJsonWriter {
+            inner,
+            depth: 0,
+            in_string: false,
+            escaped: false,
+            opened: false,
+            buf: Vec::new(),
+        }
+    }
+
+    fn newline(&mut self) {
+        self.buf.push(b'\n');
+        self.buf
+            .extend(std::iter::repeat_n(b' ', PRETTY_JSON_INDENT * self.depth));
+    }
+}
+
+/// Number of spaces per level of pretty JSON indentation.
+const PRETTY_JSON_INDENT: usize = 2;
+
+impl<W: Write> Write for PrettyJsonWriter<W> {
+    fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+        self.buf.clear();
+        for &b in bytes {
+            if self.in_string {
+                self.buf.push(b);
+                if self.escaped {
+                    self.escaped = false;
+                } else if b == b'\\' {
+                    self.escaped = true;
+                } else if b == b'"' {
+                    self.in_string = false;
+                }
+                continue;
+            }
+            if b.is_ascii_whitespace() {
+                continue;
+            }
+            if self.opened {
+                self.opened = false;
+                if b == b'}' || b == b']' {
+                    self.depth -= 1;
+                    self.buf.push(b);
+                    continue;
+                }
+                self.newline();
+            }
+            match b {
+                b'{' | b'[' => {
+                    self.buf.push(b);
+                    self.depth += 1;
+                    self.opened = true;
+                }
+                b'}' | b']' => {
+                    self.depth = self.depth.saturating_sub(1);
+                    self.newline();
+                    self.buf.push(b);
+                }
+                b',' => {
+                    self.buf.push(b);
+                    self.newline();
+                }
+                b':' => self.buf.extend_from_slice(b": "),
+                b'"' => {
+                    self.buf.push(b);
+                    self.in_string = true;
+                }
+                _ => self.buf.push(b),
+            }
+        }
+        self.inner.write_all(&self.buf)?;
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Write record batches from a reader to the given writer as a YAML sequence of rows. Each
+/// batch is emitted as soon as it is read.
 pub fn write_record_batches_as_yaml<W>(
     reader: &mut dyn RecordBatchReader,
     mut w: W,
@@ -109,28 +376,214 @@ pub fn write_record_batches_as_yaml<W>(
 where
     W: Write,
 {
-    let batches: Vec<RecordBatch> = reader
-        .collect::<std::result::Result<Vec<_>, arrow::error::ArrowError>>()
-        .map_err(Error::ArrowError)?;
-    let yaml_rows: Vec<Yaml<'static>> = batches
+    let mut empty = true;
+    for batch in reader {
+        let batch = batch.map_err(Error::ArrowError)?;
+        if batch.num_rows() == 0 {
+            continue;
+        }
+        let doc = Yaml::Sequence(record_batch_to_yaml_rows(&batch, sparse)?);
+        let mut out = String::new();
+        let mut emitter = YamlEmitter::new(&mut out);
+        emitter
+            .dump(&doc)
+            .map_err(|e| Error::GenericError(format!("Failed to emit YAML: {e}")))?;
+        let to_write = out.strip_prefix("---\n").unwrap_or(&out);
+        if !empty {
+            writeln!(w)?;
+        }
+        write!(w, "{to_write}").map_err(|e| Error::GenericError(format!("Write failed: {e}")))?;
+        empty = false;
+    }
+    if empty {
+        write!(w, "[]").map_err(|e| Error::GenericError(format!("Write failed: {e}")))?;
+    }
+    Ok(())
+}
+
+/// Returns the column names of a schema and their alignments, with numbers right-aligned.
+fn table_header(schema: &Schema) -> (Vec<String>, Vec<Align>) {
+    let header = schema.fields().iter().map(|f| f.name().clone()).collect();
+    let align = schema
+        .fields()
         .iter()
-        .flat_map(|batch| record_batch_to_yaml_rows(batch, sparse))
+        .map(|f| {
+            if f.data_type().is_numeric() {
+                Align::Right
+            } else {
+                Align::Left
+            }
+        })
         .collect();
-    let doc = Yaml::Sequence(yaml_rows);
-    let mut out = String::new();
-    let mut emitter = YamlEmitter::new(&mut out);
-    emitter
-        .dump(&doc)
-        .map_err(|e| Error::GenericError(format!("Failed to emit YAML: {e}")))?;
-    let to_write = out.strip_prefix("---\n").unwrap_or(&out);
-    write!(w, "{to_write}").map_err(|e| Error::GenericError(format!("Write failed: {e}")))?;
+    (header, align)
+}
+
+/// Formats the rows of a batch as table cells, with nulls as `None`.
+fn batch_to_table_rows(batch: &RecordBatch) -> Result<Vec<Vec<Option<String>>>> {
+    let formatters = batch
+        .columns()
+        .iter()
+        .map(|c| ArrayFormatter::try_new(c.as_ref(), &FormatOptions::default()))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok((0..batch.num_rows())
+        .map(|row| {
+            batch
+                .columns()
+                .iter()
+                .zip(&formatters)
+                .map(|(c, f)| c.is_valid(row).then(|| f.value(row).to_string()))
+                .collect()
+        })
+        .collect())
+}
+
+/// Reads all record batches into a [`Table`], with numbers right-aligned.
+fn record_batches_to_table(reader: &mut dyn RecordBatchReader) -> Result<Table> {
+    let (header, align) = table_header(&reader.schema());
+    let mut table = Table::new(header, align);
+    for batch in reader {
+        let batch = batch.map_err(Error::ArrowError)?;
+        for row in batch_to_table_rows(&batch)? {
+            table.push_row(row);
+        }
+    }
+    Ok(table)
+}
+
+/// Write record batches from a reader to the given writer as a bordered text table, with
+/// numbers right-aligned and nulls shown as [`table::NULL_DISPLAY`].
+pub fn write_record_batches_as_table<W>(
+    reader: &mut dyn RecordBatchReader,
+    w: W,
+    options: &TableOptions,
+) -> Result<()>
+where
+    W: Write,
+{
+    record_batches_to_table(reader)?.write(w, options)?;
+    Ok(())
+}
+
+/// Write record batches from a reader to the given writer as a Markdown table. Rows are
+/// written as they are read; column widths come from the first rows.
+pub fn write_record_batches_as_markdown<W>(reader: &mut dyn RecordBatchReader, w: W) -> Result<()>
+where
+    W: Write,
+{
+    let (header, align) = table_header(&reader.schema());
+    let mut writer = MarkdownTableWriter::new(w, &header, &align);
+    for batch in reader {
+        let batch = batch.map_err(Error::ArrowError)?;
+        for row in batch_to_table_rows(&batch)? {
+            writer.write_row(&row)?;
+        }
+    }
+    writer.finish()?;
+    Ok(())
+}
+
+/// Write record batches from a reader to the given writer as a standalone HTML page with a
+/// sortable table. Rows are written as they are read.
+pub fn write_record_batches_as_html<W>(
+    reader: &mut dyn RecordBatchReader,
+    w: W,
+    title: &str,
+) -> Result<()>
+where
+    W: Write,
+{
+    let (header, align) = table_header(&reader.schema());
+    let mut writer = HtmlTableWriter::new(w, &header, &align, title)?;
+    for batch in reader {
+        let batch = batch.map_err(Error::ArrowError)?;
+        for row in batch_to_table_rows(&batch)? {
+            writer.write_row(&row)?;
+        }
+    }
+    writer.finish()?;
+    Ok(())
+}
+
+/// Write a serializable value (e.g. a metadata report) to the given writer as JSON,
+/// pretty-formatted JSON, or YAML. Field order follows the value's `Serialize` implementation.
+///
+/// CSV and the table formats have no generic representation for nested values, so callers are
+/// expected to render their own line-oriented text for [`DisplayOutputFormat::Csv`] and
+/// [`DisplayOutputFormat::Table`], and do not support Markdown or HTML.
+pub fn write_serializable<T, W>(
+    value: &T,
+    output_format: DisplayOutputFormat,
+    mut w: W,
+) -> Result<()>
+where
+    T: Serialize,
+    W: Write,
+{
+    match output_format {
+        DisplayOutputFormat::Csv
+        | DisplayOutputFormat::Table
+        | DisplayOutputFormat::Markdown
+        | DisplayOutputFormat::Html => {
+            return Err(Error::GenericError(format!(
+                "{output_format} output is not supported for this value"
+            )));
+        }
+        DisplayOutputFormat::Json => {
+            serde_json::to_writer(&mut w, value)
+                .map_err(|e| Error::GenericError(format!("Failed to write JSON: {e}")))?;
+        }
+        DisplayOutputFormat::JsonPretty => {
+            serde_json::to_writer_pretty(&mut w, value)
+                .map_err(|e| Error::GenericError(format!("Failed to write JSON: {e}")))?;
+        }
+        DisplayOutputFormat::JsonLines => {
+            // A list is written one element per line; any other value on a line of its own.
+            let json = serde_json::to_value(value)
+                .map_err(|e| Error::GenericError(format!("Failed to serialize value: {e}")))?;
+            let lines = match json {
+                serde_json::Value::Array(elements) => elements,
+                other => vec![other],
+            };
+            if lines.is_empty() {
+                return Ok(());
+            }
+            for (i, line) in lines.iter().enumerate() {
+                if i > 0 {
+                    writeln!(w)?;
+                }
+                serde_json::to_writer(&mut w, line)
+                    .map_err(|e| Error::GenericError(format!("Failed to write JSON: {e}")))?;
+            }
+        }
+        DisplayOutputFormat::Yaml => {
+            // JSON is valid YAML, and saphyr's loader keeps mapping keys in document order.
+            let json = serde_json::to_string(value)
+                .map_err(|e| Error::GenericError(format!("Failed to serialize value: {e}")))?;
+            let docs = Yaml::load_from_str(&json)
+                .map_err(|e| Error::GenericError(format!("Failed to convert to YAML: {e}")))?;
+            let mut out = String::new();
+            let mut emitter = YamlEmitter::new(&mut out);
+            for doc in &docs {
+                emitter
+                    .dump(doc)
+                    .map_err(|e| Error::GenericError(format!("Failed to emit YAML: {e}")))?;
+            }
+            let to_write = out.strip_prefix("---\n").unwrap_or(&out);
+            write!(w, "{to_write}")?;
+        }
+    }
+    writeln!(w)?;
     Ok(())
 }
 
-/// Pipeline step that writes record batches to stdout as CSV or JSON.
+/// Pipeline step that writes record batches to stdout in a display format.
 pub struct DisplayWriterStep {
     pub output_format: DisplayOutputFormat,
     pub sparse: bool,
+    /// For [`DisplayOutputFormat::Table`]: the maximum width of a column.
+    pub max_col_width: Option<usize>,
+    /// For [`DisplayOutputFormat::Csv`]: the CSV dialect.
+    pub csv: CsvOptions,
 }
 
 impl Step for DisplayWriterStep {
@@ -141,7 +594,7 @@ impl Step for DisplayWriterStep {
         let mut reader = input.get()?;
         match self.output_format {
             DisplayOutputFormat::Csv => {
-                write_record_batches_as_csv(&mut *reader, std::io::stdout())?;
+                write_record_batches_as_csv(&mut *reader, std::io::stdout(), &self.csv)?;
             }
             DisplayOutputFormat::Json => {
                 write_record_batches_as_json(&mut *reader, std::io::stdout(), self.sparse)?;
@@ -149,9 +602,22 @@ impl Step for DisplayWriterStep {
             DisplayOutputFormat::JsonPretty => {
                 write_record_batches_as_json_pretty(&mut *reader, std::io::stdout(), self.sparse)?;
             }
+            DisplayOutputFormat::JsonLines => {
+                write_record_batches_as_json_lines(&mut *reader, std::io::stdout(), self.sparse)?;
+            }
             DisplayOutputFormat::Yaml => {
                 write_record_batches_as_yaml(&mut *reader, std::io::stdout(), self.sparse)?;
             }
+            DisplayOutputFormat::Table => {
+                let options = TableOptions::for_stdout(self.max_col_width);
+                write_record_batches_as_table(&mut *reader, std::io::stdout(), &options)?;
+            }
+            DisplayOutputFormat::Markdown => {
+                write_record_batches_as_markdown(&mut *reader, std::io::stdout())?;
+            }
+            DisplayOutputFormat::Html => {
+                write_record_batches_as_html(&mut *reader, std::io::stdout(), "datu")?;
+            }
         }
         Ok(())
     }
@@ -159,21 +625,43 @@ impl Step for DisplayWriterStep {
 
 #[cfg(test)]
 mod tests {
+    use std::io::Write;
     use std::sync::Arc;
 
+    use arrow::array::ArrayRef;
+    use arrow::array::BooleanArray;
+    use arrow::array::Date32Array;
+    use arrow::array::Float32Array;
+    use arrow::array::Float64Array;
+    use arrow::array::Float64Builder;
     use arrow::array::Int32Array;
+    use arrow::array::Int64Array;
+    use arrow::array::ListBuilder;
+    use arrow::array::MapBuilder;
     use arrow::array::StringArray;
+    use arrow::array::StringBuilder;
+    use arrow::array::StructArray;
+    use arrow::array::TimestampMillisecondArray;
     use arrow::datatypes::DataType;
     use arrow::datatypes::Field;
     use arrow::datatypes::Schema;
     use arrow::record_batch::RecordBatch;
 
+    use super::PrettyJsonWriter;
     use super::write_record_batches_as_csv;
+    use super::write_record_batches_as_html;
     use super::write_record_batches_as_json;
+    use super::write_record_batches_as_json_lines;
     use super::write_record_batches_as_json_pretty;
+    use super::write_record_batches_as_markdown;
+    use super::write_record_batches_as_table;
     use super::write_record_batches_as_yaml;
+    use crate::cli::CsvOptions;
+    use crate::cli::CsvQuoteStyle;
+    use crate::cli::LineTerminator;
     use crate::pipeline::Source;
     use crate::pipeline::VecRecordBatchReaderSource;
+    use crate::pipeline::display::table::TableOptions;
 
     fn make_test_batch() -> RecordBatch {
         let schema = Schema::new(vec![
@@ -196,13 +684,33 @@ mod tests {
         let mut source = VecRecordBatchReaderSource::new(vec![batch]);
         let mut reader = source.get().unwrap();
         let mut out = Vec::new();
-        write_record_batches_as_csv(&mut *reader, &mut out).unwrap();
+        write_record_batches_as_csv(&mut *reader, &mut out, &CsvOptions::default()).unwrap();
         let s = String::from_utf8(out).unwrap();
         assert!(s.contains("id,name"));
         assert!(s.contains("1,alice"));
         assert!(s.contains("2,bob"));
     }
 
+    #[test]
+    fn test_write_record_batches_as_csv_with_dialect() {
+        let batch = make_test_batch();
+        let mut source = VecRecordBatchReaderSource::new(vec![batch]);
+        let mut reader = source.get().unwrap();
+        let mut out = Vec::new();
+        let options = CsvOptions {
+            delimiter: Some(b'|'),
+            quote_style: CsvQuoteStyle::NonNumeric,
+            line_terminator: LineTerminator::Crlf,
+            bom: true,
+            ..Default::default()
+        };
+        write_record_batches_as_csv(&mut *reader, &mut out, &options).unwrap();
+        assert_eq!(
+            out,
+            b"\xEF\xBB\xBF\"id\"|\"name\"\r\n1|\"alice\"\r\n2|\"bob\"\r\n"
+        );
+    }
+
     #[test]
     fn test_write_record_batches_as_json() {
         let batch = make_test_batch();
@@ -218,6 +726,20 @@ mod tests {
         assert!(s.contains("bob"));
     }
 
+    #[test]
+    fn test_write_record_batches_as_json_lines() {
+        let batch = make_test_batch();
+        let mut source = VecRecordBatchReaderSource::new(vec![batch.clone(), batch]);
+        let mut reader = source.get().unwrap();
+        let mut out = Vec::new();
+        write_record_batches_as_json_lines(&mut *reader, &mut out, true).unwrap();
+        let s = String::from_utf8(out).unwrap();
+        assert_eq!(
+            s,
+            "{\"id\":1,\"name\":\"alice\"}\n{\"id\":2,\"name\":\"bob\"}\n".repeat(2)
+        );
+    }
+
     #[test]
     fn test_write_record_batches_as_json_pretty() {
         let batch = make_test_batch();
@@ -234,6 +756,51 @@ mod tests {
         assert!(s.contains('\n'), "pretty output should contain newlines");
     }
 
+    #[test]
+    fn test_pretty_json_writer_matches_serde_json() {
+        let value = serde_json::json!([
+            {"id": 1, "tags": [], "attrs": {}, "text": "a, \"b\": [c] {d}\\"},
+            {"id": 2, "nested": {"list": [1, [2, 3], {"k": null}]}},
+        ]);
+        let compact = serde_json::to_string(&value).unwrap();
+        let mut out = Vec::new();
+        let mut writer = PrettyJsonWriter::new(&mut out);
+        // Split the input mid-string and mid-escape to check that state carries over.
+        for chunk in compact.as_bytes().chunks(7) {
+            writer.write_all(chunk).unwrap();
+        }
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            serde_json::to_string_pretty(&value).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_write_record_batches_as_json_pretty_across_batches() {
+        let batch = make_test_batch();
+        let mut source = VecRecordBatchReaderSource::new(vec![batch.clone(), batch]);
+        let mut reader = source.get().unwrap();
+        let mut out = Vec::new();
+        write_record_batches_as_json_pretty(&mut *reader, &mut out, true).unwrap();
+        let row = |id, name| format!("  {{\n    \"id\": {id},\n    \"name\": \"{name}\"\n  }}");
+        let rows = [
+            row(1, "alice"),
+            row(2, "bob"),
+            row(1, "alice"),
+            row(2, "bob"),
+        ];
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            format!("[\n{}\n]", rows.join(",\n"))
+        );
+
+        let mut source = VecRecordBatchReaderSource::new(vec![]);
+        let mut reader = source.get().unwrap();
+        let mut out = Vec::new();
+        write_record_batches_as_json_pretty(&mut *reader, &mut out, true).unwrap();
+        assert_eq!(out, b"[]");
+    }
+
     #[test]
     fn test_write_record_batches_as_yaml() {
         let batch = make_test_batch();
@@ -252,4 +819,162 @@ mod tests {
         assert!(s.contains("alice"));
         assert!(s.contains("bob"));
     }
+
+    #[test]
+    fn test_write_record_batches_as_yaml_typed_values() {
+        let address = StructArray::from(vec![
+            (
+                Arc::new(Field::new("city", DataType::Utf8, true)),
+                Arc::new(StringArray::from(vec![Some("Oslo"), None])) as ArrayRef,
+            ),
+            (
+                Arc::new(Field::new("zip", DataType::Int32, true)),
+                Arc::new(Int32Array::from(vec![Some(150), Some(7)])) as ArrayRef,
+            ),
+        ]);
+        let mut tags = ListBuilder::new(StringBuilder::new());
+        tags.append_value([Some("a"), Some("true")]);
+        tags.append_value([None::<&str>; 0]);
+        let mut scores = MapBuilder::new(None, StringBuilder::new(), Float64Builder::new());
+        scores.keys().append_value("x");
+        scores.values().append_value(1.0);
+        scores.append(true).unwrap();
+        scores.append(false).unwrap();
+        let batch = RecordBatch::try_from_iter(vec![
+            ("id", Arc::new(Int64Array::from(vec![1, 2])) as ArrayRef),
+            (
+                "code",
+                Arc::new(StringArray::from(vec!["007", "null"])) as ArrayRef,
+            ),
+            (
+                "ok",
+                Arc::new(BooleanArray::from(vec![true, false])) as ArrayRef,
+            ),
+            (
+                "ratio",
+                Arc::new(Float64Array::from(vec![1.0, 1e-7])) as ArrayRef,
+            ),
+            (
+                "weight",
+                Arc::new(Float32Array::from(vec![f32::NAN, f32::NEG_INFINITY])) as ArrayRef,
+            ),
+            (
+                "day",
+                Arc::new(Date32Array::from(vec![Some(19349), None])) as ArrayRef,
+            ),
+            (
+                "seen",
+                Arc::new(
+                    TimestampMillisecondArray::from(vec![1_671_795_829_000, 0])
+                        .with_timezone("UTC"),
+                ) as ArrayRef,
+            ),
+            ("address", Arc::new(address) as ArrayRef),
+            ("tags", Arc::new(tags.finish()) as ArrayRef),
+            ("scores", Arc::new(scores.finish()) as ArrayRef),
+        ])
+        .unwrap();
+
+        let mut source = VecRecordBatchReaderSource::new(vec![batch.clone()]);
+        let mut reader = source.get().unwrap();
+        let mut out = Vec::new();
+        write_record_batches_as_yaml(&mut *reader, &mut out, true).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            r#"- id: 1
+  code: "007"
+  ok: true
+  ratio: 1.0
+  weight: .nan
+  day: 2022-12-23
+  seen: 2022-12-23T11:43:49Z
+  address:
+    city: Oslo
+    zip: 150
+  tags:
+    - a
+    - "true"
+  scores:
+    x: 1.0
+- id: 2
+  code: "null"
+  ok: false
+  ratio: 1.0e-7
+  weight: -.inf
+  seen: 1970-01-01T00:00:00Z
+  address:
+    zip: 7
+  tags: []"#
+        );
+
+        let mut source = VecRecordBatchReaderSource::new(vec![batch.slice(1, 1)]);
+        let mut reader = source.get().unwrap();
+        let mut out = Vec::new();
+        write_record_batches_as_yaml(&mut *reader, &mut out, false).unwrap();
+        let s = String::from_utf8(out).unwrap();
+        assert!(s.contains("  day: null\n"), "{s}");
+        assert!(s.contains("    city: null\n"), "{s}");
+        assert!(s.contains("  scores: null"), "{s}");
+    }
+
+    #[test]
+    fn test_write_record_batches_as_yaml_across_batches() {
+        let batch = make_test_batch();
+        let mut source =
+            VecRecordBatchReaderSource::new(vec![batch.slice(0, 1), batch.slice(1, 1)]);
+        let mut reader = source.get().unwrap();
+        let mut streamed = Vec::new();
+        write_record_batches_as_yaml(&mut *reader, &mut streamed, true).unwrap();
+
+        let mut source = VecRecordBatchReaderSource::new(vec![batch]);
+        let mut reader = source.get().unwrap();
+        let mut whole = Vec::new();
+        write_record_batches_as_yaml(&mut *reader, &mut whole, true).unwrap();
+        assert_eq!(
+            String::from_utf8(streamed).unwrap(),
+            String::from_utf8(whole).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_write_record_batches_as_table() {
+        let batch = make_test_batch();
+        let mut source = VecRecordBatchReaderSource::new(vec![batch]);
+        let mut reader = source.get().unwrap();
+        let mut out = Vec::new();
+        write_record_batches_as_table(&mut *reader, &mut out, &TableOptions::default()).unwrap();
+        let s = String::from_utf8(out).unwrap();
+        assert!(s.contains("│ id │ name  │"));
+        assert!(s.contains("│  1 │ alice │"));
+        assert!(s.contains("│  2 │ bob   │"));
+    }
+
+    #[test]
+    fn test_write_record_batches_as_markdown() {
+        let batch = make_test_batch();
+        let mut source = VecRecordBatchReaderSource::new(vec![batch]);
+        let mut reader = source.get().unwrap();
+        let mut out = Vec::new();
+        write_record_batches_as_markdown(&mut *reader, &mut out).unwrap();
+        let s = String::from_utf8(out).unwrap();
+        assert_eq!(
+            s,
+            "|   id | name  |\n| ---: | ----- |\n|    1 | alice |\n|    2 | bob   |\n"
+        );
+    }
+
+    #[test]
+    fn test_write_record_batches_as_html() {
+        let batch = make_test_batch();
+        let mut source = VecRecordBatchReaderSource::new(vec![batch]);
+        let mut reader = source.get().unwrap();
+        let mut out = Vec::new();
+        write_record_batches_as_html(&mut *reader, &mut out, "users <test>").unwrap();
+        let s = String::from_utf8(out).unwrap();
+        assert!(s.starts_with("<!DOCTYPE html>"));
+        assert!(s.contains("<title>users &lt;test&gt;</title>"));
+        assert!(s.contains("<tr><th class=\"num\">id</th><th>name</th></tr>"));
+        assert!(s.contains("<tr><td class=\"num\">1</td><td>alice</td></tr>"));
+        assert!(s.trim_end().ends_with("</html>"));
+    }
 }
@@ -0,0 +1,593 @@
+//! Renders rows as a table: a bordered text table for the terminal, a Markdown table, or a
+//! standalone HTML page.
+
+use std::io::IsTerminal;
+use std::io::Write;
+
+use unicode_width::UnicodeWidthChar;
+use unicode_width::UnicodeWidthStr;
+
+/// How null values are shown, so they cannot be mistaken for empty strings, nor for the
+/// string `NULL`.
+pub const NULL_DISPLAY: &str = "∅";
+
+/// Marks a cell that was cut short to fit its column.
+const ELLIPSIS: char = '…';
+
+/// Columns are never narrowed below this width (or their own width, if smaller) to fit the
+/// terminal; a table that still does not fit overflows instead.
+const MIN_COL_WIDTH: usize = 5;
+
+/// Horizontal alignment of a column's values.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+/// Limits on the width of a rendered table.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TableOptions {
+    /// Maximum width of any column; longer values are truncated with an ellipsis.
+    pub max_col_width: Option<usize>,
+    /// Maximum width of the whole table; the widest columns are narrowed to fit.
+    pub max_width: Option<usize>,
+}
+
+impl TableOptions {
+    /// Options for a table printed to stdout: as wide as the terminal (or `$COLUMNS`), and not
+    /// limited when stdout is not a terminal.
+    pub fn for_stdout(max_col_width: Option<usize>) -> Self {
+        let max_width = std::env::var("COLUMNS")
+            .ok()
+            .and_then(|c| c.parse().ok())
+            .or_else(|| {
+                if std::io::stdout().is_terminal() {
+                    ratatui::crossterm::terminal::size()
+                        .ok()
+                        .map(|(w, _)| w as usize)
+                } else {
+                    None
+                }
+            });
+        TableOptions {
+            max_col_width,
+            max_width,
+        }
+    }
+}
+
+/// A table of text cells with a header row. Null cells are `None`.
+pub struct Table {
+    header: Vec<String>,
+    align: Vec<Align>,
+    rows: Vec<Vec<Option<String>>>,
+}
+
+impl Table {
+    /// Creates an empty table with the given column names and alignments.
+    pub fn new(header: Vec<String>, align: Vec<Align>) -> Self {
+        Table {
+            header,
+            align,
+            rows: Vec::new(),
+        }
+    }
+
+    /// Appends a row, which must have one cell per column.
+    pub fn push_row(&mut self, row: Vec<Option<String>>) {
+        debug_assert_eq!(row.len(), self.header.len());
+        self.rows.push(row);
+    }
+
+    /// Writes the table, fitting it to the widths in `options`.
+    pub fn write<W: Write>(&self, mut w: W, options: &TableOptions) -> std::io::Result<()> {
+        if self.header.is_empty() {
+            return Ok(());
+        }
+        let header: Vec<String> = self.header.iter().map(|h| escape(h)).collect();
+        let rows: Vec<Vec<String>> = self
+            .rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| cell.as_deref().map_or(NULL_DISPLAY.to_string(), escape))
+                    .collect()
+            })
+            .collect();
+        let widths = self.column_widths(&header, &rows, options);
+
+        let border = |left: &str, mid: &str, right: &str| {
+            let lines: Vec<String> = widths.iter().map(|&n| "─".repeat(n + 2)).collect();
+            format!("{left}{}{right}", lines.join(mid))
+        };
+        writeln!(w, "{}", border("┌", "┬", "┐"))?;
+        self.write_row(&mut w, &header, &widths, true)?;
+        writeln!(w, "{}", border("├", "┼", "┤"))?;
+        for row in &rows {
+            self.write_row(&mut w, row, &widths, false)?;
+        }
+        writeln!(w, "{}", border("└", "┴", "┘"))?;
+        Ok(())
+    }
+
+    /// Returns the width of each column: its widest cell, capped at `max_col_width`, with the
+    /// widest columns then narrowed until the table fits `max_width`.
+    fn column_widths(
+        &self,
+        header: &[String],
+        rows: &[Vec<String>],
+        options: &TableOptions,
+    ) -> Vec<usize> {
+        let mut widths: Vec<usize> = header.iter().map(|h| h.width()).collect();
+        for row in rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.width());
+            }
+        }
+        if let Some(max) = options.max_col_width {
+            widths.iter_mut().for_each(|w| *w = (*w).min(max.max(1)));
+        }
+        if let Some(max_width) = options.max_width {
+            // Each column takes its width plus "│ " and " ", and the table one closing "│".
+            let mut total: usize = widths.iter().map(|w| w + 3).sum::<usize>() + 1;
+            while total > max_width {
+                let Some(widest) = (0..widths.len()).max_by_key(|&i| widths[i]) else {
+                    break;
+                };
+                if widths[widest] <= MIN_COL_WIDTH {
+                    break;
+                }
+                widths[widest] -= 1;
+                total -= 1;
+            }
+        }
+        widths
+    }
+
+    fn write_row<W: Write>(
+        &self,
+        w: &mut W,
+        cells: &[String],
+        widths: &[usize],
+        is_header: bool,
+    ) -> std::io::Result<()> {
+        let mut line = String::from("│");
+        for ((cell, &width), align) in cells.iter().zip(widths).zip(&self.align) {
+            let text = truncate(cell, width);
+            let padding = " ".repeat(width - text.width());
+            line.push(' ');
+            if *align == Align::Right && !is_header {
+                line.push_str(&padding);
+                line.push_str(&text);
+            } else {
+                line.push_str(&text);
+                line.push_str(&padding);
+            }
+            line.push_str(" │");
+        }
+        writeln!(w, "{line}")
+    }
+}
+
+impl Table {
+    /// Writes the table as a GitHub-flavored Markdown table, with columns padded to line up.
+    pub fn write_markdown<W: Write>(&self, w: W) -> std::io::Result<()> {
+        let mut writer = MarkdownTableWriter::new(w, &self.header, &self.align);
+        for row in &self.rows {
+            writer.write_row(row)?;
+        }
+        writer.finish()
+    }
+
+    /// Writes the table as a standalone HTML page titled `title`. Clicking a column header
+    /// sorts the rows by that column.
+    pub fn write_html<W: Write>(&self, w: W, title: &str) -> std::io::Result<()> {
+        let mut writer = HtmlTableWriter::new(w, &self.header, &self.align, title)?;
+        for row in &self.rows {
+            writer.write_row(row)?;
+        }
+        writer.finish()
+    }
+}
+
+/// Number of rows, after the header, that the column widths of a Markdown table are computed
+/// from. Wider cells in later rows are written unpadded, which renders the same.
+const MARKDOWN_SAMPLE_ROWS: usize = 1000;
+
+/// Writes a GitHub-flavored Markdown table row by row. Columns are padded to line up, to the
+/// widths of the header and the first [`MARKDOWN_SAMPLE_ROWS`] rows, so that only those rows
+/// are held in memory.
+pub struct MarkdownTableWriter<W: Write> {
+    w: W,
+    align: Vec<Align>,
+    /// Escaped cells of the header and the rows read while the widths are not yet known.
+    sample: Vec<Vec<String>>,
+    /// Column widths, once the sample has been written.
+    widths: Option<Vec<usize>>,
+}
+
+impl<W: Write> MarkdownTableWriter<W> {
+    /// Creates a writer for a table with the given column names and alignments. Nothing is
+    /// written for a table without columns.
+    pub fn new(w: W, header: &[String], align: &[Align]) -> Self {
+        MarkdownTableWriter {
+            w,
+            align: align.to_vec(),
+            sample: vec![header.iter().map(|h| escape_markdown(h)).collect()],
+            widths: None,
+        }
+    }
+
+    /// Writes a row, which must have one cell per column. Null cells are `None`.
+    pub fn write_row(&mut self, row: &[Option<String>]) -> std::io::Result<()> {
+        let cells: Vec<String> = row
+            .iter()
+            .map(|cell| {
+                cell.as_deref()
+                    .map_or(NULL_DISPLAY.to_string(), escape_markdown)
+            })
+            .collect();
+        match &self.widths {
+            Some(widths) => {
+                let line = markdown_line(&cells, widths, &self.align);
+                writeln!(self.w, "{line}")
+            }
+            None => {
+                self.sample.push(cells);
+                if self.sample.len() > MARKDOWN_SAMPLE_ROWS {
+                    self.write_sample()?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Writes any rows still held and flushes the writer.
+    pub fn finish(mut self) -> std::io::Result<()> {
+        if self.widths.is_none() {
+            self.write_sample()?;
+        }
+        self.w.flush()
+    }
+
+    fn write_sample(&mut self) -> std::io::Result<()> {
+        let rows = std::mem::take(&mut self.sample);
+        let Some(header) = rows.first() else {
+            return Ok(());
+        };
+        // Separators need at least three dashes, plus a colon for right-aligned columns.
+        let mut widths: Vec<usize> = header.iter().map(|h| h.width().max(4)).collect();
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.width());
+            }
+        }
+        if !widths.is_empty() {
+            writeln!(self.w, "{}", markdown_line(header, &widths, &self.align))?;
+            let separators: Vec<String> = widths
+                .iter()
+                .zip(&self.align)
+                .map(|(&width, align)| match align {
+                    Align::Left => "-".repeat(width),
+                    Align::Right => format!("{}:", "-".repeat(width - 1)),
+                })
+                .collect();
+            writeln!(self.w, "| {} |", separators.join(" | "))?;
+            for row in &rows[1..] {
+                writeln!(self.w, "{}", markdown_line(row, &widths, &self.align))?;
+            }
+        }
+        self.widths = Some(widths);
+        Ok(())
+    }
+}
+
+/// Formats a Markdown table row, padding each cell to its column's width.
+fn markdown_line(cells: &[String], widths: &[usize], align: &[Align]) -> String {
+    let cells: Vec<String> = cells
+        .iter()
+        .zip(widths)
+        .zip(align)
+        .map(|((cell, &width), align)| {
+            let padding = " ".repeat(width.saturating_sub(cell.width()));
+            match align {
+                Align::Left => format!("{cell}{padding}"),
+                Align::Right => format!("{padding}{cell}"),
+            }
+        })
+        .collect();
+    format!("| {} |", cells.join(" | "))
+}
+
+/// Writes a standalone HTML page with a sortable table row by row: the page up to the table
+/// header when created, each row as it is written, and the rest of the page when finished.
+pub struct HtmlTableWriter<W: Write> {
+    w: W,
+    align: Vec<Align>,
+    rows: usize,
+}
+
+impl<W: Write> HtmlTableWriter<W> {
+    /// Creates a writer for a page titled `title`, with a table with the given column names
+    /// and alignments, and writes the start of the page.
+    pub fn new(mut w: W, header: &[String], align: &[Align], title: &str) -> std::io::Result<Self> {
+        let title = escape_html(title);
+        writeln!(w, "<!DOCTYPE html>")?;
+        writeln!(w, "<html lang=\"en\">")?;
+        writeln!(w, "<head>")?;
+        writeln!(w, "<meta charset=\"utf-8\">")?;
+        writeln!(w, "<title>{title}</title>")?;
+        writeln!(w, "<style>{HTML_STYLE}</style>")?;
+        writeln!(w, "</head>")?;
+        writeln!(w, "<body>")?;
+        writeln!(w, "<h1>{title}</h1>")?;
+        writeln!(w, "<table>")?;
+        writeln!(w, "<thead>")?;
+        write!(w, "<tr>")?;
+        for (name, align) in header.iter().zip(align) {
+            let class = match align {
+                Align::Left => "",
+                Align::Right => " class=\"num\"",
+            };
+            write!(w, "<th{class}>{}</th>", escape_html(name))?;
+        }
+        writeln!(w, "</tr>")?;
+        writeln!(w, "</thead>")?;
+        writeln!(w, "<tbody>")?;
+        Ok(HtmlTableWriter {
+            w,
+            align: align.to_vec(),
+            rows: 0,
+        })
+    }
+
+    /// Writes a row, which must have one cell per column. Null cells are `None`.
+    pub fn write_row(&mut self, row: &[Option<String>]) -> std::io::Result<()> {
+        let w = &mut self.w;
+        write!(w, "<tr>")?;
+        for (cell, align) in row.iter().zip(&self.align) {
+            match (cell, align) {
+                (None, _) => write!(w, "<td class=\"null\">{NULL_DISPLAY}</td>")?,
+                (Some(cell), Align::Left) => write!(w, "<td>{}</td>", escape_html(cell))?,
+                (Some(cell), Align::Right) => {
+                    write!(w, "<td class=\"num\">{}</td>", escape_html(cell))?
+                }
+            }
+        }
+        writeln!(w, "</tr>")?;
+        self.rows += 1;
+        Ok(())
+    }
+
+    /// Writes the end of the page, with the number of rows, and flushes the writer.
+    pub fn finish(mut self) -> std::io::Result<()> {
+        let w = &mut self.w;
+        writeln!(w, "</tbody>")?;
+        writeln!(w, "</table>")?;
+        let rows = self.rows;
+        let plural = if rows == 1 { "" } else { "s" };
+        writeln!(w, "<p class=\"rows\">{rows} row{plural}</p>")?;
+        writeln!(w, "<script>{HTML_SCRIPT}</script>")?;
+        writeln!(w, "</body>")?;
+        writeln!(w, "</html>")?;
+        w.flush()
+    }
+}
+
+const HTML_STYLE: &str = "
+body { font-family: system-ui, sans-serif; margin: 2rem; color: #1f2328; }
+h1 { font-size: 1.25rem; }
+table { border-collapse: collapse; font-size: 0.875rem; }
+th, td { border: 1px solid #d0d7de; padding: 0.25rem 0.5rem; text-align: left; vertical-align: top; white-space: pre-wrap; }
+th { background: #f6f8fa; cursor: pointer; position: sticky; top: 0; user-select: none; }
+th[data-order=asc]::after { content: \" \\25B2\"; }
+th[data-order=desc]::after { content: \" \\25BC\"; }
+tbody tr:nth-child(even) { background: #f6f8fa; }
+.num { text-align: right; font-variant-numeric: tabular-nums; }
+.null { color: #8c959f; font-style: italic; }
+.rows { color: #57606a; font-size: 0.875rem; }
+";
+
+/// Sorts the rows by the clicked column, numerically for numeric columns, with nulls last.
+const HTML_SCRIPT: &str = "
+document.querySelectorAll('th').forEach((th, i) => th.addEventListener('click', () => {
+  const tbody = th.closest('table').tBodies[0];
+  const asc = th.dataset.order !== 'asc';
+  th.parentElement.querySelectorAll('th').forEach(h => delete h.dataset.order);
+  th.dataset.order = asc ? 'asc' : 'desc';
+  const numeric = th.classList.contains('num');
+  const key = row => {
+    const cell = row.cells[i];
+    if (cell.classList.contains('null')) return null;
+    return numeric ? parseFloat(cell.textContent) : cell.textContent;
+  };
+  const rows = Array.from(tbody.rows).sort((a, b) => {
+    const x = key(a), y = key(b);
+    if (x === null || y === null) return (x === null) - (y === null);
+    const order = numeric ? x - y : x.localeCompare(y, undefined, { numeric: true });
+    return asc ? order : -order;
+  });
+  tbody.append(...rows);
+}));
+";
+
+/// Escapes the characters that would end a Markdown table cell or row.
+fn escape_markdown(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '|' => out.push_str("\\|"),
+            '\n' => out.push_str("<br>"),
+            '\r' => {}
+            c if c.is_control() => out.extend(c.escape_unicode()),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Escapes the characters that are special in HTML text and attributes.
+fn escape_html(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Replaces control characters, which would break the table's lines, with escapes.
+fn escape(s: &str) -> String {
+    if !s.chars().any(char::is_control) {
+        return s.to_string();
+    }
+    s.chars()
+        .map(|c| match c {
+            '\n' => "\\n".to_string(),
+            '\r' => "\\r".to_string(),
+            '\t' => "\\t".to_string(),
+            c if c.is_control() => c.escape_unicode().to_string(),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+/// Shortens `s` to at most `width` columns, ending it with an ellipsis if it was cut.
+fn truncate(s: &str, width: usize) -> String {
+    if s.width() <= width {
+        return s.to_string();
+    }
+    let mut out = String::new();
+    let mut used = 0;
+    for c in s.chars() {
+        let w = c.width().unwrap_or(0);
+        if used + w + 1 > width {
+            break;
+        }
+        out.push(c);
+        used += w;
+    }
+    out.push(ELLIPSIS);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(table: &Table, options: TableOptions) -> String {
+        let mut out = Vec::new();
+        table.write(&mut out, &options).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    fn table() -> Table {
+        let mut table = Table::new(
+            vec!["id".to_string(), "name".to_string()],
+            vec![Align::Right, Align::Left],
+        );
+        table.push_row(vec![
+            Some("1".to_string()),
+            Some("Alice Anderson".to_string()),
+        ]);
+        table.push_row(vec![Some("100".to_string()), None]);
+        table
+    }
+
+    #[test]
+    fn test_table_layout() {
+        assert_eq!(
+            render(&table(), TableOptions::default()),
+            "┌─────┬────────────────┐\n\
+             │ id  │ name           │\n\
+             ├─────┼────────────────┤\n\
+             │   1 │ Alice Anderson │\n\
+             │ 100 │ ∅              │\n\
+             └─────┴────────────────┘\n"
+        );
+    }
+
+    #[test]
+    fn test_table_max_col_width() {
+        let options = TableOptions {
+            max_col_width: Some(8),
+            max_width: None,
+        };
+        assert!(render(&table(), options).contains("│   1 │ Alice A… │\n"));
+    }
+
+    #[test]
+    fn test_table_max_width() {
+        let options = TableOptions {
+            max_col_width: None,
+            max_width: Some(19),
+        };
+        let out = render(&table(), options);
+        assert!(out.contains("│   1 │ Alice An… │\n"));
+        assert!(out.lines().all(|line| line.width() <= 19));
+    }
+
+    #[test]
+    fn test_escape_and_truncate() {
+        assert_eq!(escape("a\nb"), "a\\nb");
+        assert_eq!(truncate("日本語テキスト", 7), "日本語…");
+        assert_eq!(truncate("short", 5), "short");
+    }
+
+    #[test]
+    fn test_null_and_null_string() {
+        let mut table = Table::new(vec!["value".to_string()], vec![Align::Left]);
+        table.push_row(vec![None]);
+        table.push_row(vec![Some("NULL".to_string())]);
+        let lines = |text: String| -> Vec<String> { text.lines().map(str::to_string).collect() };
+
+        let text = lines(render(&table, TableOptions::default()));
+        assert_eq!(text[3], "│ ∅     │");
+        assert_eq!(text[4], "│ NULL  │");
+
+        let mut markdown = Vec::new();
+        table.write_markdown(&mut markdown).unwrap();
+        let markdown = lines(String::from_utf8(markdown).unwrap());
+        assert_eq!(markdown[2], "| ∅     |");
+        assert_eq!(markdown[3], "| NULL  |");
+    }
+
+    #[test]
+    fn test_markdown_widths_from_sample() {
+        let mut out = Vec::new();
+        let mut writer = MarkdownTableWriter::new(&mut out, &["id".to_string()], &[Align::Right]);
+        for _ in 0..MARKDOWN_SAMPLE_ROWS {
+            writer.write_row(&[Some("1".to_string())]).unwrap();
+        }
+        writer.write_row(&[Some("123456".to_string())]).unwrap();
+        writer.finish().unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), MARKDOWN_SAMPLE_ROWS + 3);
+        assert_eq!(lines[0], "|   id |");
+        assert_eq!(lines[1], "| ---: |");
+        assert_eq!(lines[2], "|    1 |");
+        assert_eq!(lines[MARKDOWN_SAMPLE_ROWS + 2], "| 123456 |");
+    }
+
+    #[test]
+    fn test_html_rows_written_as_they_come() {
+        let mut out = Vec::new();
+        let mut writer =
+            HtmlTableWriter::new(&mut out, &["name".to_string()], &[Align::Left], "t").unwrap();
+        writer.write_row(&[Some("<b>".to_string())]).unwrap();
+        writer.write_row(&[None]).unwrap();
+        writer.finish().unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("<tr><td>&lt;b&gt;</td></tr>\n<tr><td class=\"null\">∅</td></tr>"));
+        assert!(out.contains("<p class=\"rows\">2 rows</p>"));
+    }
+}
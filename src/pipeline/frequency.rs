@@ -0,0 +1,724 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use arrow::array::Array;
+use arrow::array::ArrayRef;
+use arrow::array::AsArray;
+use arrow::array::Float64Array;
+use arrow::array::Int64Array;
+use arrow::array::RecordBatchReader;
+use arrow::array::StringArray;
+use arrow::array::UInt64Array;
+use arrow::compute::SortOptions;
+use arrow::compute::cast;
+use arrow::datatypes::DataType;
+use arrow::datatypes::Field;
+use arrow::datatypes::Float64Type;
+use arrow::datatypes::Int64Type;
+use arrow::datatypes::Schema;
+use arrow::datatypes::TimeUnit;
+use arrow::record_batch::RecordBatch;
+use arrow::row::OwnedRow;
+use arrow::row::RowConverter;
+use arrow::row::SortField;
+use arrow::util::display::ArrayFormatter;
+use arrow::util::display::FormatOptions;
+
+use crate::Error;
+use crate::Result;
+use crate::pipeline::RecordBatchReaderSource;
+use crate::pipeline::Step;
+use crate::pipeline::VecRecordBatchReaderSource;
+
+/// Label of the row that sums up the values past the top `K` of a categorical column.
+pub const OTHER_VALUES: &str = "(other)";
+
+/// Values of the same count are listed in ascending order, with nulls last.
+const NULLS_LAST: SortOptions = SortOptions {
+    descending: false,
+    nulls_first: false,
+};
+
+/// Character used to draw the bars added by [`FrequencyStep`] when `bar_width` is set.
+const BAR: char = '█';
+
+/// Opens the input. Called once per pass, since histograms need the range of each column
+/// before they can count values into buckets.
+pub type SourceFactory = Box<dyn FnMut() -> Result<RecordBatchReaderSource>>;
+
+/// Pipeline step that computes the frequency distribution of columns.
+///
+/// Categorical columns (and all columns, when `categorical` is set) get their `top` most
+/// common values, largest first, followed by an [`OTHER_VALUES`] row for the rest. Numeric and
+/// temporal columns get a histogram of `bins` equal-width buckets between their smallest and
+/// largest values; integer and date buckets have whole-number bounds, shown inclusively.
+///
+/// Produces one row per value or bucket, with the `column`, the `value` (null for nulls), its
+/// `count`, and its `percent` of all rows, plus a `bar` when `bar_width` is set.
+pub struct FrequencyStep {
+    pub columns: Vec<String>,
+    pub top: usize,
+    pub bins: usize,
+    /// When true, counts distinct values of numeric and temporal columns too.
+    pub categorical: bool,
+    /// When set, adds a `bar` column scaled so each column's largest count is this wide.
+    pub bar_width: Option<usize>,
+}
+
+/// How a column's values are counted.
+enum Counter {
+    Categorical {
+        converter: RowConverter,
+        counts: HashMap<OwnedRow, u64>,
+    },
+    /// Integers and temporal values, binned by their integer representation. Times and
+    /// durations have the number of their units in a second, so that their buckets can be
+    /// rounded to whole time units.
+    Integer {
+        range: Range<i64>,
+        units_per_second: Option<i64>,
+    },
+    Float(Range<f64>),
+}
+
+/// The extent of a numeric column, then its bucket counts once `bins` is filled in.
+struct Range<T> {
+    min: Option<T>,
+    max: Option<T>,
+    nulls: u64,
+    bins: Vec<u64>,
+}
+
+impl<T> Default for Range<T> {
+    fn default() -> Self {
+        Self {
+            min: None,
+            max: None,
+            nulls: 0,
+            bins: Vec::new(),
+        }
+    }
+}
+
+/// The rows produced for one column.
+struct ColumnFrequencies {
+    values: Vec<Option<String>>,
+    counts: Vec<u64>,
+}
+
+impl Step for FrequencyStep {
+    type Input = SourceFactory;
+    type Output = RecordBatchReaderSource;
+
+    fn execute(self, mut input: Self::Input) -> Result<Self::Output> {
+        if self.bins == 0 {
+            return Err(Error::GenericError("bins must be at least 1".to_string()));
+        }
+        let reader = input()?.get()?;
+        let schema = reader.schema();
+        let indices = self
+            .columns
+            .iter()
+            .map(|col| {
+                schema
+                    .index_of(col)
+                    .map_err(|e| Error::GenericError(format!("Column '{col}' not found: {e}")))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let data_types: Vec<DataType> = indices
+            .iter()
+            .map(|&i| schema.field(i).data_type().clone())
+            .collect();
+        let mut counters = data_types
+            .iter()
+            .map(|data_type| self.counter(data_type))
+            .collect::<Result<Vec<_>>>()?;
+
+        // First pass: categorical counts, and the range of each numeric column.
+        let mut total_rows: u64 = 0;
+        for batch in reader {
+            let batch = batch?;
+            total_rows += batch.num_rows() as u64;
+            for (counter, &i) in counters.iter_mut().zip(&indices) {
+                observe(counter, batch.column(i))?;
+            }
+        }
+
+        // Second pass, only for histograms: count the values into buckets.
+        let mut numeric = false;
+        for counter in counters.iter_mut() {
+            match counter {
+                Counter::Integer {
+                    range,
+                    units_per_second,
+                } => {
+                    let count = integer_buckets(range, *units_per_second, self.bins)
+                        .map_or(0, |buckets| buckets.count);
+                    range.bins = vec![0; count];
+                    numeric = true;
+                }
+                Counter::Float(range) => {
+                    range.bins = vec![0; if range.min.is_some() { self.bins } else { 0 }];
+                    numeric = true;
+                }
+                Counter::Categorical { .. } => {}
+            }
+        }
+        if numeric {
+            let reader = input()?.get()?;
+            for batch in reader {
+                let batch = batch?;
+                for (counter, &i) in counters.iter_mut().zip(&indices) {
+                    self.bin(counter, batch.column(i))?;
+                }
+            }
+        }
+
+        let mut column_names = Vec::new();
+        let mut values = Vec::new();
+        let mut counts = Vec::new();
+        let mut bars = Vec::new();
+        for ((counter, column), data_type) in
+            counters.into_iter().zip(&self.columns).zip(&data_types)
+        {
+            let frequencies = match counter {
+                Counter::Categorical { converter, counts } => {
+                    self.top_values(&converter, counts)?
+                }
+                Counter::Integer {
+                    range,
+                    units_per_second,
+                } => self.integer_histogram(&range, units_per_second, data_type)?,
+                Counter::Float(range) => self.float_histogram(&range),
+            };
+            let max = frequencies.counts.iter().copied().max().unwrap_or(0);
+            for (value, count) in frequencies.values.into_iter().zip(frequencies.counts) {
+                column_names.push(column.clone());
+                values.push(value);
+                counts.push(count);
+                if let Some(width) = self.bar_width {
+                    bars.push(bar(count, max, width));
+                }
+            }
+        }
+
+        let percents: Vec<f64> = counts
+            .iter()
+            .map(|&count| {
+                if total_rows == 0 {
+                    0.0
+                } else {
+                    (count as f64 * 10000.0 / total_rows as f64).round() / 100.0
+                }
+            })
+            .collect();
+        let mut fields = vec![
+            Field::new("column", DataType::Utf8, false),
+            Field::new("value", DataType::Utf8, true),
+            Field::new("count", DataType::UInt64, false),
+            Field::new("percent", DataType::Float64, false),
+        ];
+        let mut arrays: Vec<ArrayRef> = vec![
+            Arc::new(StringArray::from(column_names)),
+            Arc::new(StringArray::from(values)),
+            Arc::new(UInt64Array::from(counts)),
+            Arc::new(Float64Array::from(percents)),
+        ];
+        if self.bar_width.is_some() {
+            fields.push(Field::new("bar", DataType::Utf8, false));
+            arrays.push(Arc::new(StringArray::from(bars)));
+        }
+        let batch = RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)?;
+        Ok(Box::new(VecRecordBatchReaderSource::new(vec![batch])))
+    }
+}
+
+impl FrequencyStep {
+    fn counter(&self, data_type: &DataType) -> Result<Counter> {
+        if !self.categorical {
+            if is_integer_like(data_type) {
+                return Ok(Counter::Integer {
+                    range: Range::default(),
+                    units_per_second: units_per_second(data_type),
+                });
+            }
+            if is_float_like(data_type) {
+                return Ok(Counter::Float(Range::default()));
+            }
+        }
+        Ok(Counter::Categorical {
+            converter: RowConverter::new(vec![SortField::new_with_options(
+                data_type.clone(),
+                NULLS_LAST,
+            )])?,
+            counts: HashMap::new(),
+        })
+    }
+
+    /// Counts a batch of a numeric column into the buckets of its counter.
+    fn bin(&self, counter: &mut Counter, array: &ArrayRef) -> Result<()> {
+        match counter {
+            Counter::Integer {
+                range,
+                units_per_second,
+            } => {
+                let Some(buckets) = integer_buckets(range, *units_per_second, self.bins) else {
+                    return Ok(());
+                };
+                let last = range.bins.len() - 1;
+                for value in to_i64(array)?.iter().flatten() {
+                    let bin =
+                        ((value as i128 - buckets.start as i128) / buckets.width as i128) as usize;
+                    range.bins[bin.min(last)] += 1;
+                }
+            }
+            Counter::Float(range) => {
+                let (Some(min), Some(max)) = (range.min, range.max) else {
+                    return Ok(());
+                };
+                let width = (max - min) / self.bins as f64;
+                let last = range.bins.len() - 1;
+                for value in to_f64(array)?.iter().flatten().filter(|v| !v.is_nan()) {
+                    let bin = if width > 0.0 {
+                        ((value - min) / width) as usize
+                    } else {
+                        0
+                    };
+                    range.bins[bin.min(last)] += 1;
+                }
+            }
+            Counter::Categorical { .. } => {}
+        }
+        Ok(())
+    }
+
+    /// Returns the `top` most common values, then the total of the rest.
+    fn top_values(
+        &self,
+        converter: &RowConverter,
+        counts: HashMap<OwnedRow, u64>,
+    ) -> Result<ColumnFrequencies> {
+        let mut groups: Vec<(OwnedRow, u64)> = counts.into_iter().collect();
+        groups.sort_unstable_by(|(a, a_count), (b, b_count)| {
+            b_count.cmp(a_count).then_with(|| a.cmp(b))
+        });
+        let other: u64 = groups.iter().skip(self.top).map(|(_, count)| count).sum();
+        groups.truncate(self.top);
+
+        let columns = converter.convert_rows(groups.iter().map(|(row, _)| row.row()))?;
+        let formatter = ArrayFormatter::try_new(columns[0].as_ref(), &FormatOptions::default())?;
+        let mut values: Vec<Option<String>> = (0..columns[0].len())
+            .map(|i| {
+                columns[0]
+                    .is_valid(i)
+                    .then(|| formatter.value(i).to_string())
+            })
+            .collect();
+        let mut counts: Vec<u64> = groups.iter().map(|(_, count)| *count).collect();
+        if other > 0 {
+            values.push(Some(OTHER_VALUES.to_string()));
+            counts.push(other);
+        }
+        Ok(ColumnFrequencies { values, counts })
+    }
+
+    /// Labels integer buckets with their bounds, shown in the column's own type: inclusive, except
+    /// for times and durations.
+    fn integer_histogram(
+        &self,
+        range: &Range<i64>,
+        units_per_second: Option<i64>,
+        data_type: &DataType,
+    ) -> Result<ColumnFrequencies> {
+        let mut values = Vec::new();
+        if let Some(buckets) = integer_buckets(range, units_per_second, self.bins) {
+            let bounds: Vec<i64> = (0..buckets.count)
+                .flat_map(|i| {
+                    let lo = buckets.start as i128 + i as i128 * buckets.width as i128;
+                    // Times are continuous, so their buckets end where the next one starts.
+                    let hi = match units_per_second {
+                        Some(_) => lo + buckets.width as i128,
+                        None => lo + buckets.width as i128 - 1,
+                    };
+                    [lo as i64, hi.min(i64::MAX as i128) as i64]
+                })
+                .collect();
+            let bounds = from_i64(Int64Array::from(bounds), data_type)?;
+            let formatter = ArrayFormatter::try_new(bounds.as_ref(), &FormatOptions::default())?;
+            for i in 0..range.bins.len() {
+                let (lo, hi) = (formatter.value(2 * i), formatter.value(2 * i + 1));
+                let close = if units_per_second.is_some() { ')' } else { ']' };
+                values.push(Some(format!("[{lo}, {hi}{close}")));
+            }
+        }
+        Ok(with_nulls(values, range))
+    }
+
+    /// Labels float buckets as half-open ranges, except the last, which includes the largest
+    /// value.
+    fn float_histogram(&self, range: &Range<f64>) -> ColumnFrequencies {
+        let mut values = Vec::new();
+        if let (Some(min), Some(max)) = (range.min, range.max) {
+            let width = (max - min) / self.bins as f64;
+            // Enough decimals to tell the bounds apart.
+            let precision = if width > 0.0 {
+                (1 - width.log10().floor() as i64).clamp(0, 10) as usize
+            } else {
+                0
+            };
+            let n = range.bins.len();
+            for i in 0..n {
+                let lo = min + i as f64 * width;
+                let hi = if i + 1 == n {
+                    max
+                } else {
+                    min + (i + 1) as f64 * width
+                };
+                let close = if i + 1 == n { ']' } else { ')' };
+                values.push(Some(format!("[{lo:.precision$}, {hi:.precision$}{close}")));
+            }
+        }
+        with_nulls(values, range)
+    }
+}
+
+/// Updates a counter with a batch of its column in the first pass.
+fn observe(counter: &mut Counter, array: &ArrayRef) -> Result<()> {
+    match counter {
+        Counter::Categorical { converter, counts } => {
+            let rows = converter.convert_columns(std::slice::from_ref(array))?;
+            for row in rows.iter() {
+                *counts.entry(row.owned()).or_default() += 1;
+            }
+        }
+        Counter::Integer { range, .. } => {
+            let values = to_i64(array)?;
+            range.nulls += values.null_count() as u64;
+            for value in values.iter().flatten() {
+                range.min = Some(range.min.map_or(value, |min| min.min(value)));
+                range.max = Some(range.max.map_or(value, |max| max.max(value)));
+            }
+        }
+        Counter::Float(range) => {
+            for value in to_f64(array)?.iter() {
+                match value {
+                    Some(value) if !value.is_nan() => {
+                        range.min = Some(range.min.map_or(value, |min| min.min(value)));
+                        range.max = Some(range.max.map_or(value, |max| max.max(value)));
+                    }
+                    // NaN has no place in a histogram, so it is counted with the nulls.
+                    _ => range.nulls += 1,
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Integer buckets: `count` buckets of `width` values from `start`.
+struct IntegerBuckets {
+    start: i64,
+    width: u64,
+    count: usize,
+}
+
+/// Returns the buckets covering a column's range. Their width is a whole number, so that no two
+/// buckets share a label; for times and durations it is a whole number of milliseconds,
+/// seconds, minutes, hours, or days, and the first bucket starts on a multiple of it.
+fn integer_buckets(
+    range: &Range<i64>,
+    units_per_second: Option<i64>,
+    bins: usize,
+) -> Option<IntegerBuckets> {
+    let (min, max) = (range.min? as i128, range.max? as i128);
+    let mut width = ((max - min + 1) as u128).div_ceil(bins as u128).max(1) as i128;
+    let mut start = min;
+    if let Some(units) = units_per_second {
+        // The span between the bounds rather than the number of values, which at these
+        // resolutions would round e.g. 30 minutes up to 31.
+        width = ((max - min) as u128).div_ceil(bins as u128).max(1) as i128;
+        let units = units as i128;
+        let steps = [units / 1000, units, 60 * units, 3600 * units, 86400 * units];
+        if let Some(step) = steps.into_iter().rev().find(|&s| s > 0 && s <= width) {
+            width = (width as u128).div_ceil(step as u128) as i128 * step;
+            start = min.div_euclid(step) * step;
+        }
+    }
+    Some(IntegerBuckets {
+        start: start as i64,
+        width: width as u64,
+        count: ((max - start + 1) as u128).div_ceil(width as u128) as usize,
+    })
+}
+
+/// Returns the number of units in a second of a time, timestamp, or duration type.
+fn units_per_second(data_type: &DataType) -> Option<i64> {
+    let unit = match data_type {
+        DataType::Timestamp(unit, _)
+        | DataType::Time32(unit)
+        | DataType::Time64(unit)
+        | DataType::Duration(unit) => unit,
+        DataType::Date64 => return Some(1000),
+        _ => return None,
+    };
+    Some(match unit {
+        TimeUnit::Second => 1,
+        TimeUnit::Millisecond => 1_000,
+        TimeUnit::Microsecond => 1_000_000,
+        TimeUnit::Nanosecond => 1_000_000_000,
+    })
+}
+
+/// Appends a null bucket when the column has nulls.
+fn with_nulls<T>(mut values: Vec<Option<String>>, range: &Range<T>) -> ColumnFrequencies {
+    let mut counts = range.bins.clone();
+    if range.nulls > 0 {
+        values.push(None);
+        counts.push(range.nulls);
+    }
+    ColumnFrequencies { values, counts }
+}
+
+fn bar(count: u64, max: u64, width: usize) -> String {
+    let len = if max == 0 {
+        0
+    } else {
+        (count as f64 / max as f64 * width as f64).round() as usize
+    };
+    std::iter::repeat_n(BAR, len).collect()
+}
+
+fn is_integer_like(data_type: &DataType) -> bool {
+    data_type.is_integer()
+        || matches!(
+            data_type,
+            DataType::Date32
+                | DataType::Date64
+                | DataType::Time32(_)
+                | DataType::Time64(_)
+                | DataType::Timestamp(_, _)
+                | DataType::Duration(_)
+        )
+}
+
+fn is_float_like(data_type: &DataType) -> bool {
+    data_type.is_floating()
+        || matches!(
+            data_type,
+            DataType::Decimal32(_, _)
+                | DataType::Decimal64(_, _)
+                | DataType::Decimal128(_, _)
+                | DataType::Decimal256(_, _)
+        )
+}
+
+/// The 32-bit temporal types, which are stored as (and cast through) `Int32`.
+fn is_temporal_32(data_type: &DataType) -> bool {
+    matches!(data_type, DataType::Date32 | DataType::Time32(_))
+}
+
+/// Returns the integer representation of an integer or temporal column, e.g. days since the
+/// epoch for `Date32`. Unsigned values too large for an `i64` become null.
+fn to_i64(array: &ArrayRef) -> Result<Int64Array> {
+    let array = if is_temporal_32(array.data_type()) {
+        cast(array, &DataType::Int32)?
+    } else {
+        array.clone()
+    };
+    Ok(cast(&array, &DataType::Int64)?
+        .as_primitive::<Int64Type>()
+        .clone())
+}
+
+/// Converts integers back to the column's type, for labelling buckets.
+fn from_i64(values: Int64Array, data_type: &DataType) -> Result<ArrayRef> {
+    let values: ArrayRef = Arc::new(values);
+    if data_type.is_integer() {
+        return Ok(values);
+    }
+    let values = if is_temporal_32(data_type) {
+        cast(&values, &DataType::Int32)?
+    } else {
+        values
+    };
+    Ok(cast(&values, data_type)?)
+}
+
+fn to_f64(array: &ArrayRef) -> Result<Float64Array> {
+    Ok(cast(array, &DataType::Float64)?
+        .as_primitive::<Float64Type>()
+        .clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::Date32Array;
+    use arrow::array::Int32Array;
+
+    use super::*;
+
+    fn batch() -> RecordBatch {
+        let schema = Schema::new(vec![
+            Field::new("status", DataType::Utf8, true),
+            Field::new("age", DataType::Int32, true),
+            Field::new("score", DataType::Float64, true),
+            Field::new("day", DataType::Date32, false),
+        ]);
+        RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(StringArray::from(vec![
+                    Some("ok"),
+                    Some("ok"),
+                    Some("error"),
+                    None,
+                    Some("retry"),
+                ])),
+                Arc::new(Int32Array::from(vec![
+                    Some(20),
+                    Some(29),
+                    Some(30),
+                    None,
+                    Some(39),
+                ])),
+                Arc::new(Float64Array::from(vec![
+                    Some(0.0),
+                    Some(0.25),
+                    Some(0.5),
+                    Some(f64::NAN),
+                    Some(1.0),
+                ])),
+                // 2024-01-01 through 2024-01-05
+                Arc::new(Date32Array::from(vec![19723, 19724, 19725, 19726, 19727])),
+            ],
+        )
+        .unwrap()
+    }
+
+    fn frequencies(
+        columns: &[&str],
+        top: usize,
+        bins: usize,
+        categorical: bool,
+    ) -> Vec<(String, Option<String>, u64, f64)> {
+        let step = FrequencyStep {
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            top,
+            bins,
+            categorical,
+            bar_width: None,
+        };
+        let factory: SourceFactory = Box::new(|| {
+            Ok(Box::new(VecRecordBatchReaderSource::new(vec![batch()])) as RecordBatchReaderSource)
+        });
+        let mut output = step.execute(factory).unwrap();
+        let batch = output.get().unwrap().next().unwrap().unwrap();
+        let columns = batch.column(0).as_string::<i32>();
+        let values = batch.column(1).as_string::<i32>();
+        let counts = batch
+            .column(2)
+            .as_primitive::<arrow::datatypes::UInt64Type>();
+        let percents = batch.column(3).as_primitive::<Float64Type>();
+        (0..batch.num_rows())
+            .map(|i| {
+                (
+                    columns.value(i).to_string(),
+                    values.is_valid(i).then(|| values.value(i).to_string()),
+                    counts.value(i),
+                    percents.value(i),
+                )
+            })
+            .collect()
+    }
+
+    fn row(
+        column: &str,
+        value: Option<&str>,
+        count: u64,
+        percent: f64,
+    ) -> (String, Option<String>, u64, f64) {
+        (
+            column.to_string(),
+            value.map(str::to_string),
+            count,
+            percent,
+        )
+    }
+
+    #[test]
+    fn test_top_values() {
+        assert_eq!(
+            frequencies(&["status"], 2, 10, false),
+            vec![
+                row("status", Some("ok"), 2, 40.0),
+                row("status", Some("error"), 1, 20.0),
+                row("status", Some(OTHER_VALUES), 2, 40.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_integer_histogram() {
+        assert_eq!(
+            frequencies(&["age"], 10, 2, false),
+            vec![
+                row("age", Some("[20, 29]"), 2, 40.0),
+                row("age", Some("[30, 39]"), 2, 40.0),
+                row("age", None, 1, 20.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_float_histogram() {
+        assert_eq!(
+            frequencies(&["score"], 10, 2, false),
+            vec![
+                row("score", Some("[0.00, 0.50)"), 2, 40.0),
+                row("score", Some("[0.50, 1.00]"), 2, 40.0),
+                row("score", None, 1, 20.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_date_histogram() {
+        assert_eq!(
+            frequencies(&["day"], 10, 2, false),
+            vec![
+                row("day", Some("[2024-01-01, 2024-01-03]"), 3, 60.0),
+                row("day", Some("[2024-01-04, 2024-01-06]"), 2, 40.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_timestamp_buckets_whole_units() {
+        let range = Range {
+            min: Some(1_700_000_012_345_678_901),
+            max: Some(1_700_000_012_345_678_901 + 7_200_000_000_000),
+            ..Range::default()
+        };
+        let buckets = integer_buckets(&range, Some(1_000_000_000), 4).unwrap();
+        assert_eq!(buckets.width, 1_800_000_000_000);
+        assert_eq!(buckets.start, 1_699_999_980_000_000_000);
+        assert_eq!(buckets.count, 5);
+    }
+
+    #[test]
+    fn test_categorical_numeric() {
+        assert_eq!(
+            frequencies(&["age"], 1, 10, true),
+            vec![
+                row("age", Some("20"), 1, 20.0),
+                row("age", Some(OTHER_VALUES), 4, 80.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bar() {
+        assert_eq!(bar(5, 10, 4), "██");
+        assert_eq!(bar(0, 0, 4), "");
+    }
+}
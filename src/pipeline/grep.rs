@@ -0,0 +1,336 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use arrow::array::Array;
+use arrow::array::ArrayRef;
+use arrow::array::AsArray;
+use arrow::array::BooleanArray;
+use arrow::array::RecordBatchReader;
+use arrow::array::UInt64Array;
+use arrow::compute::filter;
+use arrow::compute::filter_record_batch;
+use arrow::datatypes::DataType;
+use arrow::datatypes::Field;
+use arrow::datatypes::Schema;
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+use arrow::util::display::ArrayFormatter;
+use arrow::util::display::FormatOptions;
+use regex::Regex;
+
+use crate::Error;
+use crate::Result;
+use crate::pipeline::RecordBatchReaderSource;
+use crate::pipeline::Step;
+use crate::pipeline::record_batch_filter::RecordBatchReaderHolder;
+
+/// Name of the column added by [`GrepStep`] when `row_numbers` is set.
+pub const ROW_NUMBER_COLUMN: &str = "_row";
+
+/// Pipeline step that keeps only the rows with a value matching a regular expression.
+///
+/// Nested values (structs, lists, and maps) match if any value inside them matches. Non-string
+/// values are matched against their display form, e.g. `2024-01-31` for a date.
+pub struct GrepStep {
+    pub regex: Regex,
+    /// Columns to search; all columns when `None`.
+    pub columns: Option<Vec<String>>,
+    /// When true, keeps the rows that do not match instead.
+    pub invert: bool,
+    /// When true, adds a [`ROW_NUMBER_COLUMN`] column holding each row's one-based position in
+    /// the input.
+    pub row_numbers: bool,
+    /// Incremented by the number of rows kept, as they are read.
+    pub matched: Rc<Cell<usize>>,
+}
+
+impl Step for GrepStep {
+    type Input = RecordBatchReaderSource;
+    type Output = RecordBatchReaderSource;
+
+    fn execute(self, mut input: Self::Input) -> Result<Self::Output> {
+        let reader = input.get()?;
+        let input_schema = reader.schema();
+        let indices = match &self.columns {
+            Some(columns) => columns
+                .iter()
+                .map(|col| {
+                    input_schema
+                        .index_of(col)
+                        .map_err(|e| Error::GenericError(format!("Column '{col}' not found: {e}")))
+                })
+                .collect::<Result<Vec<_>>>()?,
+            None => (0..input_schema.fields().len()).collect(),
+        };
+        let schema = if self.row_numbers {
+            let mut fields = vec![Arc::new(Field::new(
+                ROW_NUMBER_COLUMN,
+                DataType::UInt64,
+                false,
+            ))];
+            fields.extend(input_schema.fields().iter().cloned());
+            Arc::new(Schema::new(fields))
+        } else {
+            input_schema
+        };
+        let grep_reader = GrepRecordBatchReader {
+            reader,
+            schema,
+            regex: self.regex,
+            indices,
+            invert: self.invert,
+            row_numbers: self.row_numbers,
+            rows_read: 0,
+            matched: self.matched,
+        };
+        Ok(Box::new(RecordBatchReaderHolder {
+            reader: Some(Box::new(grep_reader)),
+        }))
+    }
+}
+
+/// Record batch reader that filters each batch to the matching rows.
+struct GrepRecordBatchReader {
+    reader: Box<dyn RecordBatchReader + 'static>,
+    schema: SchemaRef,
+    regex: Regex,
+    indices: Vec<usize>,
+    invert: bool,
+    row_numbers: bool,
+    rows_read: usize,
+    matched: Rc<Cell<usize>>,
+}
+
+impl GrepRecordBatchReader {
+    fn filter_batch(&self, batch: &RecordBatch) -> arrow::error::Result<RecordBatch> {
+        let mut mask = vec![false; batch.num_rows()];
+        for &i in &self.indices {
+            let matches = matching_rows(batch.column(i).as_ref(), &self.regex)?;
+            for (m, column_match) in mask.iter_mut().zip(matches) {
+                *m |= column_match;
+            }
+        }
+        if self.invert {
+            mask.iter_mut().for_each(|m| *m = !*m);
+        }
+        let mask = BooleanArray::from(mask);
+        let filtered = filter_record_batch(batch, &mask)?;
+        if !self.row_numbers {
+            return Ok(filtered);
+        }
+        let start = self.rows_read as u64 + 1;
+        let numbers = UInt64Array::from_iter_values(start..start + batch.num_rows() as u64);
+        let mut columns = vec![filter(&numbers, &mask)?];
+        columns.extend(filtered.columns().iter().cloned());
+        RecordBatch::try_new(self.schema.clone(), columns)
+    }
+}
+
+impl RecordBatchReader for GrepRecordBatchReader {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+impl Iterator for GrepRecordBatchReader {
+    type Item = arrow::error::Result<RecordBatch>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let batch = match self.reader.next()? {
+                Ok(batch) => batch,
+                Err(e) => return Some(Err(e)),
+            };
+            let filtered = self.filter_batch(&batch);
+            self.rows_read += batch.num_rows();
+            match filtered {
+                Ok(filtered) if filtered.num_rows() == 0 => continue,
+                Ok(filtered) => {
+                    self.matched.set(self.matched.get() + filtered.num_rows());
+                    return Some(Ok(filtered));
+                }
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+/// Returns, for each row of `array`, whether it holds a value (or, for nested types, contains
+/// a value) that matches `regex`. Null values never match.
+fn matching_rows(array: &dyn Array, regex: &Regex) -> arrow::error::Result<Vec<bool>> {
+    let mut matches = match array.data_type() {
+        DataType::Utf8 => array
+            .as_string::<i32>()
+            .iter()
+            .map(|v| v.is_some_and(|v| regex.is_match(v)))
+            .collect(),
+        DataType::LargeUtf8 => array
+            .as_string::<i64>()
+            .iter()
+            .map(|v| v.is_some_and(|v| regex.is_match(v)))
+            .collect(),
+        DataType::Utf8View => array
+            .as_string_view()
+            .iter()
+            .map(|v| v.is_some_and(|v| regex.is_match(v)))
+            .collect(),
+        DataType::Struct(_) => {
+            let mut matches = vec![false; array.len()];
+            for child in array.as_struct().columns() {
+                for (m, child_match) in matches.iter_mut().zip(matching_rows(child, regex)?) {
+                    *m |= child_match;
+                }
+            }
+            matches
+        }
+        DataType::List(_) => {
+            let list = array.as_list::<i32>();
+            let offsets: Vec<usize> = list.offsets().iter().map(|&o| o as usize).collect();
+            any_in_ranges(list.values(), &offsets, regex)?
+        }
+        DataType::LargeList(_) => {
+            let list = array.as_list::<i64>();
+            let offsets: Vec<usize> = list.offsets().iter().map(|&o| o as usize).collect();
+            any_in_ranges(list.values(), &offsets, regex)?
+        }
+        DataType::FixedSizeList(_, size) => {
+            let list = array.as_fixed_size_list();
+            let offsets: Vec<usize> = (0..=list.len()).map(|i| i * *size as usize).collect();
+            any_in_ranges(list.values(), &offsets, regex)?
+        }
+        DataType::Map(_, _) => {
+            let map = array.as_map();
+            let offsets: Vec<usize> = map.offsets().iter().map(|&o| o as usize).collect();
+            let entries: ArrayRef = Arc::new(map.entries().clone());
+            any_in_ranges(&entries, &offsets, regex)?
+        }
+        _ => {
+            let formatter = ArrayFormatter::try_new(array, &FormatOptions::default())?;
+            (0..array.len())
+                .map(|i| array.is_valid(i) && regex.is_match(&formatter.value(i).to_string()))
+                .collect()
+        }
+    };
+    // A null parent hides whatever its children hold at that position.
+    if array.null_count() > 0 {
+        for (i, m) in matches.iter_mut().enumerate() {
+            *m &= array.is_valid(i);
+        }
+    }
+    Ok(matches)
+}
+
+/// Returns, for each range `offsets[i]..offsets[i + 1]` of `values`, whether any value in it
+/// matches.
+fn any_in_ranges(
+    values: &ArrayRef,
+    offsets: &[usize],
+    regex: &Regex,
+) -> arrow::error::Result<Vec<bool>> {
+    let value_matches = matching_rows(values.as_ref(), regex)?;
+    Ok(offsets
+        .windows(2)
+        .map(|w| value_matches[w[0]..w[1]].iter().any(|&m| m))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::Int32Array;
+    use arrow::array::ListArray;
+    use arrow::array::StringArray;
+    use arrow::array::StructArray;
+    use arrow::datatypes::Int32Type;
+
+    use super::*;
+    use crate::pipeline::VecRecordBatchReaderSource;
+
+    fn source() -> RecordBatchReaderSource {
+        let address = StructArray::from(vec![(
+            Arc::new(Field::new("city", DataType::Utf8, true)),
+            Arc::new(StringArray::from(vec!["Auckland", "Boston", "Denver"])) as ArrayRef,
+        )]);
+        let scores = ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+            Some(vec![Some(1), Some(2)]),
+            None,
+            Some(vec![Some(42)]),
+        ]);
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("address", address.data_type().clone(), false),
+            Field::new("scores", scores.data_type().clone(), true),
+        ]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3])),
+                Arc::new(StringArray::from(vec![Some("Ann"), None, Some("Bob")])),
+                Arc::new(address),
+                Arc::new(scores),
+            ],
+        )
+        .unwrap();
+        Box::new(VecRecordBatchReaderSource::new(vec![batch]))
+    }
+
+    fn grep(pattern: &str, columns: Option<Vec<&str>>, invert: bool) -> (Vec<u64>, usize) {
+        let matched = Rc::new(Cell::new(0));
+        let step = GrepStep {
+            regex: Regex::new(pattern).unwrap(),
+            columns: columns.map(|c| c.into_iter().map(String::from).collect()),
+            invert,
+            row_numbers: true,
+            matched: matched.clone(),
+        };
+        let mut output = step.execute(source()).unwrap();
+        let rows = output
+            .get()
+            .unwrap()
+            .flat_map(|batch| {
+                let batch = batch.unwrap();
+                assert_eq!(batch.schema().field(0).name(), ROW_NUMBER_COLUMN);
+                batch
+                    .column(0)
+                    .as_any()
+                    .downcast_ref::<UInt64Array>()
+                    .unwrap()
+                    .values()
+                    .to_vec()
+            })
+            .collect();
+        (rows, matched.get())
+    }
+
+    #[test]
+    fn test_grep_any_column() {
+        assert_eq!(grep("^Bob$", None, false), (vec![3], 1));
+        assert_eq!(grep("o", None, false), (vec![2, 3], 2));
+        assert_eq!(grep("^2$", None, false), (vec![1, 2], 2));
+    }
+
+    #[test]
+    fn test_grep_nested_values() {
+        assert_eq!(grep("^Denver$", None, false), (vec![3], 1));
+        assert_eq!(grep("^42$", Some(vec!["scores"]), false), (vec![3], 1));
+        assert_eq!(grep("^Auck", Some(vec!["name"]), false), (vec![], 0));
+    }
+
+    #[test]
+    fn test_grep_invert() {
+        assert_eq!(grep("Ann|Bob", Some(vec!["name"]), true), (vec![2], 1));
+    }
+
+    #[test]
+    fn test_grep_unknown_column() {
+        let step = GrepStep {
+            regex: Regex::new("x").unwrap(),
+            columns: Some(vec!["missing".to_string()]),
+            invert: false,
+            row_numbers: false,
+            matched: Rc::new(Cell::new(0)),
+        };
+        assert!(step.execute(source()).is_err());
+    }
+}
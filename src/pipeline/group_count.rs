@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use arrow::array::ArrayRef;
+use arrow::array::RecordBatchReader;
+use arrow::array::UInt64Array;
+use arrow::compute::SortOptions;
+use arrow::datatypes::DataType;
+use arrow::datatypes::Field;
+use arrow::datatypes::Schema;
+use arrow::record_batch::RecordBatch;
+use arrow::row::OwnedRow;
+use arrow::row::RowConverter;
+use arrow::row::SortField;
+
+use crate::Error;
+use crate::Result;
+use crate::pipeline::RecordBatchReaderSource;
+use crate::pipeline::Step;
+use crate::pipeline::VecRecordBatchReaderSource;
+
+/// Name of the column added by [`GroupCountStep`] to hold the number of rows in each group.
+pub const COUNT_COLUMN: &str = "count";
+
+/// Pipeline step that counts the rows for each distinct combination of values in `columns`.
+///
+/// Produces one row per group, with the group's values followed by a [`COUNT_COLUMN`] column,
+/// ordered from the largest group to the smallest. Groups of the same size are ordered by
+/// their values, with nulls last. Nulls form a group of their own.
+pub struct GroupCountStep {
+    pub columns: Vec<String>,
+    /// When set, keeps only this many of the largest groups.
+    pub top: Option<usize>,
+}
+
+impl Step for GroupCountStep {
+    type Input = RecordBatchReaderSource;
+    type Output = RecordBatchReaderSource;
+
+    fn execute(self, mut input: Self::Input) -> Result<Self::Output> {
+        let reader = input.get()?;
+        let input_schema = reader.schema();
+        let indices = self
+            .columns
+            .iter()
+            .map(|col| {
+                input_schema
+                    .index_of(col)
+                    .map_err(|e| Error::GenericError(format!("Column '{col}' not found: {e}")))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let fields: Vec<Field> = indices
+            .iter()
+            .map(|&i| input_schema.field(i).clone())
+            .collect();
+        let converter = RowConverter::new(
+            fields
+                .iter()
+                .map(|f| {
+                    SortField::new_with_options(
+                        f.data_type().clone(),
+                        SortOptions {
+                            descending: false,
+                            nulls_first: false,
+                        },
+                    )
+                })
+                .collect(),
+        )?;
+
+        let mut counts: HashMap<OwnedRow, u64> = HashMap::new();
+        for batch in reader {
+            let batch = batch?;
+            let arrays: Vec<ArrayRef> = indices.iter().map(|&i| batch.column(i).clone()).collect();
+            let rows = converter.convert_columns(&arrays)?;
+            for row in rows.iter() {
+                *counts.entry(row.owned()).or_default() += 1;
+            }
+        }
+
+        let mut groups: Vec<(OwnedRow, u64)> = counts.into_iter().collect();
+        groups.sort_unstable_by(|(a, a_count), (b, b_count)| {
+            b_count.cmp(a_count).then_with(|| a.cmp(b))
+        });
+        if let Some(top) = self.top {
+            groups.truncate(top);
+        }
+
+        let mut columns = converter.convert_rows(groups.iter().map(|(row, _)| row.row()))?;
+        columns.push(Arc::new(UInt64Array::from_iter_values(
+            groups.iter().map(|(_, count)| *count),
+        )));
+        let mut fields = fields;
+        fields.push(Field::new(COUNT_COLUMN, DataType::UInt64, false));
+        let batch = RecordBatch::try_new(Arc::new(Schema::new(fields)), columns)?;
+        Ok(Box::new(VecRecordBatchReaderSource::new(vec![batch])))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::AsArray;
+    use arrow::array::Int32Array;
+    use arrow::array::StringArray;
+    use arrow::datatypes::Int32Type;
+    use arrow::datatypes::UInt64Type;
+
+    use super::*;
+
+    fn source() -> RecordBatchReaderSource {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("status", DataType::Utf8, true),
+            Field::new("code", DataType::Int32, false),
+        ]));
+        let batch = |status: Vec<Option<&str>>, code: Vec<i32>| {
+            RecordBatch::try_new(
+                schema.clone(),
+                vec![
+                    Arc::new(StringArray::from(status)),
+                    Arc::new(Int32Array::from(code)),
+                ],
+            )
+            .unwrap()
+        };
+        Box::new(VecRecordBatchReaderSource::new(vec![
+            batch(vec![Some("ok"), Some("error"), None], vec![200, 500, 0]),
+            batch(
+                vec![Some("ok"), Some("ok"), Some("error")],
+                vec![200, 201, 500],
+            ),
+        ]))
+    }
+
+    fn group_count(columns: &[&str], top: Option<usize>) -> RecordBatch {
+        let step = GroupCountStep {
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            top,
+        };
+        let mut output = step.execute(source()).unwrap();
+        let batches: Vec<RecordBatch> = output.get().unwrap().map(|b| b.unwrap()).collect();
+        assert_eq!(batches.len(), 1);
+        batches.into_iter().next().unwrap()
+    }
+
+    fn counts(batch: &RecordBatch) -> Vec<u64> {
+        batch
+            .column_by_name(COUNT_COLUMN)
+            .unwrap()
+            .as_primitive::<UInt64Type>()
+            .values()
+            .to_vec()
+    }
+
+    #[test]
+    fn test_group_count_single_column() {
+        let batch = group_count(&["status"], None);
+        let status: Vec<Option<&str>> = batch.column(0).as_string::<i32>().iter().collect();
+        assert_eq!(status, vec![Some("ok"), Some("error"), None]);
+        assert_eq!(counts(&batch), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn test_group_count_multiple_columns_top() {
+        let batch = group_count(&["status", "code"], Some(2));
+        assert_eq!(batch.schema().field(1).name(), "code");
+        let code: Vec<i32> = batch
+            .column(1)
+            .as_primitive::<Int32Type>()
+            .values()
+            .to_vec();
+        assert_eq!(code, vec![500, 200]);
+        assert_eq!(counts(&batch), vec![2, 2]);
+    }
+
+    #[test]
+    fn test_group_count_unknown_column() {
+        let step = GroupCountStep {
+            columns: vec!["missing".to_string()],
+            top: None,
+        };
+        let result = step.execute(source());
+        assert!(result.is_err());
+    }
+}
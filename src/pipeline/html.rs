@@ -0,0 +1,32 @@
+use std::io::BufWriter;
+use std::path::Path;
+
+use crate::Result;
+use crate::pipeline::RecordBatchReaderSource;
+use crate::pipeline::Step;
+use crate::pipeline::WriteArgs;
+use crate::pipeline::display::write_record_batches_as_html;
+use crate::pipeline::output::OutputFile;
+
+/// Pipeline step that writes record batches to a standalone HTML page with a sortable table,
+/// titled after the file name.
+pub struct WriteHtmlStep {
+    pub args: WriteArgs,
+}
+
+impl Step for WriteHtmlStep {
+    type Input = RecordBatchReaderSource;
+    type Output = ();
+
+    fn execute(self, mut input: Self::Input) -> Result<Self::Output> {
+        let path = Path::new(&self.args.path);
+        let title = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let mut output = OutputFile::create(path, self.args.no_clobber)?;
+        let mut reader = input.get()?;
+        write_record_batches_as_html(&mut *reader, BufWriter::new(&mut output), &title)?;
+        output.commit()
+    }
+}
@@ -1,5 +1,6 @@
-use arrow::record_batch::RecordBatch;
-use arrow_json::writer::JsonArray;
+use std::io::Write;
+
+use arrow_json::writer::LineDelimited;
 use arrow_json::writer::WriterBuilder;
 
 use crate::Error;
@@ -7,8 +8,12 @@ use crate::Result;
 use crate::pipeline::RecordBatchReaderSource;
 use crate::pipeline::Step;
 use crate::pipeline::WriteJsonArgs;
+use crate::pipeline::display::write_record_batches_as_json;
+use crate::pipeline::display::write_record_batches_as_json_pretty;
+use crate::pipeline::output::OutputFile;
 
 /// Pipeline step that writes record batches to a JSON file (single array of objects).
+/// Each batch is written as soon as it is read, so the input is never held in memory.
 pub struct WriteJsonStep {
     pub args: WriteJsonArgs,
 }
@@ -18,34 +23,41 @@ impl Step for WriteJsonStep {
     type Output = ();
 
     fn execute(self, mut input: Self::Input) -> Result<Self::Output> {
-        let path = self.args.path.as_str();
-        let reader = input.get()?;
-        let batches: Vec<RecordBatch> = reader
-            .collect::<std::result::Result<Vec<_>, arrow::error::ArrowError>>()
-            .map_err(Error::ArrowError)?;
-        let batch_refs: Vec<&RecordBatch> = batches.iter().collect();
-        let builder = WriterBuilder::new().with_explicit_nulls(!self.args.sparse);
-
+        let mut output = OutputFile::create(&self.args.path, self.args.no_clobber)?;
+        let mut w = std::io::BufWriter::new(&mut output);
+        let mut reader = input.get()?;
         if self.args.pretty {
-            let mut buf = Vec::new();
-            let mut writer = builder.build::<_, JsonArray>(&mut buf);
-            writer
-                .write_batches(&batch_refs)
-                .map_err(Error::ArrowError)?;
-            writer.finish().map_err(Error::ArrowError)?;
-            let value: serde_json::Value = serde_json::from_slice(&buf)
-                .map_err(|e| Error::GenericError(format!("Invalid JSON: {e}")))?;
-            let file = std::fs::File::create(path).map_err(Error::IoError)?;
-            serde_json::to_writer_pretty(file, &value)
-                .map_err(|e| Error::GenericError(format!("Failed to write JSON: {e}")))?;
+            write_record_batches_as_json_pretty(&mut *reader, &mut w, self.args.sparse)?;
         } else {
-            let file = std::fs::File::create(path).map_err(Error::IoError)?;
-            let mut writer = builder.build::<_, JsonArray>(file);
-            writer
-                .write_batches(&batch_refs)
-                .map_err(Error::ArrowError)?;
-            writer.finish().map_err(Error::ArrowError)?;
+            write_record_batches_as_json(&mut *reader, &mut w, self.args.sparse)?;
+        }
+        w.flush().map_err(Error::IoError)?;
+        drop(w);
+        output.commit()
+    }
+}
+
+/// Pipeline step that writes record batches to a JSON Lines file (one object per line).
+/// Each batch is written as soon as it is read, so the input is never held in memory.
+pub struct WriteJsonLinesStep {
+    pub args: WriteJsonArgs,
+}
+
+impl Step for WriteJsonLinesStep {
+    type Input = RecordBatchReaderSource;
+    type Output = ();
+
+    fn execute(self, mut input: Self::Input) -> Result<Self::Output> {
+        let mut output = OutputFile::create(&self.args.path, self.args.no_clobber)?;
+        let builder = WriterBuilder::new().with_explicit_nulls(!self.args.sparse);
+        let mut writer = builder.build::<_, LineDelimited>(std::io::BufWriter::new(&mut output));
+        let reader = input.get()?;
+        for batch in reader {
+            let batch = batch.map_err(Error::ArrowError)?;
+            writer.write(&batch).map_err(Error::ArrowError)?;
         }
-        Ok(())
+        writer.finish().map_err(Error::ArrowError)?;
+        writer.into_inner().flush().map_err(Error::IoError)?;
+        output.commit()
     }
 }
@@ -0,0 +1,25 @@
+use std::io::BufWriter;
+
+use crate::Result;
+use crate::pipeline::RecordBatchReaderSource;
+use crate::pipeline::Step;
+use crate::pipeline::WriteArgs;
+use crate::pipeline::display::write_record_batches_as_markdown;
+use crate::pipeline::output::OutputFile;
+
+/// Pipeline step that writes record batches to a Markdown file as a table, row by row.
+pub struct WriteMarkdownStep {
+    pub args: WriteArgs,
+}
+
+impl Step for WriteMarkdownStep {
+    type Input = RecordBatchReaderSource;
+    type Output = ();
+
+    fn execute(self, mut input: Self::Input) -> Result<Self::Output> {
+        let mut output = OutputFile::create(&self.args.path, self.args.no_clobber)?;
+        let mut reader = input.get()?;
+        write_record_batches_as_markdown(&mut *reader, BufWriter::new(&mut output))?;
+        output.commit()
+    }
+}
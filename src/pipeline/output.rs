@@ -0,0 +1,166 @@
+//! Atomic writing of output files.
+
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use tempfile::NamedTempFile;
+use tempfile::TempPath;
+
+use crate::Error;
+use crate::Result;
+
+/// An output file, written under a temporary name in the directory of its path and moved to
+/// its path by [`OutputFile::commit`]. If writing fails, or the file is dropped without being
+/// committed, the temporary file is removed: a failed write neither leaves a truncated file
+/// behind nor replaces an existing one.
+pub struct OutputFile {
+    temp: NamedTempFile,
+    path: PathBuf,
+    no_clobber: bool,
+}
+
+impl OutputFile {
+    /// Creates a temporary file for writing to `path`. With `no_clobber`, committing fails if
+    /// a file exists at `path` by then, even one created while the output was being written.
+    pub fn create(path: impl AsRef<Path>, no_clobber: bool) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let temp = temp_file_for(&path)?;
+        Ok(OutputFile {
+            temp,
+            path,
+            no_clobber,
+        })
+    }
+
+    /// Moves the written file to its path, replacing any file there unless created with
+    /// `no_clobber`.
+    pub fn commit(mut self) -> Result<()> {
+        self.temp.flush().map_err(Error::IoError)?;
+        persist(self.temp.into_temp_path(), &self.path, self.no_clobber)
+    }
+}
+
+/// Creates an empty temporary file in the directory of `path`, removed when the returned path
+/// is dropped. A file written there can be moved to `path` with [`persist`].
+pub fn temp_path_for(path: impl AsRef<Path>) -> Result<TempPath> {
+    Ok(temp_file_for(path.as_ref())?.into_temp_path())
+}
+
+/// Moves the temporary file `temp` to `path`, replacing any file there unless `no_clobber`.
+pub fn persist(temp: TempPath, path: impl AsRef<Path>, no_clobber: bool) -> Result<()> {
+    let path = path.as_ref();
+    if !no_clobber {
+        return temp.persist(path).map_err(|e| Error::IoError(e.error));
+    }
+    match temp.persist_noclobber(path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.error.kind() == std::io::ErrorKind::AlreadyExists => Err(Error::GenericError(
+            format!("Output file {} already exists", path.display()),
+        )),
+        Err(e) => Err(Error::IoError(e.error)),
+    }
+}
+
+fn temp_file_for(path: &Path) -> Result<NamedTempFile> {
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let prefix = match path.file_name() {
+        Some(name) => format!(".{}.", name.to_string_lossy()),
+        None => ".datu.".to_string(),
+    };
+    let mut builder = tempfile::Builder::new();
+    builder.prefix(&prefix).suffix(".tmp");
+    // Temporary files are only readable by their owner by default; the output file gets
+    // the permissions File::create would have given it.
+    #[cfg(unix)]
+    builder.permissions(std::os::unix::fs::PermissionsExt::from_mode(0o666));
+    builder.tempfile_in(dir).map_err(Error::IoError)
+}
+
+impl Write for OutputFile {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.temp.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.temp.flush()
+    }
+}
+
+impl Seek for OutputFile {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.temp.seek(pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commit_replaces_file() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let path = temp_dir.path().join("out.csv");
+        std::fs::write(&path, "old").unwrap();
+
+        let mut output = OutputFile::create(&path, false).unwrap();
+        output.write_all(b"new").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "old");
+        output.commit().unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "new");
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_drop_keeps_existing_file() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let path = temp_dir.path().join("out.csv");
+        std::fs::write(&path, "old").unwrap();
+
+        let mut output = OutputFile::create(&path, false).unwrap();
+        output.write_all(b"partial").unwrap();
+        drop(output);
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "old");
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_no_clobber_keeps_file_created_while_writing() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let path = temp_dir.path().join("out.csv");
+
+        let mut output = OutputFile::create(&path, true).unwrap();
+        output.write_all(b"new").unwrap();
+        std::fs::write(&path, "created meanwhile").unwrap();
+        let result = output.commit();
+
+        assert!(
+            matches!(&result, Err(Error::GenericError(e)) if e.contains("already exists")),
+            "{result:?}"
+        );
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "created meanwhile");
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let path = temp_dir.path().join("out.csv");
+        OutputFile::create(&path, false).unwrap().commit().unwrap();
+        let created = temp_dir.path().join("created.csv");
+        std::fs::File::create(&created).unwrap();
+
+        let mode = |path: &Path| std::fs::metadata(path).unwrap().permissions().mode();
+        assert_eq!(mode(&path), mode(&created));
+    }
+}
@@ -1,15 +1,33 @@
 use arrow::array::RecordBatchReader;
+use arrow::datatypes::Schema;
+use parquet::arrow::ArrowSchemaConverter;
 use parquet::arrow::ArrowWriter;
 use parquet::arrow::arrow_reader::ParquetRecordBatchReader;
 use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::basic::BrotliLevel;
+use parquet::basic::Compression;
+use parquet::basic::GzipLevel;
+use parquet::basic::ZstdLevel;
+use parquet::file::metadata::SortingColumn;
+use parquet::file::properties::DEFAULT_BLOOM_FILTER_NDV;
+use parquet::file::properties::EnabledStatistics;
+use parquet::file::properties::WriterProperties;
+use parquet::file::properties::WriterVersion;
+use parquet::schema::types::ColumnPath;
+use parquet::schema::types::SchemaDescriptor;
 
 use crate::Error;
 use crate::Result;
+use crate::cli::ParquetCompression;
+use crate::cli::ParquetStatistics;
+use crate::cli::ParquetWriteOptions;
+use crate::cli::ParquetWriterVersion;
 use crate::pipeline::ReadArgs;
 use crate::pipeline::RecordBatchReaderSource;
 use crate::pipeline::Source;
 use crate::pipeline::Step;
 use crate::pipeline::WriteArgs;
+use crate::pipeline::output::OutputFile;
 
 /// Pipeline step that reads a Parquet file and produces a record batch reader.
 pub struct ReadParquetStep {
@@ -41,6 +59,7 @@ pub fn read_parquet(args: &ReadArgs) -> Result<ParquetRecordBatchReader> {
 /// Pipeline step that writes record batches to a Parquet file.
 pub struct WriteParquetStep {
     pub args: WriteArgs,
+    pub options: ParquetWriteOptions,
 }
 
 /// Result of successfully writing a Parquet file.
@@ -51,13 +70,14 @@ impl Step for WriteParquetStep {
     type Output = WriteParquetResult;
 
     fn execute(self, mut input: Self::Input) -> Result<Self::Output> {
-        let path = self.args.path.as_str();
-        let file = std::fs::File::create(path).map_err(Error::IoError)?;
-
         let reader = input.get()?;
         let schema = reader.schema();
+        let properties = writer_properties(&self.options, &schema)?;
 
-        let mut writer = ArrowWriter::try_new(file, schema, None).map_err(Error::ParquetError)?;
+        let path = self.args.path.as_str();
+        let mut output = OutputFile::create(path, self.args.no_clobber)?;
+        let mut writer = ArrowWriter::try_new(&mut output, schema, Some(properties))
+            .map_err(Error::ParquetError)?;
 
         for batch in reader {
             let batch = batch.map_err(Error::ArrowError)?;
@@ -65,13 +85,172 @@ impl Step for WriteParquetStep {
         }
 
         writer.close().map_err(Error::ParquetError)?;
+        output.commit()?;
 
         Ok(WriteParquetResult {})
     }
 }
 
+/// Builds the Parquet writer properties for the options, checking that the columns they name
+/// are in the schema.
+pub fn writer_properties(
+    options: &ParquetWriteOptions,
+    schema: &Schema,
+) -> Result<WriterProperties> {
+    let descriptor = ArrowSchemaConverter::new().convert(schema)?;
+    let mut builder = WriterProperties::builder()
+        .set_compression(compression(options.compression, options.compression_level)?)
+        .set_writer_version(match options.writer_version {
+            ParquetWriterVersion::V1 => WriterVersion::PARQUET_1_0,
+            ParquetWriterVersion::V2 => WriterVersion::PARQUET_2_0,
+        })
+        .set_dictionary_enabled(!options.no_dictionary)
+        .set_statistics_enabled(match options.statistics {
+            ParquetStatistics::None => EnabledStatistics::None,
+            ParquetStatistics::Chunk => EnabledStatistics::Chunk,
+            ParquetStatistics::Page => EnabledStatistics::Page,
+        });
+    if let Some(size) = options.max_row_group_size {
+        if size == 0 {
+            return Err(Error::GenericError(
+                "--max-row-group-size must be at least 1".to_string(),
+            ));
+        }
+        builder = builder.set_max_row_group_row_count(Some(size));
+    }
+    if let Some(size) = options.data_page_size {
+        builder = builder.set_data_page_size_limit(size);
+    }
+    for column in column_list(&options.dictionary_columns) {
+        builder = builder.set_column_dictionary_enabled(column_path(&descriptor, &column)?, true);
+    }
+    for column in column_list(&options.no_dictionary_columns) {
+        builder = builder.set_column_dictionary_enabled(column_path(&descriptor, &column)?, false);
+    }
+    if let Some(fpp) = options.bloom_filter_fpp
+        && !(fpp > 0.0 && fpp < 1.0)
+    {
+        return Err(Error::GenericError(format!(
+            "--bloom-filter-fpp must be between 0 and 1, got {fpp}"
+        )));
+    }
+    for column in column_list(&options.bloom_filter_columns) {
+        let path = column_path(&descriptor, &column)?;
+        builder = builder.set_column_bloom_filter_enabled(path.clone(), true);
+        if let Some(fpp) = options.bloom_filter_fpp {
+            builder = builder.set_column_bloom_filter_fpp(path.clone(), fpp);
+        }
+        // A row group has at most this many distinct values, and each of its bloom filters is
+        // sized to hold them.
+        let ndv = options.bloom_filter_ndv.unwrap_or(
+            options
+                .max_row_group_size
+                .map_or(DEFAULT_BLOOM_FILTER_NDV, |size| {
+                    DEFAULT_BLOOM_FILTER_NDV.min(size as u64)
+                }),
+        );
+        builder = builder.set_column_bloom_filter_max_ndv(path, ndv);
+    }
+    let sorting_columns = column_list(&options.sorting_columns)
+        .iter()
+        .map(|spec| sorting_column(&descriptor, spec))
+        .collect::<Result<Vec<_>>>()?;
+    if !sorting_columns.is_empty() {
+        builder = builder.set_sorting_columns(Some(sorting_columns));
+    }
+    Ok(builder.build())
+}
+
+/// Returns the codec, with the level if one is given.
+fn compression(codec: ParquetCompression, level: Option<u32>) -> Result<Compression> {
+    let compression = match (codec, level) {
+        (ParquetCompression::Uncompressed, None) => Compression::UNCOMPRESSED,
+        (ParquetCompression::Snappy, None) => Compression::SNAPPY,
+        (ParquetCompression::Lz4, None) => Compression::LZ4_RAW,
+        (ParquetCompression::Gzip, level) => Compression::GZIP(match level {
+            Some(level) => GzipLevel::try_new(level)?,
+            None => GzipLevel::default(),
+        }),
+        (ParquetCompression::Zstd, level) => Compression::ZSTD(match level {
+            Some(level) => ZstdLevel::try_new(level as i32)?,
+            None => ZstdLevel::default(),
+        }),
+        (ParquetCompression::Brotli, level) => Compression::BROTLI(match level {
+            Some(level) => BrotliLevel::try_new(level)?,
+            None => BrotliLevel::default(),
+        }),
+        (codec, Some(_)) => {
+            return Err(Error::GenericError(format!(
+                "--compression-level is not supported for {codec}; use gzip, zstd, or brotli"
+            )));
+        }
+    };
+    Ok(compression)
+}
+
+/// Splits comma-separated column lists.
+fn column_list(columns: &Option<Vec<String>>) -> Vec<String> {
+    columns
+        .as_deref()
+        .map(crate::utils::parse_select_columns)
+        .unwrap_or_default()
+}
+
+/// Returns the path of the leaf column named `name`, with nested fields joined by dots.
+fn column_path(descriptor: &SchemaDescriptor, name: &str) -> Result<ColumnPath> {
+    column_index(descriptor, name).map(|i| descriptor.column(i).path().clone())
+}
+
+fn column_index(descriptor: &SchemaDescriptor, name: &str) -> Result<usize> {
+    descriptor
+        .columns()
+        .iter()
+        .position(|column| column.path().string() == name)
+        .ok_or_else(|| {
+            let columns: Vec<String> = descriptor
+                .columns()
+                .iter()
+                .map(|column| column.path().string())
+                .collect();
+            Error::GenericError(format!(
+                "Column '{name}' not found in the Parquet schema; columns are: {}",
+                columns.join(", ")
+            ))
+        })
+}
+
+/// Parses a sorting column: a column name, then optionally `:asc` or `:desc`, and
+/// `:nulls_first` or `:nulls_last`. Nulls come first by default.
+fn sorting_column(descriptor: &SchemaDescriptor, spec: &str) -> Result<SortingColumn> {
+    let mut parts = spec.split(':');
+    let name = parts.next().unwrap_or_default();
+    let mut descending = false;
+    let mut nulls_first = true;
+    for part in parts {
+        match part.to_lowercase().as_str() {
+            "asc" => descending = false,
+            "desc" => descending = true,
+            "nulls_first" => nulls_first = true,
+            "nulls_last" => nulls_first = false,
+            _ => {
+                return Err(Error::GenericError(format!(
+                    "invalid sorting column '{spec}': expected asc, desc, nulls_first, or nulls_last after ':'"
+                )));
+            }
+        }
+    }
+    Ok(SortingColumn {
+        column_idx: column_index(descriptor, name)? as i32,
+        descending,
+        nulls_first,
+    })
+}
+
 #[cfg(test)]
 mod tests {
+    use parquet::file::metadata::ParquetMetaData;
+    use parquet::file::metadata::ParquetMetaDataReader;
+
     use super::*;
     use crate::pipeline::ReadArgs;
 
@@ -92,6 +271,107 @@ mod tests {
         assert_eq!(batch.num_rows(), 3, "Expected 3 rows");
     }
 
+    fn write_userdata(options: ParquetWriteOptions) -> Result<ParquetMetaData> {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("userdata.parquet");
+        let step = WriteParquetStep {
+            args: WriteArgs {
+                path: path.to_str().unwrap().to_string(),
+                no_clobber: false,
+            },
+            options,
+        };
+        let input = ReadParquetStep {
+            args: ReadArgs {
+                path: "fixtures/userdata.parquet".to_string(),
+                limit: None,
+                offset: None,
+            },
+        };
+        step.execute(Box::new(input))?;
+        let file = std::fs::File::open(&path).unwrap();
+        Ok(ParquetMetaDataReader::new()
+            .parse_and_finish(&file)
+            .unwrap())
+    }
+
+    #[test]
+    fn test_write_parquet_with_options() {
+        let metadata = write_userdata(ParquetWriteOptions {
+            compression: ParquetCompression::Zstd,
+            compression_level: Some(9),
+            max_row_group_size: Some(300),
+            no_dictionary: true,
+            dictionary_columns: Some(vec!["gender".to_string()]),
+            statistics: ParquetStatistics::Chunk,
+            bloom_filter_columns: Some(vec!["id,email".to_string()]),
+            sorting_columns: Some(vec![
+                "id:desc".to_string(),
+                "country:nulls_last".to_string(),
+            ]),
+            ..ParquetWriteOptions::default()
+        })
+        .unwrap();
+        assert_eq!(metadata.num_row_groups(), 4);
+        let row_group = metadata.row_group(0);
+        assert_eq!(row_group.num_rows(), 300);
+        let column = |name: &str| {
+            row_group
+                .columns()
+                .iter()
+                .find(|c| c.column_path().string() == name)
+                .unwrap()
+        };
+        // The level is not stored in the file.
+        assert!(matches!(column("id").compression(), Compression::ZSTD(_)));
+        assert!(column("id").bloom_filter_offset().is_some());
+        assert!(column("email").bloom_filter_offset().is_some());
+        assert!(column("country").bloom_filter_offset().is_none());
+        assert!(column("gender").dictionary_page_offset().is_some());
+        assert!(column("country").dictionary_page_offset().is_none());
+        assert_eq!(
+            row_group.sorting_columns().unwrap(),
+            &vec![
+                SortingColumn {
+                    column_idx: 1,
+                    descending: true,
+                    nulls_first: true,
+                },
+                SortingColumn {
+                    column_idx: 8,
+                    descending: false,
+                    nulls_first: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_write_parquet_option_errors() {
+        let unknown = write_userdata(ParquetWriteOptions {
+            bloom_filter_columns: Some(vec!["nope".to_string()]),
+            ..ParquetWriteOptions::default()
+        });
+        assert!(
+            unknown
+                .err()
+                .unwrap()
+                .to_string()
+                .contains("Column 'nope' not found")
+        );
+        let level = write_userdata(ParquetWriteOptions {
+            compression: ParquetCompression::Snappy,
+            compression_level: Some(3),
+            ..ParquetWriteOptions::default()
+        });
+        assert!(level.is_err());
+        let sorting = write_userdata(ParquetWriteOptions {
+            sorting_columns: Some(vec!["id:up".to_string()]),
+            ..ParquetWriteOptions::default()
+        });
+        assert!(sorting.is_err());
+    }
+
     #[test]
     fn test_read_parquet_with_limit() {
         let args = ReadArgs {
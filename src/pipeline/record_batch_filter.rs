@@ -5,8 +5,8 @@ use crate::pipeline::Source;
 use crate::pipeline::Step;
 
 /// A Source that wraps a single RecordBatchReader and yields it on get().
-struct RecordBatchReaderHolder {
-    reader: Option<Box<dyn RecordBatchReader + 'static>>,
+pub(crate) struct RecordBatchReaderHolder {
+    pub(crate) reader: Option<Box<dyn RecordBatchReader + 'static>>,
 }
 
 impl Source<dyn RecordBatchReader + 'static> for RecordBatchReaderHolder {
@@ -0,0 +1,589 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use arrow::array::RecordBatchReader;
+use arrow::array::UInt32Array;
+use arrow::compute::take_record_batch;
+use arrow::datatypes::Schema;
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+use arrow::util::display::ArrayFormatter;
+use arrow::util::display::FormatOptions;
+
+use crate::Error;
+use crate::Result;
+use crate::pipeline::RecordBatchReaderSource;
+use crate::pipeline::Source;
+use crate::pipeline::VecRecordBatchReaderSource;
+
+/// Limit on the size of each part produced by [`SplitParts`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SplitLimit {
+    /// At most this many rows per part.
+    Rows(usize),
+    /// At most this many bytes per part, measured as the size of the written file.
+    Bytes(usize),
+}
+
+/// Most times a part is written while searching for the number of rows that fills it up to a
+/// byte limit; after that the most rows known to fit are kept.
+const MAX_PART_WRITES: usize = 5;
+
+struct SplitState {
+    reader: Box<dyn RecordBatchReader + 'static>,
+    schema: SchemaRef,
+    /// Rows read from the input that belong to the next part.
+    pending: VecDeque<RecordBatch>,
+    limit: SplitLimit,
+    /// Size of the last part written under a byte limit, divided by its rows.
+    bytes_per_row: Option<f64>,
+}
+
+impl SplitState {
+    fn next_batch(&mut self) -> Option<arrow::error::Result<RecordBatch>> {
+        if let Some(batch) = self.pending.pop_front() {
+            return Some(Ok(batch));
+        }
+        loop {
+            match self.reader.next()? {
+                Ok(batch) if batch.num_rows() == 0 => continue,
+                res => return Some(res),
+            }
+        }
+    }
+
+    /// Reads from the input until at least `rows` rows are pending, returning how many are.
+    fn fill(&mut self, rows: usize) -> Result<usize> {
+        let mut pending: usize = self.pending.iter().map(|b| b.num_rows()).sum();
+        while pending < rows {
+            match self.reader.next() {
+                None => break,
+                Some(Err(e)) => return Err(Error::ArrowError(e)),
+                Some(Ok(batch)) => {
+                    pending += batch.num_rows();
+                    if batch.num_rows() > 0 {
+                        self.pending.push_back(batch);
+                    }
+                }
+            }
+        }
+        Ok(pending)
+    }
+
+    /// Returns the first `rows` pending rows.
+    fn head(&self, rows: usize) -> Vec<RecordBatch> {
+        let mut head = Vec::new();
+        let mut remaining = rows;
+        for batch in &self.pending {
+            if remaining == 0 {
+                break;
+            }
+            let len = batch.num_rows().min(remaining);
+            head.push(batch.slice(0, len));
+            remaining -= len;
+        }
+        head
+    }
+
+    /// Drops the first `rows` pending rows.
+    fn consume(&mut self, rows: usize) {
+        let mut remaining = rows;
+        while remaining > 0 {
+            let Some(batch) = self.pending.pop_front() else {
+                break;
+            };
+            if batch.num_rows() > remaining {
+                self.pending
+                    .push_front(batch.slice(remaining, batch.num_rows() - remaining));
+                break;
+            }
+            remaining -= batch.num_rows();
+        }
+    }
+
+    /// Guesses how many rows fit in `max` bytes, from the last part written or, for the first
+    /// part, from the in-memory size of the first pending batch.
+    fn estimate_rows(&self, max: usize) -> usize {
+        let bytes_per_row = self.bytes_per_row.unwrap_or_else(|| {
+            self.pending
+                .front()
+                .map(|b| batch_size(b) as f64 / b.num_rows() as f64)
+                .unwrap_or(1.0)
+        });
+        ((max as f64 / bytes_per_row.max(1.0)) as usize).max(1)
+    }
+}
+
+/// Splits a stream of record batches into consecutive parts, each no larger than a
+/// [`SplitLimit`], and hands each part to a writer.
+///
+/// Under a row limit each part is streamed to the writer. Under a byte limit the rows of a
+/// part are held in memory while it is written, possibly several times, to find how many rows
+/// fit.
+pub struct SplitParts {
+    state: Rc<RefCell<SplitState>>,
+}
+
+impl SplitParts {
+    pub fn new(reader: Box<dyn RecordBatchReader + 'static>, limit: SplitLimit) -> Self {
+        let schema = reader.schema();
+        Self {
+            state: Rc::new(RefCell::new(SplitState {
+                reader,
+                schema,
+                pending: VecDeque::new(),
+                limit,
+                bytes_per_row: None,
+            })),
+        }
+    }
+
+    /// Writes the next part with `write`, returning `false` once the input is exhausted.
+    ///
+    /// `write` writes the rows of the part it is given and returns the size of the written
+    /// file. Under a byte limit it may be called several times for one part, with fewer or
+    /// more rows each time; only the output of the last call is to be kept. That part is no
+    /// larger than the limit unless it is a single row that alone exceeds it.
+    pub fn write_next_part<E>(
+        &mut self,
+        mut write: impl FnMut(RecordBatchReaderSource) -> std::result::Result<u64, E>,
+    ) -> std::result::Result<bool, E>
+    where
+        E: From<Error>,
+    {
+        let limit = self.state.borrow().limit;
+        match limit {
+            SplitLimit::Rows(_) => {
+                let mut state = self.state.borrow_mut();
+                match state.next_batch() {
+                    None => return Ok(false),
+                    Some(Err(e)) => return Err(Error::ArrowError(e).into()),
+                    Some(Ok(batch)) => state.pending.push_front(batch),
+                }
+                drop(state);
+                write(Box::new(PartSource {
+                    state: Some(self.state.clone()),
+                }))?;
+                Ok(true)
+            }
+            SplitLimit::Bytes(max) => self.write_sized_part(max, write),
+        }
+    }
+
+    fn write_sized_part<E>(
+        &mut self,
+        max: usize,
+        mut write: impl FnMut(RecordBatchReaderSource) -> std::result::Result<u64, E>,
+    ) -> std::result::Result<bool, E>
+    where
+        E: From<Error>,
+    {
+        let mut state = self.state.borrow_mut();
+        if state.fill(1)? == 0 {
+            return Ok(false);
+        }
+        let max = max as u64;
+        let mut rows = state.estimate_rows(max as usize);
+        // The most rows known to fit, and the fewest known not to.
+        let mut fits = 0;
+        let mut too_big = usize::MAX;
+        let mut writes = 0;
+        let size = loop {
+            let pending = state.fill(rows)?;
+            let exhausted = pending < rows;
+            rows = rows.min(pending);
+            let size = write(Box::new(VecRecordBatchReaderSource::new(state.head(rows))))?;
+            writes += 1;
+            // The next guess scales the rows by how far the file is from the limit.
+            let scaled = (rows as f64 * max as f64 / size.max(1) as f64) as usize;
+            if size <= max {
+                fits = rows;
+                if exhausted
+                    || fits + 1 >= too_big
+                    || writes >= MAX_PART_WRITES
+                    || size * 20 >= max * 19
+                {
+                    break size;
+                }
+                rows = scaled.clamp(fits + 1, too_big - 1);
+            } else {
+                too_big = rows;
+                // A single row larger than the limit gets a part of its own.
+                if rows == 1 {
+                    break size;
+                }
+                let fewest = fits.clamp(1, rows - 1);
+                rows = if writes >= MAX_PART_WRITES {
+                    fewest
+                } else {
+                    scaled.clamp(fewest, rows - 1)
+                };
+            }
+        };
+        state.bytes_per_row = Some(size as f64 / rows as f64);
+        state.consume(rows);
+        Ok(true)
+    }
+}
+
+struct PartSource {
+    state: Option<Rc<RefCell<SplitState>>>,
+}
+
+impl Source<dyn RecordBatchReader + 'static> for PartSource {
+    fn get(&mut self) -> Result<Box<dyn RecordBatchReader + 'static>> {
+        let state = self
+            .state
+            .take()
+            .ok_or_else(|| Error::GenericError("Reader already taken".to_string()))?;
+        Ok(Box::new(PartReader {
+            state,
+            rows: 0,
+            done: false,
+        }))
+    }
+}
+
+/// Record batch reader over a single part under a row limit; stops once the part is full and
+/// leaves the remaining rows for the next part.
+struct PartReader {
+    state: Rc<RefCell<SplitState>>,
+    rows: usize,
+    done: bool,
+}
+
+impl RecordBatchReader for PartReader {
+    fn schema(&self) -> SchemaRef {
+        self.state.borrow().schema.clone()
+    }
+}
+
+impl Iterator for PartReader {
+    type Item = arrow::error::Result<RecordBatch>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let mut state = self.state.borrow_mut();
+        let SplitLimit::Rows(max) = state.limit else {
+            return None;
+        };
+        let batch = match state.next_batch()? {
+            Ok(batch) => batch,
+            Err(e) => return Some(Err(e)),
+        };
+        let num_rows = batch.num_rows();
+        let fits = max.saturating_sub(self.rows);
+        if fits >= num_rows {
+            self.rows += num_rows;
+            return Some(Ok(batch));
+        }
+        self.done = true;
+        if fits == 0 {
+            state.pending.push_front(batch);
+            return None;
+        }
+        state.pending.push_front(batch.slice(fits, num_rows - fits));
+        self.rows += fits;
+        Some(Ok(batch.slice(0, fits)))
+    }
+}
+
+/// Returns the in-memory size of the rows in `batch`, counting only the sliced range.
+fn batch_size(batch: &RecordBatch) -> usize {
+    batch
+        .columns()
+        .iter()
+        .map(|c| c.to_data().get_slice_memory_size().unwrap_or_default())
+        .sum()
+}
+
+/// Splits record batches into the rows for each distinct value of a column. Values are
+/// rendered as strings, nulls as `null`.
+pub struct KeyPartitioner {
+    index: usize,
+    options: FormatOptions<'static>,
+}
+
+impl KeyPartitioner {
+    pub fn try_new(schema: &Schema, column: &str) -> Result<Self> {
+        let index = schema.index_of(column).map_err(|_| {
+            Error::PipelinePlanningError(format!("Column '{column}' not found in the input"))
+        })?;
+        Ok(Self {
+            index,
+            options: FormatOptions::default().with_null("null"),
+        })
+    }
+
+    /// Returns the rows of `batch` for each distinct value, in order of first appearance.
+    pub fn partition(&self, batch: &RecordBatch) -> Result<Vec<(String, RecordBatch)>> {
+        let formatter = ArrayFormatter::try_new(batch.column(self.index).as_ref(), &self.options)?;
+        let mut rows: Vec<(String, Vec<u32>)> = Vec::new();
+        let mut positions: HashMap<String, usize> = HashMap::new();
+        for row in 0..batch.num_rows() {
+            let key = formatter.value(row).to_string();
+            let position = *positions.entry(key.clone()).or_insert_with(|| {
+                rows.push((key, Vec::new()));
+                rows.len() - 1
+            });
+            rows[position].1.push(row as u32);
+        }
+        rows.into_iter()
+            .map(|(key, indices)| {
+                let taken = take_record_batch(batch, &UInt32Array::from(indices))?;
+                Ok((key, taken))
+            })
+            .collect()
+    }
+}
+
+/// Template for the output file names of a split, e.g. `out/part-{n:05}.parquet`.
+///
+/// `{n}` is replaced by the zero-based part number, optionally zero-padded to a width with
+/// `{n:0W}`, and `{key}` by the value of the `--by` column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputTemplate {
+    segments: Vec<TemplateSegment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TemplateSegment {
+    Literal(String),
+    Number { width: usize },
+    Key,
+}
+
+impl OutputTemplate {
+    pub fn parse(template: &str) -> Result<Self> {
+        let invalid = |message: String| {
+            Error::PipelinePlanningError(format!("Invalid output template '{template}': {message}"))
+        };
+        let mut segments = Vec::new();
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            if start > 0 {
+                segments.push(TemplateSegment::Literal(rest[..start].to_string()));
+            }
+            let end = rest[start..]
+                .find('}')
+                .map(|i| start + i)
+                .ok_or_else(|| invalid("unclosed '{'".to_string()))?;
+            let placeholder = &rest[start + 1..end];
+            let segment = match placeholder.split_once(':') {
+                None if placeholder == "n" => TemplateSegment::Number { width: 0 },
+                None if placeholder == "key" => TemplateSegment::Key,
+                Some(("n", spec)) => {
+                    let width = spec
+                        .strip_prefix('0')
+                        .and_then(|w| w.parse().ok())
+                        .ok_or_else(|| {
+                            invalid(format!(
+                                "unsupported format '{spec}' for {{n}}, expected e.g. {{n:05}}"
+                            ))
+                        })?;
+                    TemplateSegment::Number { width }
+                }
+                _ => {
+                    return Err(invalid(format!(
+                        "unknown placeholder '{{{placeholder}}}', expected {{n}}, {{n:0W}}, or {{key}}"
+                    )));
+                }
+            };
+            segments.push(segment);
+            rest = &rest[end + 1..];
+        }
+        if !rest.is_empty() {
+            segments.push(TemplateSegment::Literal(rest.to_string()));
+        }
+        Ok(Self { segments })
+    }
+
+    /// Returns true if the template contains a `{n}` placeholder.
+    pub fn has_number(&self) -> bool {
+        self.segments
+            .iter()
+            .any(|s| matches!(s, TemplateSegment::Number { .. }))
+    }
+
+    /// Returns true if the template contains a `{key}` placeholder.
+    pub fn has_key(&self) -> bool {
+        self.segments.contains(&TemplateSegment::Key)
+    }
+
+    /// Renders the file name of part `n`, substituting `key` (made safe for use in a file name)
+    /// for `{key}`.
+    pub fn render(&self, n: usize, key: Option<&str>) -> String {
+        let mut out = String::new();
+        for segment in &self.segments {
+            match segment {
+                TemplateSegment::Literal(s) => out.push_str(s),
+                TemplateSegment::Number { width } => out.push_str(&format!("{n:0width$}")),
+                TemplateSegment::Key => out.push_str(&sanitize_file_name(key.unwrap_or(""))),
+            }
+        }
+        out
+    }
+}
+
+/// Replaces characters that are not allowed in file names (or would add path components); an
+/// empty value becomes `_empty`, and `.` and `..` become `_dot` and `_dotdot` so that a value
+/// cannot name the current or parent directory.
+fn sanitize_file_name(s: &str) -> String {
+    match s {
+        "" => return "_empty".to_string(),
+        "." => return "_dot".to_string(),
+        ".." => return "_dotdot".to_string(),
+        _ => {}
+    }
+    s.chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::array::Int32Array;
+    use arrow::array::StringArray;
+    use arrow::datatypes::DataType;
+    use arrow::datatypes::Field;
+
+    use super::*;
+
+    fn reader(batches: Vec<Vec<i32>>) -> Box<dyn RecordBatchReader + 'static> {
+        let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int32, false)]));
+        let batches = batches
+            .into_iter()
+            .map(|ids| {
+                RecordBatch::try_new(schema.clone(), vec![Arc::new(Int32Array::from(ids))]).unwrap()
+            })
+            .collect();
+        VecRecordBatchReaderSource::new(batches).get().unwrap()
+    }
+
+    /// Writes every part with a writer whose files take `header` bytes plus `row` bytes per
+    /// row, returning the rows and size of each part kept and the number of writes.
+    fn write_parts(mut parts: SplitParts, header: u64, row: u64) -> (Vec<(usize, u64)>, usize) {
+        let mut kept = Vec::new();
+        let mut writes = 0;
+        loop {
+            let mut last = (0, 0);
+            let more = parts
+                .write_next_part(|mut part| {
+                    let rows = part.get()?.map(|b| b.unwrap().num_rows()).sum::<usize>();
+                    writes += 1;
+                    last = (rows, header + row * rows as u64);
+                    Ok::<_, Error>(last.1)
+                })
+                .unwrap();
+            if !more {
+                break;
+            }
+            kept.push(last);
+        }
+        (kept, writes)
+    }
+
+    fn part_rows(parts: SplitParts) -> Vec<usize> {
+        write_parts(parts, 0, 0)
+            .0
+            .iter()
+            .map(|(rows, _)| *rows)
+            .collect()
+    }
+
+    #[test]
+    fn test_split_by_rows() {
+        let parts = SplitParts::new(
+            reader(vec![vec![1, 2, 3], vec![4, 5, 6, 7]]),
+            SplitLimit::Rows(3),
+        );
+        assert_eq!(part_rows(parts), vec![3, 3, 1]);
+    }
+
+    #[test]
+    fn test_split_by_bytes() {
+        // Files of 100 bytes plus 10 per row fit 90 rows in 1000 bytes.
+        let ids: Vec<Vec<i32>> = (0..25).map(|i| (i * 10..i * 10 + 10).collect()).collect();
+        let parts = SplitParts::new(reader(ids), SplitLimit::Bytes(1000));
+        let (kept, writes) = write_parts(parts, 100, 10);
+        assert_eq!(kept, vec![(90, 1000), (90, 1000), (70, 800)]);
+        // Once a part has been measured, later parts are written once.
+        assert!(writes <= MAX_PART_WRITES + 2, "{writes} writes");
+        // A single row larger than the limit still gets its own part.
+        let parts = SplitParts::new(reader(vec![vec![1, 2]]), SplitLimit::Bytes(1));
+        assert_eq!(write_parts(parts, 0, 5).0, vec![(1, 5), (1, 5)]);
+    }
+
+    #[test]
+    fn test_partition() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("country", DataType::Utf8, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3, 4])),
+                Arc::new(StringArray::from(vec![
+                    Some("NZ"),
+                    None,
+                    Some("US"),
+                    Some("NZ"),
+                ])),
+            ],
+        )
+        .unwrap();
+        let partitioner = KeyPartitioner::try_new(&schema, "country").unwrap();
+        let summary: Vec<(String, Vec<i32>)> = partitioner
+            .partition(&batch)
+            .unwrap()
+            .into_iter()
+            .map(|(key, rows)| {
+                let ids = rows
+                    .column(0)
+                    .as_any()
+                    .downcast_ref::<Int32Array>()
+                    .unwrap()
+                    .values()
+                    .to_vec();
+                (key, ids)
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("NZ".to_string(), vec![1, 4]),
+                ("null".to_string(), vec![2]),
+                ("US".to_string(), vec![3]),
+            ]
+        );
+        assert!(KeyPartitioner::try_new(&schema, "city").is_err());
+    }
+
+    #[test]
+    fn test_output_template() {
+        let template = OutputTemplate::parse("out/part-{n:05}.parquet").unwrap();
+        assert!(template.has_number());
+        assert!(!template.has_key());
+        assert_eq!(template.render(7, None), "out/part-00007.parquet");
+        let template = OutputTemplate::parse("{key}/data-{n}.csv").unwrap();
+        assert_eq!(template.render(12, Some("a/b")), "a_b/data-12.csv");
+        assert_eq!(template.render(0, Some("")), "_empty/data-0.csv");
+        assert_eq!(template.render(1, Some("..")), "_dotdot/data-1.csv");
+        assert_eq!(template.render(2, Some(".")), "_dot/data-2.csv");
+        assert_eq!(template.render(3, Some("../..")), ".._../data-3.csv");
+        assert!(OutputTemplate::parse("part-{x}.csv").is_err());
+        assert!(OutputTemplate::parse("part-{n:5}.csv").is_err());
+        assert!(OutputTemplate::parse("part-{n.csv").is_err());
+    }
+}
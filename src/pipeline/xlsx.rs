@@ -1,30 +1,64 @@
 use arrow::array::Array;
 use arrow::array::ArrayRef;
-use arrow::array::BooleanArray;
-use arrow::array::Float32Array;
-use arrow::array::Float64Array;
-use arrow::array::Int8Array;
-use arrow::array::Int16Array;
-use arrow::array::Int32Array;
-use arrow::array::Int64Array;
-use arrow::array::StringArray;
-use arrow::array::UInt8Array;
-use arrow::array::UInt16Array;
-use arrow::array::UInt32Array;
-use arrow::array::UInt64Array;
+use arrow::array::AsArray;
+use arrow::array::RecordBatchReader;
+use arrow::array::timezone::Tz;
+use arrow::datatypes::ArrowTimestampType;
 use arrow::datatypes::DataType;
+use arrow::datatypes::Date32Type;
+use arrow::datatypes::Date64Type;
+use arrow::datatypes::DurationMicrosecondType;
+use arrow::datatypes::DurationMillisecondType;
+use arrow::datatypes::DurationNanosecondType;
+use arrow::datatypes::DurationSecondType;
+use arrow::datatypes::Float16Type;
+use arrow::datatypes::Float32Type;
+use arrow::datatypes::Float64Type;
+use arrow::datatypes::Int8Type;
+use arrow::datatypes::Int16Type;
+use arrow::datatypes::Int32Type;
+use arrow::datatypes::Int64Type;
+use arrow::datatypes::Time32MillisecondType;
+use arrow::datatypes::Time32SecondType;
+use arrow::datatypes::Time64MicrosecondType;
+use arrow::datatypes::Time64NanosecondType;
+use arrow::datatypes::TimeUnit;
+use arrow::datatypes::TimestampMicrosecondType;
+use arrow::datatypes::TimestampMillisecondType;
+use arrow::datatypes::TimestampNanosecondType;
+use arrow::datatypes::TimestampSecondType;
+use arrow::datatypes::UInt8Type;
+use arrow::datatypes::UInt16Type;
+use arrow::datatypes::UInt32Type;
+use arrow::datatypes::UInt64Type;
+use chrono::Datelike;
+use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+use chrono::NaiveTime;
+use chrono::Timelike;
+use rust_xlsxwriter::Format;
 use rust_xlsxwriter::Workbook;
 use rust_xlsxwriter::Worksheet;
 
 use crate::Error;
 use crate::Result;
+use crate::cli::XlsxOptions;
 use crate::pipeline::RecordBatchReaderSource;
 use crate::pipeline::Step;
 use crate::pipeline::WriteArgs;
+use crate::pipeline::output::OutputFile;
 
-/// Pipeline step that writes record batches to an Excel (.xlsx) file.
+/// The maximum number of rows in an Excel worksheet, including the header row.
+pub const EXCEL_MAX_ROWS: u32 = 1_048_576;
+
+/// The maximum length of an Excel worksheet name.
+const MAX_SHEET_NAME_LEN: usize = 31;
+
+/// Pipeline step that writes record batches to an Excel (.xlsx) file, as a worksheet named
+/// by `--sheet-name` (`Sheet1` by default) and further worksheets past Excel's row limit.
 pub struct WriteXlsxStep {
     pub args: WriteArgs,
+    pub options: XlsxOptions,
 }
 
 /// Result of successfully writing an XLSX file.
@@ -34,167 +68,653 @@ impl Step for WriteXlsxStep {
     type Input = RecordBatchReaderSource;
     type Output = WriteXlsxResult;
 
-    fn execute(self, mut input: Self::Input) -> Result<Self::Output> {
-        let path = self.args.path.as_str();
-        let mut workbook = Workbook::new();
-        let worksheet = workbook.add_worksheet();
+    fn execute(self, input: Self::Input) -> Result<Self::Output> {
+        if self.options.sheet_names.len() > 1 {
+            return Err(Error::GenericError(
+                "--sheet-name can only be given once for a single sheet".to_string(),
+            ));
+        }
+        let name = self
+            .options
+            .sheet_names
+            .first()
+            .cloned()
+            .unwrap_or_else(|| "Sheet1".to_string());
+        WriteXlsxSheetsStep { args: self.args }.execute(vec![XlsxSheet {
+            name,
+            source: input,
+        }])
+    }
+}
+
+/// A worksheet to write: its name and the rows to write to it.
+pub struct XlsxSheet {
+    pub name: String,
+    pub source: RecordBatchReaderSource,
+}
 
-        let reader = input.get()?;
-        let schema = reader.schema();
-        let column_names: Vec<&str> = schema.fields().iter().map(|f| f.name().as_str()).collect();
+/// Pipeline step that writes several sources to one Excel (.xlsx) file, each as its own
+/// worksheet.
+pub struct WriteXlsxSheetsStep {
+    pub args: WriteArgs,
+}
+
+impl Step for WriteXlsxSheetsStep {
+    type Input = Vec<XlsxSheet>;
+    type Output = WriteXlsxResult;
 
-        let mut excel_row: u32 = 0;
-        for (col, name) in column_names.iter().enumerate() {
-            worksheet.write_string(excel_row, col as u16, *name)?;
+    fn execute(self, input: Self::Input) -> Result<Self::Output> {
+        let mut workbook = Workbook::new();
+        for mut sheet in input {
+            let reader = sheet.source.get()?;
+            add_sheets(&mut workbook, &sheet.name, reader, EXCEL_MAX_ROWS)?;
         }
-        excel_row += 1;
-
-        for batch in reader {
-            let batch = batch.map_err(Error::ArrowError)?;
-            let batch_row_count = batch.num_rows();
-            for batch_row in 0..batch_row_count {
-                for (col, array) in batch.columns().iter().enumerate() {
-                    write_arrow_cell(worksheet, excel_row, col as u16, array, batch_row)?;
-                }
-                excel_row += 1;
+        let mut output = OutputFile::create(&self.args.path, self.args.no_clobber)?;
+        workbook.save_to_writer(&mut output)?;
+        output.commit()?;
+        Ok(WriteXlsxResult {})
+    }
+}
+
+/// Adds the rows of a reader to a workbook as a worksheet with a bold, frozen header row, an
+/// autofilter, and fitted column widths. Past `max_rows` rows, including the header, the
+/// rows continue on a new worksheet named like `Sales (2)`.
+fn add_sheets(
+    workbook: &mut Workbook,
+    name: &str,
+    reader: Box<dyn RecordBatchReader>,
+    max_rows: u32,
+) -> Result<()> {
+    let schema = reader.schema();
+    let header: Vec<&str> = schema.fields().iter().map(|f| f.name().as_str()).collect();
+    let formats: Vec<Option<Format>> = schema
+        .fields()
+        .iter()
+        .map(|f| number_format(f.data_type()))
+        .collect();
+
+    let mut part = 1;
+    let mut worksheet = new_worksheet(workbook, name, part, &header)?;
+    let mut excel_row: u32 = 1;
+    for batch in reader {
+        let batch = batch.map_err(Error::ArrowError)?;
+        let columns = batch
+            .columns()
+            .iter()
+            .map(resolve_dictionary)
+            .collect::<Result<Vec<_>>>()?;
+        for batch_row in 0..batch.num_rows() {
+            if excel_row == max_rows {
+                finish_worksheet(&mut worksheet, excel_row, header.len())?;
+                workbook.push_worksheet(worksheet);
+                part += 1;
+                worksheet = new_worksheet(workbook, name, part, &header)?;
+                excel_row = 1;
             }
+            for (col, array) in columns.iter().enumerate() {
+                write_arrow_cell(
+                    &mut worksheet,
+                    excel_row,
+                    col as u16,
+                    array.as_ref(),
+                    batch_row,
+                    formats[col].as_ref(),
+                )?;
+            }
+            excel_row += 1;
         }
+    }
+    finish_worksheet(&mut worksheet, excel_row, header.len())?;
+    workbook.push_worksheet(worksheet);
+    Ok(())
+}
 
-        workbook.save(path)?;
-        Ok(WriteXlsxResult {})
+/// Creates a worksheet with a bold header row, frozen so that it stays visible. The first
+/// part of a sheet is named `name`; later parts get a ` (2)`, ` (3)`, ... suffix.
+fn new_worksheet(
+    workbook: &mut Workbook,
+    name: &str,
+    part: usize,
+    header: &[&str],
+) -> Result<Worksheet> {
+    let mut worksheet = Worksheet::new();
+    let name = if part == 1 {
+        name.to_string()
+    } else {
+        with_suffix(name, &format!(" ({part})"))
+    };
+    if workbook
+        .worksheets()
+        .iter()
+        .any(|w| w.name().eq_ignore_ascii_case(&name))
+    {
+        return Err(Error::GenericError(format!(
+            "Duplicate sheet name '{name}'"
+        )));
+    }
+    worksheet.set_name(name)?;
+    let bold = Format::new().set_bold();
+    for (col, name) in header.iter().enumerate() {
+        worksheet.write_string_with_format(0, col as u16, *name, &bold)?;
     }
+    worksheet.set_freeze_panes(1, 0)?;
+    Ok(worksheet)
 }
 
-/// Writes a single Arrow array cell value to an Excel worksheet cell.
-fn write_arrow_cell(
-    worksheet: &mut Worksheet,
-    row: u32,
-    col: u16,
-    array: &ArrayRef,
-    index: usize,
-) -> Result<()> {
-    if array.is_null(index) {
-        worksheet.write_string(row, col, "")?;
-        return Ok(());
+/// Adds an autofilter over the header and `rows` rows, and fits the column widths.
+fn finish_worksheet(worksheet: &mut Worksheet, rows: u32, columns: usize) -> Result<()> {
+    if columns > 0 {
+        worksheet.autofilter(0, 0, rows - 1, (columns - 1) as u16)?;
+    }
+    worksheet.autofit();
+    Ok(())
+}
+
+/// Appends a suffix to a sheet name, shortening the name to keep within Excel's limit.
+fn with_suffix(name: &str, suffix: &str) -> String {
+    let keep = MAX_SHEET_NAME_LEN.saturating_sub(suffix.chars().count());
+    let name: String = name.chars().take(keep).collect();
+    format!("{}{suffix}", name.trim_end())
+}
+
+/// Makes a valid sheet name from a file name: its stem, with the characters Excel does not
+/// allow in sheet names replaced by `_`, shortened to 31 characters, and given a ` (2)`,
+/// ` (3)`, ... suffix if it is one of the `taken` names.
+pub fn sheet_name_for_path(path: &str, taken: &[String]) -> String {
+    let stem = std::path::Path::new(path)
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let name: String = stem
+        .chars()
+        .map(|c| match c {
+            '[' | ']' | ':' | '*' | '?' | '/' | '\\' => '_',
+            c => c,
+        })
+        .take(MAX_SHEET_NAME_LEN)
+        .collect();
+    let name = match name.trim_matches('\'') {
+        "" => "Sheet",
+        name => name,
+    };
+    let is_taken = |candidate: &str| taken.iter().any(|t| t.eq_ignore_ascii_case(candidate));
+    if !is_taken(name) {
+        return name.to_string();
     }
+    (2..)
+        .map(|n| with_suffix(name, &format!(" ({n})")))
+        .find(|candidate| !is_taken(candidate))
+        .expect("there are fewer taken names than suffixes")
+}
 
+/// Returns the Excel number format for values of an Arrow type, if the default `General`
+/// format would not show them well: integers without switching to scientific notation,
+/// decimals with their scale, and dates, times, and durations rather than serial numbers.
+/// Excel shows at most milliseconds, though finer values are kept as far as its precision
+/// allows.
+fn number_format(data_type: &DataType) -> Option<Format> {
+    let num_format = match data_type {
+        DataType::Int8
+        | DataType::Int16
+        | DataType::Int32
+        | DataType::Int64
+        | DataType::UInt8
+        | DataType::UInt16
+        | DataType::UInt32
+        | DataType::UInt64 => "0".to_string(),
+        DataType::Decimal32(_, scale)
+        | DataType::Decimal64(_, scale)
+        | DataType::Decimal128(_, scale)
+        | DataType::Decimal256(_, scale) => match usize::try_from(*scale) {
+            Ok(scale) if scale > 0 => format!("0.{}", "0".repeat(scale)),
+            _ => "0".to_string(),
+        },
+        DataType::Date32 | DataType::Date64 => "yyyy-mm-dd".to_string(),
+        DataType::Timestamp(TimeUnit::Second, _) => "yyyy-mm-dd hh:mm:ss".to_string(),
+        DataType::Timestamp(_, _) => "yyyy-mm-dd hh:mm:ss.000".to_string(),
+        DataType::Time32(TimeUnit::Second) => "hh:mm:ss".to_string(),
+        DataType::Time32(_) | DataType::Time64(_) => "hh:mm:ss.000".to_string(),
+        DataType::Duration(TimeUnit::Second) => "[h]:mm:ss".to_string(),
+        DataType::Duration(_) => "[h]:mm:ss.000".to_string(),
+        DataType::Dictionary(_, value_type) => return number_format(value_type),
+        _ => return None,
+    };
+    Some(Format::new().set_num_format(num_format))
+}
+
+/// Casts a dictionary-encoded column to its value type, so that its values are written
+/// rather than its keys.
+fn resolve_dictionary(array: &ArrayRef) -> Result<ArrayRef> {
     match array.data_type() {
-        DataType::Boolean => {
-            let arr = array.as_any().downcast_ref::<BooleanArray>().unwrap();
-            worksheet.write(row, col, arr.value(index))?;
-        }
-        DataType::Int8 => {
-            let arr = array.as_any().downcast_ref::<Int8Array>().unwrap();
-            worksheet.write(row, col, arr.value(index) as i64)?;
-        }
-        DataType::Int16 => {
-            let arr = array.as_any().downcast_ref::<Int16Array>().unwrap();
-            worksheet.write(row, col, arr.value(index) as i64)?;
-        }
-        DataType::Int32 => {
-            let arr = array.as_any().downcast_ref::<Int32Array>().unwrap();
-            worksheet.write(row, col, arr.value(index) as i64)?;
-        }
-        DataType::Int64 => {
-            let arr = array.as_any().downcast_ref::<Int64Array>().unwrap();
-            worksheet.write(row, col, arr.value(index))?;
-        }
-        DataType::UInt8 => {
-            let arr = array.as_any().downcast_ref::<UInt8Array>().unwrap();
-            worksheet.write(row, col, arr.value(index) as i64)?;
-        }
-        DataType::UInt16 => {
-            let arr = array.as_any().downcast_ref::<UInt16Array>().unwrap();
-            worksheet.write(row, col, arr.value(index) as i64)?;
+        DataType::Dictionary(_, value_type) => {
+            arrow::compute::cast(array, value_type).map_err(Error::ArrowError)
         }
-        DataType::UInt32 => {
-            let arr = array.as_any().downcast_ref::<UInt32Array>().unwrap();
-            worksheet.write(row, col, arr.value(index) as i64)?;
+        _ => Ok(array.clone()),
+    }
+}
+
+/// The value of an Excel cell.
+#[derive(Debug, PartialEq)]
+enum CellValue {
+    Blank,
+    Boolean(bool),
+    /// A number, or the serial number of a date, time, or duration: days since 1900, or
+    /// fractions of a day.
+    Number(f64),
+    String(String),
+}
+
+/// Integers with more digits than this are written as text, since Excel keeps only 15
+/// significant digits and would round e.g. 64-bit IDs.
+const EXCEL_MAX_EXACT_INTEGER: u128 = 999_999_999_999_999;
+
+/// Converts the value at `index` of an array into an Excel cell value. Numbers and decimals
+/// become numbers; dates, timestamps, times, and non-negative durations become serial
+/// numbers, with timestamps in the local time of their time zone, if any; and nulls are
+/// blank. Other values, such as binary, nested values, and dates outside the years Excel
+/// supports (1900 to 9999), are written as text.
+fn cell_value(array: &dyn Array, index: usize) -> CellValue {
+    if array.is_null(index) {
+        return CellValue::Blank;
+    }
+    let value = match array.data_type() {
+        DataType::Null => return CellValue::Blank,
+        DataType::Boolean => return CellValue::Boolean(array.as_boolean().value(index)),
+        DataType::Int8 => integer_value(array.as_primitive::<Int8Type>().value(index)),
+        DataType::Int16 => integer_value(array.as_primitive::<Int16Type>().value(index)),
+        DataType::Int32 => integer_value(array.as_primitive::<Int32Type>().value(index)),
+        DataType::Int64 => integer_value(array.as_primitive::<Int64Type>().value(index)),
+        DataType::UInt8 => integer_value(array.as_primitive::<UInt8Type>().value(index)),
+        DataType::UInt16 => integer_value(array.as_primitive::<UInt16Type>().value(index)),
+        DataType::UInt32 => integer_value(array.as_primitive::<UInt32Type>().value(index)),
+        DataType::UInt64 => integer_value(array.as_primitive::<UInt64Type>().value(index)),
+        DataType::Float16 => Some(array.as_primitive::<Float16Type>().value(index).to_f64()),
+        DataType::Float32 => Some(array.as_primitive::<Float32Type>().value(index).into()),
+        DataType::Float64 => Some(array.as_primitive::<Float64Type>().value(index)),
+        DataType::Decimal32(_, _)
+        | DataType::Decimal64(_, _)
+        | DataType::Decimal128(_, _)
+        | DataType::Decimal256(_, _) => display_value(array, index).parse().ok(),
+        DataType::Utf8 => return CellValue::String(array.as_string::<i32>().value(index).into()),
+        DataType::LargeUtf8 => {
+            return CellValue::String(array.as_string::<i64>().value(index).into());
         }
-        DataType::UInt64 => {
-            let arr = array.as_any().downcast_ref::<UInt64Array>().unwrap();
-            let v = arr.value(index);
-            if v <= i64::MAX as u64 {
-                worksheet.write(row, col, v as i64)?;
-            } else {
-                worksheet.write_string(row, col, v.to_string())?;
+        DataType::Utf8View => return CellValue::String(array.as_string_view().value(index).into()),
+        DataType::Date32 => array
+            .as_primitive::<Date32Type>()
+            .value_as_datetime(index)
+            .and_then(excel_serial),
+        DataType::Date64 => array
+            .as_primitive::<Date64Type>()
+            .value_as_datetime(index)
+            .and_then(excel_serial),
+        DataType::Timestamp(unit, timezone) => {
+            let timezone = timezone.as_deref();
+            match unit {
+                TimeUnit::Second => local_datetime::<TimestampSecondType>(array, index, timezone),
+                TimeUnit::Millisecond => {
+                    local_datetime::<TimestampMillisecondType>(array, index, timezone)
+                }
+                TimeUnit::Microsecond => {
+                    local_datetime::<TimestampMicrosecondType>(array, index, timezone)
+                }
+                TimeUnit::Nanosecond => {
+                    local_datetime::<TimestampNanosecondType>(array, index, timezone)
+                }
             }
+            .and_then(excel_serial)
         }
-        DataType::Float32 => {
-            let arr = array.as_any().downcast_ref::<Float32Array>().unwrap();
-            worksheet.write(row, col, arr.value(index) as f64)?;
+        DataType::Time32(TimeUnit::Second) => array
+            .as_primitive::<Time32SecondType>()
+            .value_as_time(index)
+            .map(day_fraction),
+        DataType::Time32(_) => array
+            .as_primitive::<Time32MillisecondType>()
+            .value_as_time(index)
+            .map(day_fraction),
+        DataType::Time64(TimeUnit::Microsecond) => array
+            .as_primitive::<Time64MicrosecondType>()
+            .value_as_time(index)
+            .map(day_fraction),
+        DataType::Time64(_) => array
+            .as_primitive::<Time64NanosecondType>()
+            .value_as_time(index)
+            .map(day_fraction),
+        DataType::Duration(unit) => {
+            let (value, per_second) = match unit {
+                TimeUnit::Second => (array.as_primitive::<DurationSecondType>().value(index), 1),
+                TimeUnit::Millisecond => (
+                    array.as_primitive::<DurationMillisecondType>().value(index),
+                    1_000,
+                ),
+                TimeUnit::Microsecond => (
+                    array.as_primitive::<DurationMicrosecondType>().value(index),
+                    1_000_000,
+                ),
+                TimeUnit::Nanosecond => (
+                    array.as_primitive::<DurationNanosecondType>().value(index),
+                    1_000_000_000,
+                ),
+            };
+            // Excel shows negative times as ####.
+            (value >= 0).then(|| value as f64 / per_second as f64 / SECONDS_PER_DAY)
         }
-        DataType::Float64 => {
-            let arr = array.as_any().downcast_ref::<Float64Array>().unwrap();
-            worksheet.write(row, col, arr.value(index))?;
+        _ => None,
+    };
+    match value {
+        Some(value) => CellValue::Number(value),
+        None => CellValue::String(display_value(array, index)),
+    }
+}
+
+fn integer_value(value: impl Into<i128>) -> Option<f64> {
+    let value: i128 = value.into();
+    (value.unsigned_abs() <= EXCEL_MAX_EXACT_INTEGER).then_some(value as f64)
+}
+
+/// Returns the date and time of a timestamp, in the local time of its time zone, if any.
+fn local_datetime<T: ArrowTimestampType>(
+    array: &dyn Array,
+    index: usize,
+    timezone: Option<&str>,
+) -> Option<NaiveDateTime> {
+    let array = array.as_primitive::<T>();
+    match timezone {
+        Some(timezone) => {
+            let timezone: Tz = timezone.parse().ok()?;
+            array
+                .value_as_datetime_with_tz(index, timezone)
+                .map(|datetime| datetime.naive_local())
         }
-        DataType::Utf8 => {
-            let arr = array.as_any().downcast_ref::<StringArray>().unwrap();
-            worksheet.write_string(row, col, arr.value(index))?;
+        None => array.value_as_datetime(index),
+    }
+}
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// Converts a date and time to an Excel serial number: days since 1900-01-00, with the time
+/// as a fraction of a day. Returns `None` outside the years 1900 to 9999.
+fn excel_serial(datetime: NaiveDateTime) -> Option<f64> {
+    let date = datetime.date();
+    if !(1900..=9999).contains(&date.year()) {
+        return None;
+    }
+    let epoch = NaiveDate::from_ymd_opt(1899, 12, 30)?;
+    let mut days = (date - epoch).num_days() as f64;
+    // Excel counts a 29 February 1900, which did not exist, so earlier dates are a day
+    // closer to its epoch.
+    if date < NaiveDate::from_ymd_opt(1900, 3, 1)? {
+        days -= 1.0;
+    }
+    Some(days + day_fraction(datetime.time()))
+}
+
+/// Converts a time of day to a fraction of a day, as Excel stores times.
+fn day_fraction(time: NaiveTime) -> f64 {
+    (time.num_seconds_from_midnight() as f64 + time.nanosecond() as f64 / 1e9) / SECONDS_PER_DAY
+}
+
+/// Writes a single Arrow array cell value to an Excel worksheet cell, with the column's
+/// number format, if any.
+fn write_arrow_cell(
+    worksheet: &mut Worksheet,
+    row: u32,
+    col: u16,
+    array: &dyn Array,
+    index: usize,
+    format: Option<&Format>,
+) -> Result<()> {
+    match (cell_value(array, index), format) {
+        (CellValue::Blank, _) => {}
+        (CellValue::Boolean(value), _) => {
+            worksheet.write_boolean(row, col, value)?;
         }
-        DataType::LargeUtf8 => {
-            let arr = array
-                .as_any()
-                .downcast_ref::<arrow::array::LargeStringArray>()
-                .unwrap();
-            worksheet.write_string(row, col, arr.value(index))?;
+        (CellValue::Number(value), Some(format)) => {
+            worksheet.write_number_with_format(row, col, value, format)?;
         }
-        DataType::Timestamp(_, _) | DataType::Date32 | DataType::Date64 => {
-            if let Some(dt) = arrow_temporal_to_chrono(array, index) {
-                worksheet.write(row, col, &dt)?;
-            } else {
-                worksheet.write_string(row, col, format_arrow_value_unknown(array, index))?;
-            }
+        (CellValue::Number(value), None) => {
+            worksheet.write_number(row, col, value)?;
         }
-        _ => {
-            worksheet.write_string(row, col, format_arrow_value_unknown(array, index))?;
+        (CellValue::String(value), _) => {
+            worksheet.write_string(row, col, value)?;
         }
     }
     Ok(())
 }
 
-/// Converts an Arrow temporal/date array value at the given index to a chrono datetime.
-fn arrow_temporal_to_chrono(array: &ArrayRef, index: usize) -> Option<chrono::NaiveDateTime> {
-    use arrow::array::TimestampMillisecondArray;
+/// Formats an Arrow array value with Arrow's display formatting, for values Excel has no
+/// cell type for.
+fn display_value(array: &dyn Array, index: usize) -> String {
+    arrow::util::display::array_value_to_string(array, index).unwrap_or_else(|_| "-".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::array::BinaryArray;
+    use arrow::array::Date32Array;
+    use arrow::array::Decimal128Array;
+    use arrow::array::DictionaryArray;
+    use arrow::array::DurationSecondArray;
+    use arrow::array::Int32Array;
+    use arrow::array::Int64Array;
+    use arrow::array::Time64NanosecondArray;
+    use arrow::array::TimestampNanosecondArray;
     use arrow::array::TimestampSecondArray;
+    use arrow::array::UInt64Array;
+    use arrow::record_batch::RecordBatch;
 
-    match array.data_type() {
-        DataType::Timestamp(_, _) => {
-            if let Some(arr) = array.as_any().downcast_ref::<TimestampMillisecondArray>() {
-                let ts = arr.value(index);
-                return chrono::DateTime::from_timestamp_millis(ts).map(|dt| dt.naive_utc());
-            }
-            if let Some(arr) = array.as_any().downcast_ref::<TimestampSecondArray>() {
-                let ts = arr.value(index);
-                return chrono::DateTime::from_timestamp(ts, 0).map(|dt| dt.naive_utc());
-            }
-            if let Some(arr) = array
-                .as_any()
-                .downcast_ref::<arrow::array::TimestampMicrosecondArray>()
-            {
-                let ts = arr.value(index) / 1000;
-                return chrono::DateTime::from_timestamp_millis(ts).map(|dt| dt.naive_utc());
-            }
-            if let Some(arr) = array
-                .as_any()
-                .downcast_ref::<arrow::array::TimestampNanosecondArray>()
-            {
-                let ts = arr.value(index) / 1_000_000;
-                return chrono::DateTime::from_timestamp_millis(ts).map(|dt| dt.naive_utc());
-            }
-        }
-        DataType::Date64 => {
-            let arr = array.as_any().downcast_ref::<arrow::array::Date64Array>()?;
-            let ms = arr.value(index);
-            return chrono::DateTime::from_timestamp_millis(ms).map(|dt| dt.naive_utc());
-        }
-        _ => {}
+    use super::*;
+    use crate::pipeline::Source;
+    use crate::pipeline::VecRecordBatchReaderSource;
+
+    fn make_batch(rows: i32) -> RecordBatch {
+        RecordBatch::try_from_iter(vec![
+            (
+                "id",
+                Arc::new(Int32Array::from_iter_values(0..rows)) as ArrayRef,
+            ),
+            (
+                "day",
+                Arc::new(Date32Array::from_iter_values(0..rows)) as ArrayRef,
+            ),
+        ])
+        .unwrap()
+    }
+
+    fn sheet_names(workbook: &mut Workbook) -> Vec<String> {
+        workbook.worksheets().iter().map(|w| w.name()).collect()
+    }
+
+    #[test]
+    fn test_add_sheets_rolls_over_at_max_rows() {
+        let mut workbook = Workbook::new();
+        // A header and 2 rows fit in each sheet, so 5 rows need 3 sheets.
+        let mut source = VecRecordBatchReaderSource::new(vec![make_batch(3), make_batch(2)]);
+        add_sheets(&mut workbook, "Sales", source.get().unwrap(), 3).unwrap();
+        assert_eq!(
+            sheet_names(&mut workbook),
+            vec!["Sales", "Sales (2)", "Sales (3)"]
+        );
+
+        let mut source = VecRecordBatchReaderSource::new(vec![make_batch(2)]);
+        let result = add_sheets(&mut workbook, "sales", source.get().unwrap(), 3);
+        assert!(matches!(result, Err(Error::GenericError(e)) if e.contains("Duplicate")));
+    }
+
+    #[test]
+    fn test_add_sheets_shortens_rollover_names() {
+        let mut workbook = Workbook::new();
+        let name = "A sheet name of exactly 31 char";
+        let mut source = VecRecordBatchReaderSource::new(vec![make_batch(3)]);
+        add_sheets(&mut workbook, name, source.get().unwrap(), 3).unwrap();
+        assert_eq!(
+            sheet_names(&mut workbook),
+            vec![name, "A sheet name of exactly 31 (2)"]
+        );
+    }
+
+    #[test]
+    fn test_invalid_sheet_name() {
+        let mut workbook = Workbook::new();
+        let mut source = VecRecordBatchReaderSource::new(vec![make_batch(1)]);
+        let result = add_sheets(&mut workbook, "Q1/Q2", source.get().unwrap(), 3);
+        assert!(matches!(result, Err(Error::XlsxError(_))));
+    }
+
+    #[test]
+    fn test_sheet_name_for_path() {
+        assert_eq!(sheet_name_for_path("data/sales.parquet", &[]), "sales");
+        assert_eq!(
+            sheet_name_for_path("data/[2024] sales: Q1?.parquet", &[]),
+            "_2024_ sales_ Q1_"
+        );
+        assert_eq!(
+            sheet_name_for_path("a_very_long_file_name_for_one_sheet.avro", &[]),
+            "a_very_long_file_name_for_one_s"
+        );
+        assert_eq!(sheet_name_for_path("'quoted'.orc", &[]), "quoted");
+        let taken = vec!["Sales".to_string(), "sales (2)".to_string()];
+        assert_eq!(sheet_name_for_path("sales.parquet", &taken), "sales (3)");
     }
-    None
-}
 
-/// Formats an Arrow array value as a string for types not directly supported by Excel.
-fn format_arrow_value_unknown(array: &ArrayRef, index: usize) -> String {
-    arrow::util::display::array_value_to_string(array.as_ref(), index)
-        .unwrap_or_else(|_| "-".to_string())
+    #[test]
+    fn test_write_xlsx_sheets() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let path = temp_dir.path().join("report.xlsx");
+        let sheets = ["Orders", "Returns"]
+            .into_iter()
+            .map(|name| XlsxSheet {
+                name: name.to_string(),
+                source: Box::new(VecRecordBatchReaderSource::new(vec![make_batch(2)])),
+            })
+            .collect();
+        let writer = WriteXlsxSheetsStep {
+            args: WriteArgs {
+                path: path.to_str().unwrap().to_string(),
+                no_clobber: false,
+            },
+        };
+        writer.execute(sheets).unwrap();
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn test_excel_serial() {
+        let serial = |y, m, d, h| {
+            excel_serial(
+                NaiveDate::from_ymd_opt(y, m, d)
+                    .unwrap()
+                    .and_hms_opt(h, 0, 0)
+                    .unwrap(),
+            )
+        };
+        assert_eq!(serial(1900, 1, 1, 0), Some(1.0));
+        assert_eq!(serial(1900, 2, 28, 0), Some(59.0));
+        assert_eq!(serial(1900, 3, 1, 0), Some(61.0));
+        assert_eq!(serial(2024, 1, 1, 12), Some(45292.5));
+        assert_eq!(serial(1899, 12, 31, 0), None);
+        assert_eq!(serial(10000, 1, 1, 0), None);
+    }
+
+    #[test]
+    fn test_cell_value_numbers() {
+        let ints = Int64Array::from(vec![42, -999_999_999_999_999, 1_000_000_000_000_000]);
+        assert_eq!(cell_value(&ints, 0), CellValue::Number(42.0));
+        assert_eq!(
+            cell_value(&ints, 1),
+            CellValue::Number(-999_999_999_999_999.0)
+        );
+        assert_eq!(
+            cell_value(&ints, 2),
+            CellValue::String("1000000000000000".to_string())
+        );
+        let unsigned = UInt64Array::from(vec![u64::MAX]);
+        assert_eq!(
+            cell_value(&unsigned, 0),
+            CellValue::String(u64::MAX.to_string())
+        );
+
+        let decimals = Decimal128Array::from(vec![Some(12345), None])
+            .with_precision_and_scale(10, 2)
+            .unwrap();
+        assert_eq!(cell_value(&decimals, 0), CellValue::Number(123.45));
+        assert_eq!(cell_value(&decimals, 1), CellValue::Blank);
+        assert_eq!(
+            number_format(decimals.data_type()),
+            Some(Format::new().set_num_format("0.00"))
+        );
+        assert_eq!(
+            number_format(&DataType::Decimal128(10, -2)),
+            Some(Format::new().set_num_format("0"))
+        );
+    }
+
+    #[test]
+    fn test_cell_value_temporal() {
+        // 2024-01-01T12:00:00Z
+        let noon = 1_704_110_400;
+        let utc = TimestampSecondArray::from(vec![noon]);
+        assert_eq!(cell_value(&utc, 0), CellValue::Number(45292.5));
+        let zoned = TimestampSecondArray::from(vec![noon]).with_timezone("+06:00");
+        assert_eq!(cell_value(&zoned, 0), CellValue::Number(45292.75));
+        let named = TimestampSecondArray::from(vec![noon]).with_timezone("America/New_York");
+        assert_eq!(
+            cell_value(&named, 0),
+            CellValue::Number(45292.5 - 5.0 / 24.0)
+        );
+
+        // Sub-millisecond precision is kept as far as a double allows.
+        let nanos = TimestampNanosecondArray::from(vec![noon * 1_000_000_000 + 250_000]);
+        let CellValue::Number(serial) = cell_value(&nanos, 0) else {
+            panic!("expected a number");
+        };
+        let micros = (serial - 45292.5) * SECONDS_PER_DAY * 1e6;
+        assert!((micros - 250.0).abs() < 1.0, "{micros}");
+
+        let before_1900 = Date32Array::from(vec![-365 * 120]);
+        assert_eq!(
+            cell_value(&before_1900, 0),
+            CellValue::String("1850-01-30".to_string())
+        );
+
+        let times = Time64NanosecondArray::from(vec![18 * 3_600 * 1_000_000_000]);
+        assert_eq!(cell_value(&times, 0), CellValue::Number(0.75));
+
+        let durations = DurationSecondArray::from(vec![36 * 3_600, -60]);
+        assert_eq!(cell_value(&durations, 0), CellValue::Number(1.5));
+        assert_eq!(
+            cell_value(&durations, 1),
+            CellValue::String("-PT60S".to_string())
+        );
+        assert_eq!(
+            number_format(durations.data_type()),
+            Some(Format::new().set_num_format("[h]:mm:ss"))
+        );
+    }
+
+    #[test]
+    fn test_cell_value_other_types() {
+        let dictionary: DictionaryArray<Int32Type> = vec!["a", "b", "a"].into_iter().collect();
+        let dictionary: ArrayRef = Arc::new(dictionary);
+        assert_eq!(
+            number_format(dictionary.data_type()),
+            number_format(&DataType::Utf8)
+        );
+        let values = resolve_dictionary(&dictionary).unwrap();
+        assert_eq!(
+            cell_value(values.as_ref(), 2),
+            CellValue::String("a".to_string())
+        );
+
+        let binary = BinaryArray::from(vec![&b"\x01\xff"[..]]);
+        assert_eq!(
+            cell_value(&binary, 0),
+            CellValue::String("01ff".to_string())
+        );
+
+        let nulls = arrow::array::NullArray::new(1);
+        assert_eq!(cell_value(&nulls, 0), CellValue::Blank);
+    }
 }
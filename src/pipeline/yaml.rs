@@ -3,6 +3,7 @@ use crate::pipeline::RecordBatchReaderSource;
 use crate::pipeline::Step;
 use crate::pipeline::WriteYamlArgs;
 use crate::pipeline::display::write_record_batches_as_yaml;
+use crate::pipeline::output::OutputFile;
 
 /// Pipeline step that writes record batches to a YAML file (sequence of row objects).
 pub struct WriteYamlStep {
@@ -15,9 +16,9 @@ impl Step for WriteYamlStep {
 
     fn execute(self, mut input: Self::Input) -> Result<Self::Output> {
         let path = self.args.path.as_str();
-        let file = std::fs::File::create(path)?;
+        let mut output = OutputFile::create(path, self.args.no_clobber)?;
         let mut reader = input.get()?;
-        write_record_batches_as_yaml(&mut *reader, file, self.args.sparse)?;
-        Ok(())
+        write_record_batches_as_yaml(&mut *reader, &mut output, self.args.sparse)?;
+        output.commit()
     }
 }
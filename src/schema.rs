@@ -0,0 +1,766 @@
+//! Exports Arrow schemas in other schema languages: Avro schema JSON, JSON Schema, SQL DDL,
+//! Protocol Buffers, and the Arrow IPC format.
+
+pub mod tree;
+
+use std::collections::BTreeSet;
+use std::io::Write;
+
+use arrow::datatypes::DataType;
+use arrow::datatypes::Field;
+use arrow::datatypes::Fields;
+use arrow::datatypes::Schema;
+use arrow::ipc::writer::StreamWriter;
+use arrow_avro::schema::AVRO_NAME_METADATA_KEY;
+use arrow_avro::schema::AvroSchema;
+use arrow_avro::schema::SCHEMA_METADATA_KEY;
+use serde::Serialize;
+use serde::Serializer;
+use serde::ser::SerializeMap;
+use serde_json::Value;
+use serde_json::json;
+
+use crate::Error;
+use crate::Result;
+use crate::cli::SqlDialect;
+
+/// Returns the Avro schema (`.avsc`) for `schema` as pretty-printed JSON.
+///
+/// A schema read from an Avro file carries the file's own Avro schema, which is returned as is
+/// unless a record `name` is given. Otherwise the schema is converted, naming the top-level
+/// record `name`.
+pub fn to_avsc(schema: &Schema, name: Option<&str>) -> Result<String> {
+    let mut schema = schema.clone();
+    if let Some(name) = name {
+        schema.metadata.remove(SCHEMA_METADATA_KEY);
+        schema
+            .metadata
+            .insert(AVRO_NAME_METADATA_KEY.to_string(), name.to_string());
+    }
+    let avro = AvroSchema::try_from(&schema)?;
+    // Transcoding, rather than parsing into a `Value`, keeps the keys in their original order.
+    let mut deserializer = serde_json::Deserializer::from_str(&avro.json_string);
+    let mut out = Vec::new();
+    let mut serializer = serde_json::Serializer::pretty(&mut out);
+    serde_transcode::transcode(&mut deserializer, &mut serializer)
+        .map_err(|e| Error::AvroSchemaError(e.to_string()))?;
+    String::from_utf8(out).map_err(|e| Error::AvroSchemaError(e.to_string()))
+}
+
+/// A JSON value whose object keys serialize in insertion order.
+enum JsonNode {
+    Value(Value),
+    Object(Vec<(String, JsonNode)>),
+}
+
+impl Serialize for JsonNode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match self {
+            JsonNode::Value(value) => value.serialize(serializer),
+            JsonNode::Object(entries) => {
+                let mut map = serializer.serialize_map(Some(entries.len()))?;
+                for (key, value) in entries {
+                    map.serialize_entry(key, value)?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+/// Returns a JSON Schema (draft 2020-12) describing the rows of `schema` as they are written
+/// by `datu convert` to JSON.
+pub fn to_json_schema(schema: &Schema, title: &str) -> Result<String> {
+    let mut root = vec![
+        entry(
+            "$schema",
+            json!("https://json-schema.org/draft/2020-12/schema"),
+        ),
+        entry("title", json!(title)),
+    ];
+    root.extend(json_schema_object(schema.fields()));
+    serde_json::to_string_pretty(&JsonNode::Object(root))
+        .map_err(|e| Error::GenericError(e.to_string()))
+}
+
+fn entry(key: &str, value: Value) -> (String, JsonNode) {
+    (key.to_string(), JsonNode::Value(value))
+}
+
+fn json_schema_object(fields: &Fields) -> Vec<(String, JsonNode)> {
+    let properties = fields
+        .iter()
+        .map(|f| (f.name().clone(), json_schema_field(f)))
+        .collect();
+    let required: Vec<&String> = fields
+        .iter()
+        .filter(|f| !f.is_nullable())
+        .map(|f| f.name())
+        .collect();
+    let mut object = vec![
+        entry("type", json!("object")),
+        ("properties".to_string(), JsonNode::Object(properties)),
+    ];
+    if !required.is_empty() {
+        object.push(entry("required", json!(required)));
+    }
+    object
+}
+
+fn json_schema_field(field: &Field) -> JsonNode {
+    let mut node = json_schema_type(field.data_type());
+    if field.is_nullable()
+        && let JsonNode::Object(entries) = &mut node
+        && let Some((_, value)) = entries.iter_mut().find(|(key, _)| key == "type")
+        && let JsonNode::Value(Value::String(t)) = value
+    {
+        *value = JsonNode::Value(json!([t, "null"]));
+    }
+    node
+}
+
+fn json_schema_type(data_type: &DataType) -> JsonNode {
+    let typed = |t: &str| entry("type", json!(t));
+    let string_with =
+        |key: &str, value: &str| JsonNode::Object(vec![typed("string"), entry(key, json!(value))]);
+    match data_type {
+        DataType::Null => JsonNode::Object(vec![typed("null")]),
+        DataType::Boolean => JsonNode::Object(vec![typed("boolean")]),
+        DataType::Int8
+        | DataType::Int16
+        | DataType::Int32
+        | DataType::Int64
+        | DataType::UInt8
+        | DataType::UInt16
+        | DataType::UInt32
+        | DataType::UInt64 => JsonNode::Object(vec![typed("integer")]),
+        DataType::Float16
+        | DataType::Float32
+        | DataType::Float64
+        | DataType::Decimal32(_, _)
+        | DataType::Decimal64(_, _)
+        | DataType::Decimal128(_, _)
+        | DataType::Decimal256(_, _) => JsonNode::Object(vec![typed("number")]),
+        DataType::Binary
+        | DataType::LargeBinary
+        | DataType::BinaryView
+        | DataType::FixedSizeBinary(_) => string_with("contentEncoding", "base16"),
+        DataType::Date32 | DataType::Date64 => string_with("format", "date"),
+        DataType::Time32(_) | DataType::Time64(_) => string_with("format", "time"),
+        // RFC 3339 date-times need an offset, which only zoned timestamps are written with.
+        DataType::Timestamp(_, Some(_)) => string_with("format", "date-time"),
+        DataType::List(f)
+        | DataType::LargeList(f)
+        | DataType::ListView(f)
+        | DataType::LargeListView(f)
+        | DataType::FixedSizeList(f, _) => JsonNode::Object(vec![
+            typed("array"),
+            ("items".to_string(), json_schema_field(f)),
+        ]),
+        DataType::Struct(fields) => JsonNode::Object(json_schema_object(fields)),
+        DataType::Map(entries, _) => {
+            let value = match entries.data_type() {
+                DataType::Struct(kv) if kv.len() == 2 => json_schema_field(&kv[1]),
+                _ => JsonNode::Object(vec![]),
+            };
+            JsonNode::Object(vec![
+                typed("object"),
+                ("additionalProperties".to_string(), value),
+            ])
+        }
+        DataType::Dictionary(_, value) => json_schema_type(value),
+        DataType::RunEndEncoded(_, values) => json_schema_type(values.data_type()),
+        _ => JsonNode::Object(vec![typed("string")]),
+    }
+}
+
+/// Returns a `CREATE TABLE` statement for `schema` in the given SQL dialect.
+pub fn to_sql(schema: &Schema, table: &str, dialect: SqlDialect) -> Result<String> {
+    let mut columns = Vec::with_capacity(schema.fields().len());
+    for field in schema.fields() {
+        let sql_type = sql_type(field.data_type(), dialect)?;
+        // BigQuery arrays are never null, and cannot be declared NOT NULL.
+        let is_array = dialect == SqlDialect::BigQuery && sql_type.starts_with("ARRAY<");
+        let not_null = if field.is_nullable() || is_array {
+            ""
+        } else {
+            " NOT NULL"
+        };
+        columns.push(format!(
+            "  {} {sql_type}{not_null}",
+            quote_identifier(field.name(), dialect)
+        ));
+    }
+    Ok(format!(
+        "CREATE TABLE {} (\n{}\n);",
+        quote_identifier(table, dialect),
+        columns.join(",\n")
+    ))
+}
+
+fn sql_type(data_type: &DataType, dialect: SqlDialect) -> Result<String> {
+    use SqlDialect::*;
+    let unsupported = || {
+        Error::GenericError(format!(
+            "Arrow type {data_type} has no equivalent in {dialect} SQL"
+        ))
+    };
+    let t = match (dialect, data_type) {
+        (_, DataType::Dictionary(_, value)) => return sql_type(value, dialect),
+        (_, DataType::RunEndEncoded(_, values)) => return sql_type(values.data_type(), dialect),
+
+        (BigQuery, DataType::Boolean) => "BOOL".to_string(),
+        (_, DataType::Boolean) => "BOOLEAN".to_string(),
+
+        (BigQuery, DataType::UInt64) => "NUMERIC".to_string(),
+        (BigQuery, t) if t.is_integer() => "INT64".to_string(),
+        (DuckDb, DataType::Int8) => "TINYINT".to_string(),
+        (DuckDb, DataType::UInt8) => "UTINYINT".to_string(),
+        (DuckDb, DataType::UInt16) => "USMALLINT".to_string(),
+        (DuckDb, DataType::UInt32) => "UINTEGER".to_string(),
+        (DuckDb, DataType::UInt64) => "UBIGINT".to_string(),
+        (_, DataType::Int8 | DataType::Int16 | DataType::UInt8) => "SMALLINT".to_string(),
+        (_, DataType::Int32 | DataType::UInt16) => "INTEGER".to_string(),
+        (_, DataType::Int64 | DataType::UInt32) => "BIGINT".to_string(),
+        (Postgres, DataType::UInt64) => "NUMERIC(20)".to_string(),
+        (Snowflake, DataType::UInt64) => "NUMBER(20, 0)".to_string(),
+
+        (BigQuery, t) if t.is_floating() => "FLOAT64".to_string(),
+        (Postgres, DataType::Float64) => "DOUBLE PRECISION".to_string(),
+        (Postgres, _) if data_type.is_floating() => "REAL".to_string(),
+        (Snowflake, _) if data_type.is_floating() => "FLOAT".to_string(),
+        (DuckDb, DataType::Float64) => "DOUBLE".to_string(),
+        (DuckDb, _) if data_type.is_floating() => "FLOAT".to_string(),
+
+        (
+            _,
+            DataType::Decimal32(p, s)
+            | DataType::Decimal64(p, s)
+            | DataType::Decimal128(p, s)
+            | DataType::Decimal256(p, s),
+        ) => match dialect {
+            // NUMERIC holds at most 29 integer digits and 9 fractional digits.
+            BigQuery if *s <= 9 && i16::from(*p) - i16::from(*s) <= 29 => {
+                format!("NUMERIC({p}, {s})")
+            }
+            BigQuery => format!("BIGNUMERIC({p}, {s})"),
+            Postgres => format!("NUMERIC({p}, {s})"),
+            Snowflake => format!("NUMBER({p}, {s})"),
+            DuckDb => format!("DECIMAL({p}, {s})"),
+        },
+
+        (_, DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View) => match dialect {
+            Postgres => "TEXT",
+            BigQuery => "STRING",
+            Snowflake | DuckDb => "VARCHAR",
+        }
+        .to_string(),
+        (
+            _,
+            DataType::Binary
+            | DataType::LargeBinary
+            | DataType::BinaryView
+            | DataType::FixedSizeBinary(_),
+        ) => match dialect {
+            Postgres => "BYTEA",
+            BigQuery => "BYTES",
+            Snowflake => "BINARY",
+            DuckDb => "BLOB",
+        }
+        .to_string(),
+
+        (_, DataType::Date32 | DataType::Date64) => "DATE".to_string(),
+        (_, DataType::Time32(_) | DataType::Time64(_)) => "TIME".to_string(),
+        (_, DataType::Timestamp(_, tz)) => match (dialect, tz.is_some()) {
+            (Postgres | DuckDb, false) => "TIMESTAMP",
+            (Postgres | DuckDb, true) => "TIMESTAMPTZ",
+            (BigQuery, false) => "DATETIME",
+            (BigQuery, true) => "TIMESTAMP",
+            (Snowflake, false) => "TIMESTAMP_NTZ",
+            (Snowflake, true) => "TIMESTAMP_TZ",
+        }
+        .to_string(),
+        (Snowflake, DataType::Duration(_) | DataType::Interval(_)) => return Err(unsupported()),
+        (_, DataType::Duration(_) | DataType::Interval(_)) => "INTERVAL".to_string(),
+
+        (
+            _,
+            DataType::List(f)
+            | DataType::LargeList(f)
+            | DataType::ListView(f)
+            | DataType::LargeListView(f)
+            | DataType::FixedSizeList(f, _),
+        ) => match dialect {
+            Postgres | DuckDb => format!("{}[]", sql_type(f.data_type(), dialect)?),
+            BigQuery => format!("ARRAY<{}>", sql_type(f.data_type(), dialect)?),
+            Snowflake => "ARRAY".to_string(),
+        },
+        (_, DataType::Struct(fields)) => match dialect {
+            Postgres => "JSONB".to_string(),
+            Snowflake => "OBJECT".to_string(),
+            BigQuery | DuckDb => {
+                let members = fields
+                    .iter()
+                    .map(|f| {
+                        Ok(format!(
+                            "{} {}",
+                            quote_identifier(f.name(), dialect),
+                            sql_type(f.data_type(), dialect)?
+                        ))
+                    })
+                    .collect::<Result<Vec<_>>>()?
+                    .join(", ");
+                if dialect == BigQuery {
+                    format!("STRUCT<{members}>")
+                } else {
+                    format!("STRUCT({members})")
+                }
+            }
+        },
+        (_, DataType::Map(entries, _)) => {
+            let DataType::Struct(kv) = entries.data_type() else {
+                return Err(unsupported());
+            };
+            match dialect {
+                Postgres => "JSONB".to_string(),
+                Snowflake => "OBJECT".to_string(),
+                BigQuery => format!(
+                    "ARRAY<STRUCT<key {}, value {}>>",
+                    sql_type(kv[0].data_type(), dialect)?,
+                    sql_type(kv[1].data_type(), dialect)?
+                ),
+                DuckDb => format!(
+                    "MAP({}, {})",
+                    sql_type(kv[0].data_type(), dialect)?,
+                    sql_type(kv[1].data_type(), dialect)?
+                ),
+            }
+        }
+        (Postgres, DataType::Null) => "TEXT".to_string(),
+        (BigQuery, DataType::Null) => "STRING".to_string(),
+        (_, DataType::Null) => "VARCHAR".to_string(),
+        _ => return Err(unsupported()),
+    };
+    Ok(t)
+}
+
+/// Words that must be quoted to be used as identifiers in any of the supported dialects.
+const SQL_RESERVED_WORDS: &[&str] = &[
+    "all",
+    "and",
+    "any",
+    "array",
+    "as",
+    "asc",
+    "between",
+    "by",
+    "case",
+    "cast",
+    "check",
+    "column",
+    "constraint",
+    "create",
+    "cross",
+    "current",
+    "default",
+    "desc",
+    "distinct",
+    "else",
+    "end",
+    "exists",
+    "false",
+    "for",
+    "foreign",
+    "from",
+    "full",
+    "group",
+    "having",
+    "in",
+    "inner",
+    "interval",
+    "into",
+    "is",
+    "join",
+    "left",
+    "like",
+    "limit",
+    "natural",
+    "not",
+    "null",
+    "of",
+    "offset",
+    "on",
+    "or",
+    "order",
+    "outer",
+    "primary",
+    "references",
+    "right",
+    "rows",
+    "select",
+    "set",
+    "some",
+    "table",
+    "then",
+    "to",
+    "true",
+    "union",
+    "unique",
+    "user",
+    "using",
+    "when",
+    "where",
+    "window",
+    "with",
+];
+
+/// Quotes an identifier if it is not a plain (letters, digits, underscores) name or is a
+/// reserved word.
+fn quote_identifier(name: &str, dialect: SqlDialect) -> String {
+    let plain = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !SQL_RESERVED_WORDS.contains(&name.to_lowercase().as_str());
+    if plain {
+        return name.to_string();
+    }
+    match dialect {
+        SqlDialect::BigQuery => format!("`{}`", name.replace('`', "\\`")),
+        _ => format!("\"{}\"", name.replace('"', "\"\"")),
+    }
+}
+
+/// Returns a proto3 definition of a message `name` with a field for each column of `schema`.
+///
+/// Structs become nested messages, lists repeated fields, and maps `map<>` fields. Nullable
+/// scalars are `optional`. Timestamps and durations use the well-known types; dates, times,
+/// and decimals are strings.
+pub fn to_proto(schema: &Schema, name: &str) -> Result<String> {
+    let mut imports = BTreeSet::new();
+    let mut body = String::new();
+    write_proto_message(
+        &mut body,
+        &proto_message_name(name),
+        schema.fields(),
+        0,
+        &mut imports,
+    )?;
+    let mut out = String::from("syntax = \"proto3\";\n\n");
+    for import in &imports {
+        out.push_str(&format!("import \"{import}\";\n"));
+    }
+    if !imports.is_empty() {
+        out.push('\n');
+    }
+    out.push_str(&body);
+    Ok(out)
+}
+
+fn write_proto_message(
+    out: &mut String,
+    name: &str,
+    fields: &Fields,
+    depth: usize,
+    imports: &mut BTreeSet<&'static str>,
+) -> Result<()> {
+    let indent = "  ".repeat(depth);
+    out.push_str(&format!("{indent}message {name} {{\n"));
+    let mut nested = String::new();
+    for (i, field) in fields.iter().enumerate() {
+        let field_name = proto_field_name(field.name());
+        let (label, proto_type) = match field.data_type() {
+            DataType::List(f)
+            | DataType::LargeList(f)
+            | DataType::ListView(f)
+            | DataType::LargeListView(f)
+            | DataType::FixedSizeList(f, _) => (
+                "repeated ",
+                proto_element_type(f, field.name(), depth + 1, &mut nested, imports)?,
+            ),
+            DataType::Map(entries, _) => {
+                let DataType::Struct(kv) = entries.data_type() else {
+                    return Err(Error::GenericError(format!(
+                        "Map column '{}' has no key and value fields",
+                        field.name()
+                    )));
+                };
+                let key = proto_scalar_type(kv[0].data_type(), imports)
+                    .filter(|k| is_proto_map_key(k))
+                    .ok_or_else(|| {
+                        Error::GenericError(format!(
+                            "Map column '{}' has {} keys, which protobuf maps do not support",
+                            field.name(),
+                            kv[0].data_type()
+                        ))
+                    })?;
+                let value =
+                    proto_element_type(&kv[1], field.name(), depth + 1, &mut nested, imports)?;
+                ("", format!("map<{key}, {value}>"))
+            }
+            DataType::Struct(children) => {
+                let message = proto_message_name(field.name());
+                write_proto_message(&mut nested, &message, children, depth + 1, imports)?;
+                ("", message)
+            }
+            data_type => {
+                let proto_type = proto_scalar_type(data_type, imports).ok_or_else(|| {
+                    Error::GenericError(format!(
+                        "Column '{}' has type {data_type}, which has no protobuf equivalent",
+                        field.name()
+                    ))
+                })?;
+                let label = if field.is_nullable() && !proto_type.contains('.') {
+                    "optional "
+                } else {
+                    ""
+                };
+                (label, proto_type)
+            }
+        };
+        out.push_str(&format!(
+            "{indent}  {label}{proto_type} {field_name} = {};\n",
+            i + 1
+        ));
+    }
+    if !nested.is_empty() {
+        out.push('\n');
+        out.push_str(&nested);
+    }
+    out.push_str(&format!("{indent}}}\n"));
+    Ok(())
+}
+
+/// Returns the type of a list element or map value, defining a nested message for structs (or
+/// for nested lists, which protobuf cannot repeat directly).
+fn proto_element_type(
+    field: &Field,
+    parent: &str,
+    depth: usize,
+    nested: &mut String,
+    imports: &mut BTreeSet<&'static str>,
+) -> Result<String> {
+    match field.data_type() {
+        DataType::Struct(children) => {
+            let message = proto_message_name(&format!("{parent}_item"));
+            write_proto_message(nested, &message, children, depth, imports)?;
+            Ok(message)
+        }
+        DataType::List(_)
+        | DataType::LargeList(_)
+        | DataType::ListView(_)
+        | DataType::LargeListView(_)
+        | DataType::FixedSizeList(_, _)
+        | DataType::Map(_, _) => {
+            let message = proto_message_name(&format!("{parent}_item"));
+            let wrapper = Fields::from(vec![field.clone().with_name("values")]);
+            write_proto_message(nested, &message, &wrapper, depth, imports)?;
+            Ok(message)
+        }
+        data_type => proto_scalar_type(data_type, imports).ok_or_else(|| {
+            Error::GenericError(format!(
+                "Column '{parent}' has elements of type {data_type}, which has no protobuf equivalent"
+            ))
+        }),
+    }
+}
+
+fn proto_scalar_type(data_type: &DataType, imports: &mut BTreeSet<&'static str>) -> Option<String> {
+    let t = match data_type {
+        DataType::Boolean => "bool",
+        DataType::Int8 | DataType::Int16 | DataType::Int32 => "int32",
+        DataType::Int64 => "int64",
+        DataType::UInt8 | DataType::UInt16 | DataType::UInt32 => "uint32",
+        DataType::UInt64 => "uint64",
+        DataType::Float16 | DataType::Float32 => "float",
+        DataType::Float64 => "double",
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => "string",
+        DataType::Binary
+        | DataType::LargeBinary
+        | DataType::BinaryView
+        | DataType::FixedSizeBinary(_) => "bytes",
+        DataType::Decimal32(_, _)
+        | DataType::Decimal64(_, _)
+        | DataType::Decimal128(_, _)
+        | DataType::Decimal256(_, _)
+        | DataType::Date32
+        | DataType::Date64
+        | DataType::Time32(_)
+        | DataType::Time64(_) => "string",
+        DataType::Timestamp(_, _) => {
+            imports.insert("google/protobuf/timestamp.proto");
+            "google.protobuf.Timestamp"
+        }
+        DataType::Duration(_) => {
+            imports.insert("google/protobuf/duration.proto");
+            "google.protobuf.Duration"
+        }
+        DataType::Dictionary(_, value) => return proto_scalar_type(value, imports),
+        _ => return None,
+    };
+    Some(t.to_string())
+}
+
+fn is_proto_map_key(proto_type: &str) -> bool {
+    matches!(
+        proto_type,
+        "bool" | "int32" | "int64" | "uint32" | "uint64" | "string"
+    )
+}
+
+/// Turns a column name into a valid protobuf field name.
+fn proto_field_name(name: &str) -> String {
+    let mut out: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if !out.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        out.insert_str(0, "f_");
+    }
+    out
+}
+
+/// Turns a name such as `user_address` into a message name such as `UserAddress`.
+fn proto_message_name(name: &str) -> String {
+    let mut out = String::new();
+    for part in name.split(|c: char| !c.is_ascii_alphanumeric()) {
+        let mut chars = part.chars();
+        if let Some(first) = chars.next() {
+            out.extend(first.to_uppercase());
+            out.push_str(chars.as_str());
+        }
+    }
+    if !out.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        out.insert(0, 'M');
+    }
+    out
+}
+
+/// Writes `schema` as an Arrow IPC stream with no record batches, which any Arrow
+/// implementation can read the schema from (e.g. `pyarrow.ipc.read_schema`).
+pub fn write_arrow_ipc_schema<W: Write>(schema: &Schema, writer: W) -> Result<()> {
+    let mut writer = StreamWriter::try_new(writer, schema)?;
+    writer.finish()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::datatypes::TimeUnit;
+    use arrow::ipc::reader::StreamReader;
+
+    use super::*;
+
+    fn schema() -> Schema {
+        let address = Fields::from(vec![
+            Field::new("city", DataType::Utf8, true),
+            Field::new("zip", DataType::Utf8, true),
+        ]);
+        Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("price", DataType::Decimal128(10, 2), true),
+            Field::new(
+                "created",
+                DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into())),
+                false,
+            ),
+            Field::new_list("tags", Field::new_list_field(DataType::Utf8, true), true),
+            Field::new("address", DataType::Struct(address), true),
+            Field::new("order", DataType::Int32, true),
+        ])
+    }
+
+    #[test]
+    fn test_to_sql_dialects() {
+        assert_eq!(
+            to_sql(&schema(), "items", SqlDialect::Postgres).unwrap(),
+            "CREATE TABLE items (\n  id BIGINT NOT NULL,\n  name TEXT,\n  price NUMERIC(10, 2),\n  \
+             created TIMESTAMPTZ NOT NULL,\n  tags TEXT[],\n  address JSONB,\n  \"order\" INTEGER\n);"
+        );
+        let bigquery = to_sql(&schema(), "items", SqlDialect::BigQuery).unwrap();
+        assert!(bigquery.contains("  id INT64 NOT NULL,"));
+        assert!(bigquery.contains("  created TIMESTAMP NOT NULL,"));
+        assert!(bigquery.contains("  tags ARRAY<STRING>,"));
+        assert!(bigquery.contains("  address STRUCT<city STRING, zip STRING>,"));
+        assert!(bigquery.contains("  `order` INT64"));
+        let snowflake = to_sql(&schema(), "items", SqlDialect::Snowflake).unwrap();
+        assert!(snowflake.contains("  price NUMBER(10, 2),"));
+        assert!(snowflake.contains("  created TIMESTAMP_TZ NOT NULL,"));
+        assert!(snowflake.contains("  address OBJECT,"));
+        let duckdb = to_sql(&schema(), "items", SqlDialect::DuckDb).unwrap();
+        assert!(duckdb.contains("  tags VARCHAR[],"));
+        assert!(duckdb.contains("  address STRUCT(city VARCHAR, zip VARCHAR),"));
+    }
+
+    #[test]
+    fn test_bigquery_decimals() {
+        let sql_type = |p, s| {
+            let schema = Schema::new(vec![Field::new("d", DataType::Decimal128(p, s), true)]);
+            to_sql(&schema, "t", SqlDialect::BigQuery).unwrap()
+        };
+        assert!(sql_type(10, 2).contains("d NUMERIC(10, 2)"));
+        assert!(sql_type(38, 9).contains("d NUMERIC(38, 9)"));
+        assert!(sql_type(38, 2).contains("d BIGNUMERIC(38, 2)"));
+        assert!(sql_type(38, 0).contains("d BIGNUMERIC(38, 0)"));
+        assert!(sql_type(29, 0).contains("d NUMERIC(29, 0)"));
+        assert!(sql_type(20, 10).contains("d BIGNUMERIC(20, 10)"));
+    }
+
+    #[test]
+    fn test_to_json_schema() {
+        let json: Value =
+            serde_json::from_str(&to_json_schema(&schema(), "items").unwrap()).unwrap();
+        assert_eq!(json["title"], "items");
+        assert_eq!(json["required"], json!(["id", "created"]));
+        assert_eq!(json["properties"]["id"], json!({"type": "integer"}));
+        assert_eq!(
+            json["properties"]["created"],
+            json!({"type": "string", "format": "date-time"})
+        );
+        assert_eq!(
+            json["properties"]["tags"],
+            json!({"type": ["array", "null"], "items": {"type": ["string", "null"]}})
+        );
+        assert_eq!(
+            json["properties"]["address"]["properties"]["city"],
+            json!({"type": ["string", "null"]})
+        );
+    }
+
+    #[test]
+    fn test_to_proto() {
+        let proto = to_proto(&schema(), "line_items").unwrap();
+        assert!(
+            proto.starts_with(
+                "syntax = \"proto3\";\n\nimport \"google/protobuf/timestamp.proto\";\n"
+            )
+        );
+        assert!(
+            proto.contains("message LineItems {\n  int64 id = 1;\n  optional string name = 2;\n")
+        );
+        assert!(proto.contains("  google.protobuf.Timestamp created = 4;\n"));
+        assert!(proto.contains("  repeated string tags = 5;\n"));
+        assert!(proto.contains("  Address address = 6;\n"));
+        assert!(proto.contains("  message Address {\n    optional string city = 1;\n"));
+    }
+
+    #[test]
+    fn test_to_avsc_name() {
+        let json: Value = serde_json::from_str(&to_avsc(&schema(), Some("item")).unwrap()).unwrap();
+        assert_eq!(json["type"], "record");
+        assert_eq!(json["name"], "item");
+        assert_eq!(json["fields"][0]["name"], "id");
+    }
+
+    #[test]
+    fn test_write_arrow_ipc_schema() {
+        let mut out = Vec::new();
+        write_arrow_ipc_schema(&schema(), &mut out).unwrap();
+        let reader = StreamReader::try_new(out.as_slice(), None).unwrap();
+        assert_eq!(reader.schema(), Arc::new(schema()));
+    }
+}
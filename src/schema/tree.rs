@@ -0,0 +1,357 @@
+//! A nested view of a schema: each field with its type, repetition, and children, for
+//! `datu schema --tree`.
+//!
+//! Parquet schemas are shown as stored, with their groups, `LIST`/`MAP` annotations, and
+//! physical and logical types. Avro and ORC schemas are shown as their Arrow equivalent.
+
+use std::io::Write;
+
+use arrow::datatypes::DataType;
+use arrow::datatypes::Field;
+use arrow::datatypes::Schema;
+use parquet::basic::ConvertedType;
+use parquet::basic::DecimalType;
+use parquet::basic::IntType;
+use parquet::basic::LogicalType;
+use parquet::basic::Repetition;
+use parquet::basic::TimeType;
+use parquet::basic::TimeUnit;
+use parquet::basic::TimestampType;
+use parquet::schema::types::Type;
+use serde::Serialize;
+use serde::Serializer;
+use serde::ser::SerializeMap;
+
+/// A field in a schema tree.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SchemaNode {
+    pub name: String,
+    /// The Parquet physical type (`group` for groups), or the Arrow type.
+    pub data_type: String,
+    /// The Parquet logical type, if any.
+    pub logical_type: Option<String>,
+    /// The legacy Parquet converted type, if any.
+    pub converted_type: Option<String>,
+    /// `required`, `optional`, or `repeated`.
+    pub repetition: &'static str,
+    pub children: Vec<SchemaNode>,
+}
+
+impl SchemaNode {
+    /// Returns the node's type as shown in the tree, e.g. `BYTE_ARRAY / String` or `Int64`.
+    fn describe_type(&self) -> String {
+        match (&self.logical_type, &self.converted_type) {
+            (Some(logical), _) => format!("{} / {logical}", self.data_type),
+            (None, Some(converted)) => format!("{} ({converted})", self.data_type),
+            (None, None) => self.data_type.clone(),
+        }
+    }
+}
+
+/// Serializes a [`SchemaNode`] and its children; when `sparse` is true, omits unset types
+/// and empty child lists.
+pub struct SerializeSchemaNode<'a> {
+    pub node: &'a SchemaNode,
+    pub sparse: bool,
+}
+
+impl Serialize for SerializeSchemaNode<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let node = self.node;
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("name", &node.name)?;
+        map.serialize_entry("data_type", &node.data_type)?;
+        if !self.sparse || node.logical_type.is_some() {
+            map.serialize_entry("logical_type", &node.logical_type)?;
+        }
+        if !self.sparse || node.converted_type.is_some() {
+            map.serialize_entry("converted_type", &node.converted_type)?;
+        }
+        map.serialize_entry("repetition", node.repetition)?;
+        if !self.sparse || !node.children.is_empty() {
+            let children: Vec<SerializeSchemaNode> = node
+                .children
+                .iter()
+                .map(|node| SerializeSchemaNode {
+                    node,
+                    sparse: self.sparse,
+                })
+                .collect();
+            map.serialize_entry("children", &children)?;
+        }
+        map.end()
+    }
+}
+
+/// Returns the fields of a Parquet schema (the children of its root group).
+pub fn from_parquet(root: &Type) -> Vec<SchemaNode> {
+    root.get_fields()
+        .iter()
+        .map(|field| parquet_node(field))
+        .collect()
+}
+
+fn parquet_node(field: &Type) -> SchemaNode {
+    let info = field.get_basic_info();
+    let (data_type, children) = match field {
+        Type::PrimitiveType {
+            physical_type,
+            type_length,
+            ..
+        } => {
+            let data_type = match physical_type {
+                parquet::basic::Type::FIXED_LEN_BYTE_ARRAY => {
+                    format!("{physical_type}({type_length})")
+                }
+                _ => physical_type.to_string(),
+            };
+            (data_type, Vec::new())
+        }
+        Type::GroupType { fields, .. } => (
+            "group".to_string(),
+            fields.iter().map(|f| parquet_node(f)).collect(),
+        ),
+    };
+    let repetition = if info.has_repetition() {
+        match info.repetition() {
+            Repetition::REQUIRED => "required",
+            Repetition::OPTIONAL => "optional",
+            Repetition::REPEATED => "repeated",
+        }
+    } else {
+        "required"
+    };
+    let converted_type = match info.converted_type() {
+        ConvertedType::NONE => None,
+        converted => Some(format!("{converted:?}")),
+    };
+    SchemaNode {
+        name: info.name().to_string(),
+        data_type,
+        logical_type: info.logical_type_ref().map(format_logical_type),
+        converted_type,
+        repetition,
+        children,
+    }
+}
+
+/// Formats a Parquet logical type tersely, e.g. `Timestamp(us, utc)` or `Decimal(10, 2)`.
+fn format_logical_type(logical_type: &LogicalType) -> String {
+    let unit = |unit: &TimeUnit| match unit {
+        TimeUnit::MILLIS => "ms",
+        TimeUnit::MICROS => "us",
+        TimeUnit::NANOS => "ns",
+    };
+    let utc = |adjusted: bool| if adjusted { ", utc" } else { "" };
+    match logical_type {
+        LogicalType::Decimal(DecimalType { scale, precision }) => {
+            format!("Decimal({precision}, {scale})")
+        }
+        LogicalType::Time(TimeType {
+            is_adjusted_to_u_t_c,
+            unit: u,
+        }) => format!("Time({}{})", unit(u), utc(*is_adjusted_to_u_t_c)),
+        LogicalType::Timestamp(TimestampType {
+            is_adjusted_to_u_t_c,
+            unit: u,
+        }) => format!("Timestamp({}{})", unit(u), utc(*is_adjusted_to_u_t_c)),
+        LogicalType::Integer(IntType {
+            bit_width,
+            is_signed,
+        }) => {
+            let sign = if *is_signed { "signed" } else { "unsigned" };
+            format!("Integer({bit_width}, {sign})")
+        }
+        LogicalType::Variant { .. } => "Variant".to_string(),
+        LogicalType::Geometry { .. } => "Geometry".to_string(),
+        LogicalType::Geography { .. } => "Geography".to_string(),
+        LogicalType::_Unknown { .. } => "Unknown".to_string(),
+        other => format!("{other:?}"),
+    }
+}
+
+/// Returns the fields of an Arrow schema.
+pub fn from_arrow(schema: &Schema) -> Vec<SchemaNode> {
+    schema.fields().iter().map(|f| arrow_node(f)).collect()
+}
+
+fn arrow_node(field: &Field) -> SchemaNode {
+    let (data_type, children) = match field.data_type() {
+        DataType::Struct(fields) => (
+            "Struct".to_string(),
+            fields.iter().map(|f| arrow_node(f)).collect(),
+        ),
+        DataType::List(f) => ("List".to_string(), vec![arrow_node(f)]),
+        DataType::LargeList(f) => ("LargeList".to_string(), vec![arrow_node(f)]),
+        DataType::ListView(f) => ("ListView".to_string(), vec![arrow_node(f)]),
+        DataType::LargeListView(f) => ("LargeListView".to_string(), vec![arrow_node(f)]),
+        DataType::FixedSizeList(f, size) => (format!("FixedSizeList({size})"), vec![arrow_node(f)]),
+        // The key and value, without the entries struct that holds them.
+        DataType::Map(entries, _) => {
+            let children = match entries.data_type() {
+                DataType::Struct(fields) => fields.iter().map(|f| arrow_node(f)).collect(),
+                _ => vec![arrow_node(entries)],
+            };
+            ("Map".to_string(), children)
+        }
+        DataType::Union(fields, _) => (
+            "Union".to_string(),
+            fields.iter().map(|(_, f)| arrow_node(f)).collect(),
+        ),
+        data_type => (data_type.to_string(), Vec::new()),
+    };
+    SchemaNode {
+        name: field.name().clone(),
+        data_type,
+        logical_type: None,
+        converted_type: None,
+        repetition: if field.is_nullable() {
+            "optional"
+        } else {
+            "required"
+        },
+        children,
+    }
+}
+
+/// Writes the nodes as an indented tree under a `root` line, one field per line.
+pub fn write_tree<W: Write>(nodes: &[SchemaNode], root: &str, mut w: W) -> std::io::Result<()> {
+    writeln!(w, "{root}")?;
+    write_children(nodes, "", &mut w)
+}
+
+fn write_children<W: Write>(nodes: &[SchemaNode], prefix: &str, w: &mut W) -> std::io::Result<()> {
+    for (i, node) in nodes.iter().enumerate() {
+        let last = i + 1 == nodes.len();
+        let (branch, indent) = if last {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+        writeln!(
+            w,
+            "{prefix}{branch}{}: {}, {}",
+            node.name,
+            node.describe_type(),
+            node.repetition
+        )?;
+        write_children(&node.children, &format!("{prefix}{indent}"), w)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::array::RecordBatch;
+    use parquet::arrow::ArrowWriter;
+    use parquet::file::metadata::ParquetMetaDataReader;
+
+    use super::*;
+
+    fn nested_schema() -> Schema {
+        let address = DataType::Struct(
+            vec![
+                Field::new("city", DataType::Utf8, true),
+                Field::new("zip", DataType::Int32, false),
+            ]
+            .into(),
+        );
+        Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new_list("tags", Field::new_list_field(DataType::Utf8, true), true),
+            Field::new("address", address, true),
+            Field::new_map(
+                "scores",
+                "entries",
+                Field::new("key", DataType::Utf8, false),
+                Field::new("value", DataType::Float64, true),
+                false,
+                true,
+            ),
+        ])
+    }
+
+    fn render(nodes: &[SchemaNode]) -> String {
+        let mut out = Vec::new();
+        write_tree(nodes, "root", &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_arrow_tree() {
+        assert_eq!(
+            render(&from_arrow(&nested_schema())),
+            "root\n\
+             ├── id: Int64, required\n\
+             ├── tags: List, optional\n\
+             │   └── item: Utf8, optional\n\
+             ├── address: Struct, optional\n\
+             │   ├── city: Utf8, optional\n\
+             │   └── zip: Int32, required\n\
+             └── scores: Map, optional\n    \
+             ├── key: Utf8, required\n    \
+             └── value: Float64, optional\n"
+        );
+    }
+
+    #[test]
+    fn test_parquet_tree() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("nested.parquet");
+        let schema = Arc::new(nested_schema());
+        let mut writer =
+            ArrowWriter::try_new(std::fs::File::create(&path).unwrap(), schema.clone(), None)
+                .unwrap();
+        writer.write(&RecordBatch::new_empty(schema)).unwrap();
+        writer.close().unwrap();
+
+        let file = std::fs::File::open(&path).unwrap();
+        let metadata = ParquetMetaDataReader::new()
+            .parse_and_finish(&file)
+            .unwrap();
+        let root = metadata.file_metadata().schema_descr().root_schema();
+        assert_eq!(
+            render(&from_parquet(root)),
+            "root\n\
+             ├── id: INT64, required\n\
+             ├── tags: group / List, optional\n\
+             │   └── list: group, repeated\n\
+             │       └── item: BYTE_ARRAY / String, optional\n\
+             ├── address: group, optional\n\
+             │   ├── city: BYTE_ARRAY / String, optional\n\
+             │   └── zip: INT32, required\n\
+             └── scores: group / Map, optional\n    \
+             └── entries: group, repeated\n        \
+             ├── key: BYTE_ARRAY / String, required\n        \
+             └── value: DOUBLE, optional\n"
+        );
+    }
+
+    #[test]
+    fn test_serialize_sparse() {
+        let nodes = from_arrow(&nested_schema());
+        let json = |sparse| {
+            serde_json::to_value(SerializeSchemaNode {
+                node: &nodes[1],
+                sparse,
+            })
+            .unwrap()
+        };
+        assert_eq!(
+            json(true),
+            serde_json::json!({
+                "name": "tags",
+                "data_type": "List",
+                "repetition": "optional",
+                "children": [{"name": "item", "data_type": "Utf8", "repetition": "optional"}]
+            })
+        );
+        assert_eq!(
+            json(false)["children"][0]["children"],
+            serde_json::json!([])
+        );
+        assert_eq!(json(false)["logical_type"], serde_json::Value::Null);
+    }
+}
@@ -36,14 +36,66 @@ pub fn parse_select_columns(select: &[String]) -> Vec<String> {
     columns
 }
 
+/// Parse a byte size such as `256MB`, `1.5GiB`, or `1000`. Decimal suffixes (KB, MB, GB, TB)
+/// are powers of 1000 and binary suffixes (KiB, MiB, GiB, TiB) powers of 1024; case is ignored.
+pub fn parse_byte_size(s: &str) -> Result<usize, String> {
+    let trimmed = s.trim();
+    let split = trimmed
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(trimmed.len());
+    let (number, unit) = trimmed.split_at(split);
+    let multiplier: u64 = match unit.trim().to_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kb" => 1_000,
+        "m" | "mb" => 1_000_000,
+        "g" | "gb" => 1_000_000_000,
+        "t" | "tb" => 1_000_000_000_000,
+        "kib" => 1 << 10,
+        "mib" => 1 << 20,
+        "gib" => 1 << 30,
+        "tib" => 1 << 40,
+        _ => {
+            return Err(format!(
+                "unknown size unit in '{s}', expected e.g. 500KB, 256MB, or 1GiB"
+            ));
+        }
+    };
+    let number: f64 = number
+        .parse()
+        .map_err(|_| format!("invalid size '{s}', expected e.g. 500KB, 256MB, or 1GiB"))?;
+    let bytes = (number * multiplier as f64) as usize;
+    if bytes == 0 {
+        return Err(format!("size '{s}' must be greater than zero"));
+    }
+    Ok(bytes)
+}
+
+/// Parse a CSV field delimiter: a single ASCII character, or `tab` / `\\t` for a tab.
+pub fn parse_delimiter(s: &str) -> Result<u8, String> {
+    match s {
+        "tab" | "\\t" | "\t" => Ok(b'\t'),
+        _ if s.len() == 1 && s.is_ascii() && s != "\n" && s != "\r" && s != "\"" => {
+            Ok(s.as_bytes()[0])
+        }
+        _ => Err(format!(
+            "invalid delimiter '{s}', expected a single ASCII character other than a quote or line break, or tab"
+        )),
+    }
+}
+
 /// A supported input or output file type
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum FileType {
     Avro,
     Csv,
+    Html,
     Json,
+    Markdown,
+    /// JSON Lines: one JSON object per line.
+    Ndjson,
     Orc,
     Parquet,
+    Tsv,
     Xlsx,
     Yaml,
 }
@@ -60,12 +112,16 @@ impl TryFrom<&str> for FileType {
         {
             let file_type = match s.to_lowercase().as_str() {
                 "json" => FileType::Json,
+                "ndjson" | "jsonl" => FileType::Ndjson,
                 "csv" => FileType::Csv,
+                "tsv" | "tab" => FileType::Tsv,
                 "parq" | "parquet" => FileType::Parquet,
                 "orc" => FileType::Orc,
                 "avro" => FileType::Avro,
                 "xlsx" => FileType::Xlsx,
                 "yaml" | "yml" => FileType::Yaml,
+                "md" | "markdown" => FileType::Markdown,
+                "html" | "htm" => FileType::Html,
                 _ => return Err(crate::Error::UnknownFileType(s.to_owned())),
             };
             return Ok(file_type);
@@ -102,9 +158,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_byte_size() {
+        assert_eq!(parse_byte_size("1000"), Ok(1000));
+        assert_eq!(parse_byte_size("256MB"), Ok(256_000_000));
+        assert_eq!(parse_byte_size("256 mb"), Ok(256_000_000));
+        assert_eq!(parse_byte_size("1.5KiB"), Ok(1536));
+        assert_eq!(parse_byte_size("2GiB"), Ok(2 << 30));
+        assert!(parse_byte_size("12XB").is_err());
+        assert!(parse_byte_size("MB").is_err());
+        assert!(parse_byte_size("0").is_err());
+    }
+
+    #[test]
+    fn test_ndjson_file_type() {
+        assert_eq!(
+            FileType::try_from("events.ndjson").unwrap(),
+            FileType::Ndjson
+        );
+        assert_eq!(
+            FileType::try_from("events.JSONL").unwrap(),
+            FileType::Ndjson
+        );
+    }
+
+    #[test]
+    fn test_parse_delimiter() {
+        assert_eq!(parse_delimiter("|"), Ok(b'|'));
+        assert_eq!(parse_delimiter(";"), Ok(b';'));
+        assert_eq!(parse_delimiter("tab"), Ok(b'\t'));
+        assert_eq!(parse_delimiter("\\t"), Ok(b'\t'));
+        assert!(parse_delimiter("||").is_err());
+        assert!(parse_delimiter("\"").is_err());
+        assert!(parse_delimiter("é").is_err());
+    }
+
     #[test]
     fn test_valid_extensions() {
         assert_eq!(FileType::try_from("test.csv").unwrap(), FileType::Csv);
+        assert_eq!(FileType::try_from("test.tsv").unwrap(), FileType::Tsv);
         assert_eq!(FileType::try_from("data.json").unwrap(), FileType::Json);
         assert_eq!(FileType::try_from("file.parq").unwrap(), FileType::Parquet);
         assert_eq!(FileType::try_from("data.orc").unwrap(), FileType::Orc);
@@ -112,6 +204,9 @@ mod tests {
         assert_eq!(FileType::try_from("data.xlsx").unwrap(), FileType::Xlsx);
         assert_eq!(FileType::try_from("data.yaml").unwrap(), FileType::Yaml);
         assert_eq!(FileType::try_from("data.yml").unwrap(), FileType::Yaml);
+        assert_eq!(FileType::try_from("sample.md").unwrap(), FileType::Markdown);
+        assert_eq!(FileType::try_from("sample.html").unwrap(), FileType::Html);
+        assert_eq!(FileType::try_from("sample.htm").unwrap(), FileType::Html);
     }
 
     #[test]
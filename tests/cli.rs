@@ -74,6 +74,17 @@ fn command_should_succeed(world: &mut CliWorld) {
     );
 }
 
+#[then(regex = r#"^the command should fail$"#)]
+fn command_should_fail(world: &mut CliWorld) {
+    let output = world.output.as_ref().expect("No output captured");
+    assert!(
+        !output.status.success(),
+        "Command succeeded but was expected to fail:\nstdout: {}\nstderr: {}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
 #[then(regex = r#"^the first line should contain "(.+)"$"#)]
 fn first_line_should_contain(world: &mut CliWorld, expected: String) {
     let output = world.output.as_ref().expect("No output captured");
@@ -248,6 +259,21 @@ fn file_should_have_n_lines(world: &mut CliWorld, path: String, n: usize) {
     );
 }
 
+#[then(regex = r#"^the file "(.+)" should be at most (\d+) bytes$"#)]
+fn file_should_be_at_most_n_bytes(world: &mut CliWorld, path: String, n: u64) {
+    let path_resolved = resolve_path(world, &path);
+    let size = std::fs::metadata(&path_resolved)
+        .expect("Failed to read file metadata")
+        .len();
+    assert!(
+        size <= n,
+        "Expected file {} to be at most {} bytes, but it is {}",
+        path_resolved,
+        n,
+        size
+    );
+}
+
 #[then(regex = r#"^the first line of that file should contain "(.+)"$"#)]
 fn first_line_of_that_file_should_contain(world: &mut CliWorld, expected: String) {
     let path_resolved = world